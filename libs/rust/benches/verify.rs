@@ -0,0 +1,93 @@
+// Copyright 2024 Horizen Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0 or MIT
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use ark_ff::PrimeField;
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use ultraplonk_no_std::{
+    key::{PreparedVerificationKey, VerificationKey},
+    proof::Proof,
+    verify, verify_deferred, verify_prepared, verify_prepared_with_srs, verify_with_srs, Fr,
+    PreparedSrs,
+};
+
+static VK: &[u8] = include_bytes!("../tests/resources/v0.33.0/vk.bin");
+static BB_OUTPUT: &[u8] = include_bytes!("../tests/resources/v0.33.0/proof.bin");
+
+fn bench_verify(c: &mut Criterion) {
+    let vk = VerificationKey::<()>::try_from(VK).unwrap();
+    let raw_vk = vk.as_solidity_bytes();
+    let (pubs, proof) = Proof::<()>::from_bb_output(BB_OUTPUT, &vk).unwrap();
+    let raw_proof = &BB_OUTPUT[BB_OUTPUT.len() - ultraplonk_no_std::PROOF_SIZE..];
+
+    let prepared_vk = PreparedVerificationKey::try_from(&vk).unwrap();
+    let prepared_vk_with_tables = PreparedVerificationKey::try_from(&vk)
+        .unwrap()
+        .with_fixed_base_tables(8)
+        .unwrap();
+    let prepared_srs = PreparedSrs::<()>::default();
+    let typed_pubs = pubs
+        .iter()
+        .map(|pi| Fr::from_be_bytes_mod_order(pi))
+        .collect::<Vec<_>>();
+
+    let mut group = c.benchmark_group("verify");
+    group.bench_function("prepare_srs", |b| {
+        b.iter(|| black_box(PreparedSrs::<()>::default()))
+    });
+    group.bench_function("raw", |b| {
+        b.iter(|| verify::<()>(black_box(&raw_vk), black_box(raw_proof), black_box(&pubs)))
+    });
+    group.bench_function("raw_with_prepared_srs", |b| {
+        b.iter(|| {
+            verify_with_srs::<()>(
+                black_box(&raw_vk),
+                black_box(raw_proof),
+                black_box(&pubs),
+                &prepared_srs,
+            )
+        })
+    });
+    group.bench_function("deferred", |b| {
+        b.iter(|| verify_deferred(&(), &prepared_vk, black_box(&proof), black_box(&typed_pubs)))
+    });
+    group.bench_function("prepared_vk", |b| {
+        b.iter(|| verify_prepared(&(), &prepared_vk, black_box(&proof), black_box(&typed_pubs)))
+    });
+    group.bench_function("prepared_vk_with_fixed_base_tables", |b| {
+        b.iter(|| {
+            verify_prepared(
+                &(),
+                &prepared_vk_with_tables,
+                black_box(&proof),
+                black_box(&typed_pubs),
+            )
+        })
+    });
+    group.bench_function("prepared_vk_and_srs", |b| {
+        b.iter(|| {
+            verify_prepared_with_srs(
+                &(),
+                &prepared_vk,
+                black_box(&proof),
+                black_box(&typed_pubs),
+                &prepared_srs,
+            )
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_verify);
+criterion_main!(benches);
@@ -0,0 +1,195 @@
+// Copyright 2024 Horizen Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0 or MIT
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use alloc::vec::Vec;
+
+use ark_bn254_ext::CurveHooks;
+use ark_ec::{AdditiveGroup, AffineRepr, CurveGroup};
+use ark_ff::{Field, PrimeField};
+use ark_std::{rand::RngCore, UniformRand};
+
+use crate::{
+    check_public_input_number,
+    errors::{GroupError, VerifyError},
+    hooks::VerifierHooks,
+    key::PreparedVerificationKey,
+    pairing_check, prepare_pairing_points,
+    proof::{PreparedProof, Proof},
+    srs::{PreparedSrs, Srs},
+    utils::{read_g1_util, IntoBytes},
+    DecodingMode, Fq, Fr, Keccak256Transcript, Transcript, G1, G2, U256,
+};
+
+pub const ACCUMULATOR_SIZE: usize = 128;
+pub const EVM_PAIRING_INPUT_SIZE: usize = 384;
+
+/// The `(PAIRING_LHS, PAIRING_RHS)` couple a proof reduces to right before the final pairing.
+///
+/// A proof is valid iff `e(rhs, [1]_2) * e(lhs, [x]_2) == 1`, where `lhs` is already negated.
+/// Accumulators of different proofs can be merged and settled later with a single pairing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PairingAccumulator<H: CurveHooks> {
+    pub lhs: G1<H>,
+    pub rhs: G1<H>,
+}
+
+impl<H: CurveHooks> PairingAccumulator<H> {
+    /// Folds `other` into `self` as `self + r * other`, with `r` drawn from `rng`.
+    pub fn merge<R: RngCore>(
+        &self,
+        hooks: &H,
+        other: &Self,
+        rng: &mut R,
+    ) -> Result<Self, VerifyError>
+    where
+        H: VerifierHooks,
+    {
+        let scalars = [Fr::ONE, Fr::rand(rng)];
+        let lhs = hooks
+            .msm_g1(&[self.lhs, other.lhs], &scalars)
+            .map_err(|_| VerifyError::CurveHooksError)?;
+        let rhs = hooks
+            .msm_g1(&[self.rhs, other.rhs], &scalars)
+            .map_err(|_| VerifyError::CurveHooksError)?;
+
+        Ok(Self {
+            lhs: lhs.into_affine(),
+            rhs: rhs.into_affine(),
+        })
+    }
+
+    /// Performs the pairing check against the Ignition `[x]_2` point.
+    pub fn finalize(&self, hooks: &H) -> Result<(), VerifyError>
+    where
+        H: VerifierHooks,
+    {
+        self.finalize_with_srs(hooks, &PreparedSrs::default())
+    }
+
+    /// Performs the pairing check against the prepared `[x]_2` point of `srs`.
+    pub fn finalize_with_srs(&self, hooks: &H, srs: &PreparedSrs<H>) -> Result<(), VerifyError>
+    where
+        H: VerifierHooks,
+    {
+        if pairing_check(hooks, self.lhs, self.rhs, srs)? {
+            Ok(())
+        } else {
+            Err(VerifyError::VerificationError)
+        }
+    }
+
+    /// Serializes the accumulator as `lhs.x || lhs.y || rhs.x || rhs.y`, big-endian.
+    pub fn to_bytes(&self) -> [u8; ACCUMULATOR_SIZE] {
+        let mut out = [0u8; ACCUMULATOR_SIZE];
+        out[..64].copy_from_slice(&g1_to_bytes::<H>(&self.lhs));
+        out[64..].copy_from_slice(&g1_to_bytes::<H>(&self.rhs));
+        out
+    }
+
+    /// Encodes the pairing check as the input of the EVM `ecPairing` precompile (`0x08`):
+    /// `rhs || [1]_2 || lhs || [x]_2`, with G2 coordinates in `(c1, c0)` order, `[x]_2`
+    /// being the Ignition point.
+    pub fn to_evm_pairing_input(&self) -> [u8; EVM_PAIRING_INPUT_SIZE] {
+        self.to_evm_pairing_input_with_srs(&Srs::ignition())
+    }
+
+    /// Same as [`Self::to_evm_pairing_input`], with the `[x]_2` point of `srs`: it must be
+    /// the one the accumulator would be finalized against.
+    pub fn to_evm_pairing_input_with_srs(&self, srs: &Srs<H>) -> [u8; EVM_PAIRING_INPUT_SIZE] {
+        let mut out = [0u8; EVM_PAIRING_INPUT_SIZE];
+        out[..64].copy_from_slice(&g1_to_bytes::<H>(&self.rhs));
+        out[64..192].copy_from_slice(&g2_to_evm_bytes::<H>(&G2::<H>::generator()));
+        out[192..256].copy_from_slice(&g1_to_bytes::<H>(&self.lhs));
+        out[256..].copy_from_slice(&g2_to_evm_bytes::<H>(srs.g2()));
+        out
+    }
+}
+
+impl<H: CurveHooks> TryFrom<&[u8]> for PairingAccumulator<H> {
+    type Error = GroupError;
+
+    fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
+        if data.len() != ACCUMULATOR_SIZE {
+            return Err(GroupError::InvalidSliceLength {
+                actual_length: data.len(),
+                expected_length: ACCUMULATOR_SIZE,
+            });
+        }
+
+        Ok(Self {
+            lhs: read_g1::<H>(&data[..64])?,
+            rhs: read_g1::<H>(&data[64..])?,
+        })
+    }
+}
+
+/// Runs the whole verification of `proof` but the final pairing, and returns the
+/// [`PairingAccumulator`] to be finalized (possibly after merging it with others).
+///
+/// As [`crate::verify_prepared`], it only verifies UltraPlonk proofs.
+pub fn verify_deferred<H: VerifierHooks>(
+    hooks: &H,
+    vk: &PreparedVerificationKey<H>,
+    proof: &Proof<H>,
+    pubs: &[Fr],
+) -> Result<PairingAccumulator<H>, VerifyError> {
+    verify_deferred_with_transcript::<H, Keccak256Transcript<H>>(hooks, vk, proof, pubs)
+}
+
+/// Same as [`verify_deferred`], but the Fiat-Shamir challenges are derived with the `T`
+/// transcript: it must be the one the proof was generated with.
+pub fn verify_deferred_with_transcript<H: VerifierHooks, T: Transcript<H>>(
+    hooks: &H,
+    vk: &PreparedVerificationKey<H>,
+    proof: &Proof<H>,
+    pubs: &[Fr],
+) -> Result<PairingAccumulator<H>, VerifyError> {
+    check_public_input_number(vk.num_public_inputs, pubs)?;
+    let public_inputs = &pubs
+        .iter()
+        .map(|pi| pi.into_bigint())
+        .collect::<Vec<U256>>();
+    let proof = PreparedProof::try_from(proof).map_err(|_| VerifyError::InvalidProofError)?;
+
+    let (lhs, rhs) = prepare_pairing_points::<H, T>(hooks, vk, &proof, public_inputs)?;
+
+    Ok(PairingAccumulator { lhs, rhs })
+}
+
+// The point at infinity is encoded as (0, 0), as the EVM precompiles do.
+fn g1_to_bytes<H: CurveHooks>(point: &G1<H>) -> [u8; 64] {
+    let (x, y) = point.xy().unwrap_or((Fq::ZERO, Fq::ZERO));
+    let mut out = [0u8; 64];
+    out[..32].copy_from_slice(&x.into_bytes());
+    out[32..].copy_from_slice(&y.into_bytes());
+    out
+}
+
+fn g2_to_evm_bytes<H: CurveHooks>(point: &G2<H>) -> [u8; 128] {
+    let (x, y) = (point.x, point.y);
+    let mut out = [0u8; 128];
+    out[..32].copy_from_slice(&x.c1.into_bytes());
+    out[32..64].copy_from_slice(&x.c0.into_bytes());
+    out[64..96].copy_from_slice(&y.c1.into_bytes());
+    out[96..].copy_from_slice(&y.c0.into_bytes());
+    out
+}
+
+fn read_g1<H: CurveHooks>(data: &[u8]) -> Result<G1<H>, GroupError> {
+    if data.iter().all(|&b| b == 0) {
+        return Ok(G1::<H>::zero());
+    }
+    read_g1_util::<H>(data, false, DecodingMode::Strict)
+}
@@ -0,0 +1,194 @@
+// Copyright 2024 Horizen Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0 or MIT
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use alloc::vec::Vec;
+
+use ark_bn254_ext::CurveHooks;
+use ark_ec::{pairing::Pairing, CurveGroup};
+use ark_std::{rand::RngCore, UniformRand};
+
+use crate::{
+    check_public_input_number,
+    errors::VerifyError,
+    hooks::VerifierHooks,
+    key::PreparedVerificationKey,
+    pairing_check, prepare_pairing_points,
+    proof::{PreparedProof, Proof},
+    utils::IntoU256,
+    Bn254, Fr, Keccak256Transcript, PreparedSrs, Public, Transcript, G1, U256,
+};
+
+pub(crate) type G1Projective<H> = <Bn254<H> as Pairing>::G1;
+
+/// The MSM the folded pairing check is computed with.
+pub(crate) type Msm<H> = fn(&H, &[G1<H>], &[Fr]) -> Result<G1Projective<H>, VerifyError>;
+
+/// A proof to be batch verified, together with its verification key and public inputs.
+pub type BatchItem<'a, H> = (&'a PreparedVerificationKey<H>, &'a Proof<H>, &'a Public);
+
+pub(crate) struct BatchEntry<H: CurveHooks> {
+    pub(crate) index: usize,
+    pub(crate) pairing_lhs: G1<H>,
+    pub(crate) pairing_rhs: G1<H>,
+    pub(crate) r: Fr,
+}
+
+/// Verifies many proofs with a single two-pairing check.
+///
+/// Every proof is reduced to its `(PAIRING_LHS, PAIRING_RHS)` couple and the couples are
+/// folded together with random scalars drawn from `rng`. When the folded check fails, the
+/// batch is bisected and the indices of the bad proofs are reported through
+/// [`VerifyError::BatchVerificationError`]. Proofs rejected before the pairing (e.g. for
+/// malformed public inputs) are reported in the same way.
+///
+/// The items hold prepared keys, so only UltraPlonk proofs can be batched.
+pub fn verify_batch<H: VerifierHooks, R: RngCore>(
+    hooks: &H,
+    items: &[BatchItem<'_, H>],
+    rng: &mut R,
+) -> Result<(), VerifyError> {
+    verify_batch_with_srs(hooks, items, rng, &PreparedSrs::default())
+}
+
+/// Same as [`verify_batch`], but the pairings are checked against `srs`.
+pub fn verify_batch_with_srs<H: VerifierHooks, R: RngCore>(
+    hooks: &H,
+    items: &[BatchItem<'_, H>],
+    rng: &mut R,
+    srs: &PreparedSrs<H>,
+) -> Result<(), VerifyError> {
+    verify_batch_with_transcript::<H, Keccak256Transcript<H>, R>(hooks, items, rng, srs)
+}
+
+/// Same as [`verify_batch_with_srs`], but the Fiat-Shamir challenges of every proof are
+/// derived with the `T` transcript.
+pub fn verify_batch_with_transcript<H: VerifierHooks, T: Transcript<H>, R: RngCore>(
+    hooks: &H,
+    items: &[BatchItem<'_, H>],
+    rng: &mut R,
+    srs: &PreparedSrs<H>,
+) -> Result<(), VerifyError> {
+    let mut invalid = Vec::new();
+    let mut entries = Vec::with_capacity(items.len());
+
+    for (index, (vk, proof, pubs)) in items.iter().enumerate() {
+        match prepare_item::<H, T>(hooks, vk, proof, pubs) {
+            Ok((pairing_lhs, pairing_rhs)) => entries.push(BatchEntry {
+                index,
+                pairing_lhs,
+                pairing_rhs,
+                r: Fr::rand(rng),
+            }),
+            Err(_) => invalid.push(index),
+        }
+    }
+
+    check_entries(hooks, &entries, invalid, srs, msm_g1::<H>)
+}
+
+/// Runs the folded pairing check of `entries`, and reports the proofs that fail it
+/// together with the `invalid` ones.
+pub(crate) fn check_entries<H: VerifierHooks>(
+    hooks: &H,
+    entries: &[BatchEntry<H>],
+    mut invalid: Vec<usize>,
+    srs: &PreparedSrs<H>,
+    msm: Msm<H>,
+) -> Result<(), VerifyError> {
+    find_invalid(hooks, entries, &mut invalid, srs, msm)?;
+
+    if invalid.is_empty() {
+        Ok(())
+    } else {
+        invalid.sort_unstable();
+        Err(VerifyError::BatchVerificationError { invalid })
+    }
+}
+
+pub(crate) fn prepare_item<H: VerifierHooks, T: Transcript<H>>(
+    hooks: &H,
+    vk: &PreparedVerificationKey<H>,
+    proof: &Proof<H>,
+    pubs: &Public,
+) -> Result<(G1<H>, G1<H>), VerifyError> {
+    check_public_input_number(vk.num_public_inputs, pubs)?;
+    let public_inputs = pubs
+        .iter()
+        .map(|pi_bytes| pi_bytes.into_u256())
+        .collect::<Vec<U256>>();
+    let proof = PreparedProof::try_from(proof).map_err(|_| VerifyError::InvalidProofError)?;
+
+    prepare_pairing_points::<H, T>(hooks, vk, &proof, &public_inputs)
+}
+
+// Bisect the entries until every failing proof is isolated.
+fn find_invalid<H: VerifierHooks>(
+    hooks: &H,
+    entries: &[BatchEntry<H>],
+    invalid: &mut Vec<usize>,
+    srs: &PreparedSrs<H>,
+    msm: Msm<H>,
+) -> Result<(), VerifyError> {
+    if entries.is_empty() || folded_pairing_check(hooks, entries, srs, msm)? {
+        return Ok(());
+    }
+
+    if let [entry] = entries {
+        invalid.push(entry.index);
+        return Ok(());
+    }
+
+    let (left, right) = entries.split_at(entries.len() / 2);
+    find_invalid(hooks, left, invalid, srs, msm)?;
+    find_invalid(hooks, right, invalid, srs, msm)
+}
+
+// e(Σ rᵢ·PAIRING_RHSᵢ, [1]_2) · e(Σ rᵢ·PAIRING_LHSᵢ, [x]_2) == 1
+fn folded_pairing_check<H: VerifierHooks>(
+    hooks: &H,
+    entries: &[BatchEntry<H>],
+    srs: &PreparedSrs<H>,
+    msm: Msm<H>,
+) -> Result<bool, VerifyError> {
+    let scalars = entries.iter().map(|e| e.r).collect::<Vec<Fr>>();
+    let lhs_bases = entries
+        .iter()
+        .map(|e| e.pairing_lhs)
+        .collect::<Vec<G1<H>>>();
+    let rhs_bases = entries
+        .iter()
+        .map(|e| e.pairing_rhs)
+        .collect::<Vec<G1<H>>>();
+
+    let pairing_lhs = msm(hooks, &lhs_bases, &scalars)?;
+    let pairing_rhs = msm(hooks, &rhs_bases, &scalars)?;
+
+    pairing_check(
+        hooks,
+        pairing_lhs.into_affine(),
+        pairing_rhs.into_affine(),
+        srs,
+    )
+}
+
+fn msm_g1<H: VerifierHooks>(
+    hooks: &H,
+    bases: &[G1<H>],
+    scalars: &[Fr],
+) -> Result<G1Projective<H>, VerifyError> {
+    hooks
+        .msm_g1(bases, scalars)
+        .map_err(|_| VerifyError::CurveHooksError)
+}
@@ -4,6 +4,10 @@ use ark_ff::MontFp;
 pub const NEGATIVE_INVERSE_OF_2: Fr =
     MontFp!("10944121435919637611123202872628637544274182200208017171849102093287904247808");
 pub const MAX_LOG2_CIRCUIT_SIZE: u32 = 28;
+/// Number of public inputs holding the aggregation object of a recursive proof.
+pub const AGGREGATION_OBJECT_SIZE: usize = 16;
+/// Bit size of each limb of the aggregation object coordinates.
+pub const AGGREGATION_OBJECT_LIMB_BITS: usize = 68;
 
 #[rustfmt::skip]
 pub const fn root_of_unity(log2_circuit_size: u32) -> Option<Fr> {
@@ -14,7 +14,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use alloc::string::String;
+use alloc::{string::String, vec::Vec};
 use snafu::Snafu;
 
 /// The verification error type
@@ -35,6 +35,12 @@ pub enum VerifyError {
     /// Provided an invalid verification key.
     #[snafu(display("Key Error"))]
     KeyError,
+    /// Batch verification failed: `invalid` lists the indices of the bad proofs.
+    #[snafu(display("Batch Verification Failed: {:?}", invalid))]
+    BatchVerificationError { invalid: Vec<usize> },
+    /// A `CurveHooks` operation (MSM or pairing) failed.
+    #[snafu(display("Curve Hooks Error"))]
+    CurveHooksError,
 }
 
 #[derive(Debug, PartialEq)]
@@ -44,6 +50,8 @@ pub enum GroupError {
         expected_length: usize,
     },
     NotOnCurve,
+    NotMember,
+    PointAtInfinity,
 }
 
 #[derive(Debug, PartialEq)]
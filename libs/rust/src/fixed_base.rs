@@ -0,0 +1,266 @@
+// Copyright 2024 Horizen Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0 or MIT
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Fixed-base window tables for the points of the final MSM that only depend on the
+//! circuit: the 23 commitments of the verification key and the G1 generator.
+//!
+//! For a window of `w` bits, the table of a base `P` holds `j * 2^(w * i) * P` for every
+//! window `i` and every non zero digit `j < 2^w`. The scalar multiplications of these bases
+//! then reduce to one mixed addition per non zero digit, with no doubling at all. A table
+//! takes `ceil(254 / w) * (2^w - 1)` points of 64 bytes, e.g. 60 KiB per base with `w = 4`
+//! and 510 KiB with `w = 8`.
+//!
+//! Tables are only ever built from the key they're attached to, by
+//! [`PreparedVerificationKey::with_fixed_base_tables`], and are never deserialized: a
+//! serialized key only keeps their window size, and they're built again when it's loaded.
+
+use alloc::vec::Vec;
+use core::fmt;
+
+use ark_bn254_ext::CurveHooks;
+use ark_ec::{pairing::Pairing, AdditiveGroup, AffineRepr, CurveGroup};
+use ark_ff::PrimeField;
+use snafu::Snafu;
+
+use crate::{key::PreparedVerificationKey, Bn254, Fr, G1};
+
+type G1Projective<H> = <Bn254<H> as Pairing>::G1;
+
+/// Number of fixed bases: the verification key commitments and the generator.
+pub const FIXED_BASES: usize = 24;
+
+/// Largest accepted window. Tables grow as `2^w / w`: 16 bits would already take 1.5 GiB.
+pub const MAX_WINDOW_BITS: u8 = 12;
+
+#[derive(Debug, PartialEq, Snafu)]
+pub enum FixedBaseError {
+    #[snafu(display(
+        "Invalid window size. Expected: 1 to {}; Got: {}",
+        MAX_WINDOW_BITS,
+        window_bits
+    ))]
+    InvalidWindowBits { window_bits: u8 },
+
+    #[snafu(display("Base {} is the point at infinity", index))]
+    PointAtInfinity { index: usize },
+}
+
+/// The window tables of the [`FIXED_BASES`] fixed bases of a verification key.
+///
+/// Every point of a table is computed from its base, so a table is correct as long as its
+/// base is: when the tables are used, the first point of each of them, which is the base
+/// itself, is compared with the key they are used with.
+#[derive(Clone, PartialEq, Eq)]
+pub struct FixedBaseTables<H: CurveHooks> {
+    window_bits: u8,
+    // Base major, then window, then digit.
+    points: Vec<G1<H>>,
+}
+
+impl<H: CurveHooks> fmt::Debug for FixedBaseTables<H> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FixedBaseTables")
+            .field("window_bits", &self.window_bits)
+            .field("points", &self.points.len())
+            .finish()
+    }
+}
+
+impl<H: CurveHooks> FixedBaseTables<H> {
+    /// Builds the tables of the fixed bases of `vk`, with windows of `window_bits` bits.
+    pub fn new(vk: &PreparedVerificationKey<H>, window_bits: u8) -> Result<Self, FixedBaseError> {
+        check_window_bits(window_bits)?;
+        let bases = vk.fixed_bases();
+        if let Some(index) = bases.iter().position(|base| base.is_zero()) {
+            return Err(FixedBaseError::PointAtInfinity { index });
+        }
+
+        #[cfg(not(feature = "parallel"))]
+        let tables = bases
+            .iter()
+            .map(|base| base_table::<H>(base, window_bits))
+            .collect::<Vec<_>>();
+        #[cfg(feature = "parallel")]
+        let tables = {
+            use rayon::prelude::*;
+            bases
+                .par_iter()
+                .map(|base| base_table::<H>(base, window_bits))
+                .collect::<Vec<_>>()
+        };
+
+        Ok(Self {
+            window_bits,
+            points: tables.concat(),
+        })
+    }
+
+    /// The window size the tables were built with.
+    pub fn window_bits(&self) -> u8 {
+        self.window_bits
+    }
+
+    /// Returns `Σ scalarsᵢ.basesᵢ`, or `None` if `bases` aren't the ones the tables were
+    /// built for.
+    pub(crate) fn msm(
+        &self,
+        bases: &[G1<H>; FIXED_BASES],
+        scalars: &[Fr],
+    ) -> Option<G1Projective<H>> {
+        let table_size = table_size(self.window_bits);
+        let digits = digits(self.window_bits);
+
+        let mut acc = G1Projective::<H>::ZERO;
+        for (i, (base, scalar)) in bases.iter().zip(scalars).enumerate() {
+            let table = &self.points[i * table_size..(i + 1) * table_size];
+            if table[0] != *base {
+                return None;
+            }
+
+            let scalar = scalar.into_bigint();
+            for window in 0..windows(self.window_bits) {
+                let digit = window_digit(
+                    &scalar.0,
+                    window * self.window_bits as usize,
+                    self.window_bits,
+                );
+                if digit != 0 {
+                    acc += table[window * digits + digit - 1];
+                }
+            }
+        }
+
+        Some(acc)
+    }
+}
+
+impl<H: CurveHooks> PreparedVerificationKey<H> {
+    /// Attaches the [`FixedBaseTables`] of this key, built with `window_bits` bits windows.
+    pub fn with_fixed_base_tables(mut self, window_bits: u8) -> Result<Self, FixedBaseError> {
+        self.fixed_base_tables = Some(FixedBaseTables::new(&self, window_bits)?);
+        Ok(self)
+    }
+
+    /// The [`FixedBaseTables`] attached by [`Self::with_fixed_base_tables`], if any.
+    pub fn fixed_base_tables(&self) -> Option<&FixedBaseTables<H>> {
+        self.fixed_base_tables.as_ref()
+    }
+}
+
+// Builds the table of a single base.
+fn base_table<H: CurveHooks>(base: &G1<H>, window_bits: u8) -> Vec<G1<H>> {
+    let digits = digits(window_bits);
+    let mut points = Vec::with_capacity(table_size(window_bits));
+    let mut window_base = base.into_group();
+    for _ in 0..windows(window_bits) {
+        let mut multiple = window_base;
+        points.push(multiple);
+        for _ in 1..digits {
+            multiple += window_base;
+            points.push(multiple);
+        }
+        // 2^w * window_base
+        window_base = multiple + window_base;
+    }
+
+    G1Projective::<H>::normalize_batch(&points)
+}
+
+fn check_window_bits(window_bits: u8) -> Result<(), FixedBaseError> {
+    if window_bits == 0 || window_bits > MAX_WINDOW_BITS {
+        return Err(FixedBaseError::InvalidWindowBits { window_bits });
+    }
+    Ok(())
+}
+
+// Number of windows a scalar is split in.
+fn windows(window_bits: u8) -> usize {
+    (Fr::MODULUS_BIT_SIZE as usize).div_ceil(window_bits as usize)
+}
+
+// Number of non zero digits of a window.
+fn digits(window_bits: u8) -> usize {
+    (1 << window_bits) - 1
+}
+
+// Number of points in the table of a single base.
+fn table_size(window_bits: u8) -> usize {
+    windows(window_bits) * digits(window_bits)
+}
+
+// Reads the `window_bits` bits of `limbs` starting at bit `start`.
+fn window_digit(limbs: &[u64; 4], start: usize, window_bits: u8) -> usize {
+    let (limb, shift) = (start / 64, start % 64);
+    let mut digit = limbs[limb] >> shift;
+    if shift + window_bits as usize > 64 && limb + 1 < limbs.len() {
+        digit |= limbs[limb + 1] << (64 - shift);
+    }
+    (digit & ((1 << window_bits) - 1)) as usize
+}
+
+#[cfg(test)]
+mod should {
+    use super::*;
+    use crate::fixtures::bb_prepared_vk;
+    use ark_ec::VariableBaseMSM;
+    use ark_std::{test_rng, UniformRand};
+    use rstest::rstest;
+
+    #[rstest]
+    fn compute_the_same_msm_as_pippenger(
+        bb_prepared_vk: PreparedVerificationKey<()>,
+        #[values(1, 4, 5, 8)] window_bits: u8,
+    ) {
+        let tables = FixedBaseTables::new(&bb_prepared_vk, window_bits).unwrap();
+        let rng = &mut test_rng();
+        let mut scalars: [Fr; FIXED_BASES] = core::array::from_fn(|_| Fr::rand(rng));
+        scalars[0] = -Fr::from(1u64);
+        scalars[1] = Fr::from(0u64);
+
+        let bases = bb_prepared_vk.fixed_bases();
+        assert_eq!(
+            tables.msm(&bases, &scalars).unwrap().into_affine(),
+            G1Projective::<()>::msm(&bases, &scalars)
+                .unwrap()
+                .into_affine()
+        );
+    }
+
+    mod reject {
+        use super::*;
+
+        #[rstest]
+        fn a_window_out_of_range(
+            bb_prepared_vk: PreparedVerificationKey<()>,
+            #[values(0, MAX_WINDOW_BITS + 1)] window_bits: u8,
+        ) {
+            assert_eq!(
+                FixedBaseTables::new(&bb_prepared_vk, window_bits).unwrap_err(),
+                FixedBaseError::InvalidWindowBits { window_bits }
+            );
+        }
+
+        #[rstest]
+        fn tables_of_another_key(bb_prepared_vk: PreparedVerificationKey<()>) {
+            let tables = FixedBaseTables::new(&bb_prepared_vk, 2).unwrap();
+            let mut other_vk = bb_prepared_vk;
+            other_vk.q_1 = other_vk.q_2;
+
+            assert!(tables
+                .msm(&other_vk.fixed_bases(), &[Fr::from(1u64); FIXED_BASES])
+                .is_none());
+        }
+    }
+}
@@ -0,0 +1,41 @@
+// Copyright 2024 Horizen Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0 or MIT
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The bb v0.33.0 verification key and proof that the unit tests of the crate share.
+
+use rstest::fixture;
+
+use crate::key::{PreparedVerificationKey, VerificationKey};
+
+/// The raw key written by `bb write_vk`.
+pub(crate) static BB_VK: &[u8] = include_bytes!("../tests/resources/v0.33.0/vk.bin");
+
+/// The output of `bb prove`: its single public input, followed by the proof.
+pub(crate) static BB_OUTPUT: &[u8] = include_bytes!("../tests/resources/v0.33.0/proof.bin");
+
+#[fixture]
+pub(crate) fn bb_vk() -> VerificationKey<()> {
+    VerificationKey::try_from(BB_VK).unwrap()
+}
+
+#[fixture]
+pub(crate) fn bb_prepared_vk(bb_vk: VerificationKey<()>) -> PreparedVerificationKey<()> {
+    PreparedVerificationKey::try_from(&bb_vk).unwrap()
+}
+
+#[fixture]
+pub(crate) fn bb_output() -> &'static [u8] {
+    BB_OUTPUT
+}
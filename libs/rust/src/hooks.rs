@@ -0,0 +1,259 @@
+// Copyright 2024 Horizen Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0 or MIT
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Curve operations and hashing the verifier delegates to the embedder.
+//!
+//! [`CurveHooks`] only has static functions, so it can't reach any context of the caller.
+//! The verifier instead calls the expensive operations on a `&H` instance through
+//! [`VerifierHooks`], which can carry a host handle, a gas meter or call counters. Every
+//! curve method defaults to the matching [`CurveHooks`] function, so stateless hooks only
+//! need an empty `impl`, besides the [`KeccakHooks`] one the Keccak-256 transcript hashes
+//! with.
+
+use ark_bn254_ext::CurveHooks;
+use ark_ec::pairing::Pairing;
+
+#[cfg(any(test, feature = "hooks-conformance"))]
+use crate::G2;
+use crate::{transcript::KeccakHooks, Bn254, Fr, G1};
+#[cfg(any(test, feature = "hooks-conformance"))]
+use ark_ec::AffineRepr;
+
+type G1Projective<H> = <Bn254<H> as Pairing>::G1;
+type G1Prepared<H> = <Bn254<H> as Pairing>::G1Prepared;
+type G2Prepared<H> = <Bn254<H> as Pairing>::G2Prepared;
+type TargetField<H> = <Bn254<H> as Pairing>::TargetField;
+
+/// The hooks instance threaded through the verification.
+pub trait VerifierHooks: CurveHooks + KeccakHooks {
+    /// Computes `Σ scalarsᵢ.basesᵢ`.
+    fn msm_g1(&self, bases: &[G1<Self>], scalars: &[Fr]) -> Result<G1Projective<Self>, ()> {
+        Self::bn254_msm_g1(bases, scalars)
+    }
+
+    /// Computes the product of the Miller loops of the `(g1ᵢ, g2ᵢ)` couples.
+    fn multi_miller_loop(
+        &self,
+        g1: impl Iterator<Item = G1Prepared<Self>>,
+        g2: impl Iterator<Item = G2Prepared<Self>>,
+    ) -> Result<TargetField<Self>, ()> {
+        Self::bn254_multi_miller_loop(g1, g2)
+    }
+
+    /// Raises the output of [`VerifierHooks::multi_miller_loop`] to `(p¹² - 1) / r`.
+    fn final_exponentiation(&self, target: TargetField<Self>) -> Result<TargetField<Self>, ()> {
+        Self::bn254_final_exponentiation(target)
+    }
+}
+
+/// The zero-sized software implementation.
+impl VerifierHooks for () {}
+
+/// Converts a point of the reference `()` hooks to the `H` hooks.
+#[cfg(any(test, feature = "hooks-conformance"))]
+pub(crate) fn from_reference_g1<H: CurveHooks>(point: &G1<()>) -> G1<H> {
+    match point.xy() {
+        Some((x, y)) => G1::<H>::new_unchecked(x, y),
+        None => G1::<H>::zero(),
+    }
+}
+
+/// Converts a point of the `H` hooks to the reference `()` hooks.
+#[cfg(any(test, feature = "hooks-conformance"))]
+pub(crate) fn to_reference_g1<H: CurveHooks>(point: &G1<H>) -> G1<()> {
+    match point.xy() {
+        Some((x, y)) => G1::<()>::new_unchecked(x, y),
+        None => G1::<()>::zero(),
+    }
+}
+
+/// Converts a point of the reference `()` hooks to the `H` hooks.
+#[cfg(any(test, feature = "hooks-conformance"))]
+pub(crate) fn from_reference_g2<H: CurveHooks>(point: &G2<()>) -> G2<H> {
+    match point.xy() {
+        Some((x, y)) => G2::<H>::new_unchecked(x, y),
+        None => G2::<H>::zero(),
+    }
+}
+
+/// Converts a point of the `H` hooks to the reference `()` hooks.
+#[cfg(any(test, feature = "hooks-conformance"))]
+pub(crate) fn to_reference_g2<H: CurveHooks>(point: &G2<H>) -> G2<()> {
+    match point.xy() {
+        Some((x, y)) => G2::<()>::new_unchecked(x, y),
+        None => G2::<()>::zero(),
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod testing {
+    //! Hooks for the tests of the crate. Their [`CurveHooks`] functions go through the
+    //! reference `()` hooks, while their [`VerifierHooks`] methods can be made to misbehave.
+
+    use super::*;
+    use alloc::vec::Vec;
+    use ark_ec::CurveGroup;
+    use ark_ff::Field;
+    use ark_models_ext::bn;
+    use core::sync::atomic::{AtomicUsize, Ordering};
+    use sha3::{Digest, Keccak256};
+
+    type G2Projective<H> = <Bn254<H> as Pairing>::G2;
+
+    /// How a [`TestHooks`] instance misbehaves.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub(crate) enum Fault {
+        FailingMsm,
+        FailingMillerLoop,
+        FailingFinalExponentiation,
+        /// The MSM result is shifted by the G1 generator.
+        MsmOffByGenerator,
+        /// The Miller loop output is shifted by one.
+        WrongMillerLoop,
+        /// The final exponentiation always returns one.
+        FinalExponentiationIsOne,
+    }
+
+    #[derive(Debug, Default)]
+    pub(crate) struct TestHooks {
+        pub(crate) fault: Option<Fault>,
+        /// Number of [`KeccakHooks::keccak_256`] calls.
+        pub(crate) keccak_calls: AtomicUsize,
+        /// Number of [`VerifierHooks::msm_g1`] calls.
+        pub(crate) msm_calls: AtomicUsize,
+        /// Number of [`VerifierHooks::multi_miller_loop`] calls.
+        pub(crate) miller_loop_calls: AtomicUsize,
+        /// Number of [`VerifierHooks::final_exponentiation`] calls.
+        pub(crate) final_exponentiation_calls: AtomicUsize,
+    }
+
+    impl TestHooks {
+        pub(crate) fn with_fault(fault: Fault) -> Self {
+            Self {
+                fault: Some(fault),
+                ..Default::default()
+            }
+        }
+
+        fn faulty(&self, fault: Fault) -> bool {
+            self.fault == Some(fault)
+        }
+    }
+
+    impl VerifierHooks for TestHooks {
+        fn msm_g1(&self, bases: &[G1<Self>], scalars: &[Fr]) -> Result<G1Projective<Self>, ()> {
+            self.msm_calls.fetch_add(1, Ordering::Relaxed);
+            if self.faulty(Fault::FailingMsm) {
+                return Err(());
+            }
+            let msm = Self::bn254_msm_g1(bases, scalars)?;
+            if self.faulty(Fault::MsmOffByGenerator) {
+                return Ok(msm + G1::<Self>::generator());
+            }
+            Ok(msm)
+        }
+
+        fn multi_miller_loop(
+            &self,
+            g1: impl Iterator<Item = G1Prepared<Self>>,
+            g2: impl Iterator<Item = G2Prepared<Self>>,
+        ) -> Result<TargetField<Self>, ()> {
+            self.miller_loop_calls.fetch_add(1, Ordering::Relaxed);
+            if self.faulty(Fault::FailingMillerLoop) {
+                return Err(());
+            }
+            let miller_loop = Self::bn254_multi_miller_loop(g1, g2)?;
+            if self.faulty(Fault::WrongMillerLoop) {
+                return Ok(miller_loop + TargetField::<Self>::ONE);
+            }
+            Ok(miller_loop)
+        }
+
+        fn final_exponentiation(&self, target: TargetField<Self>) -> Result<TargetField<Self>, ()> {
+            self.final_exponentiation_calls
+                .fetch_add(1, Ordering::Relaxed);
+            if self.faulty(Fault::FailingFinalExponentiation) {
+                return Err(());
+            }
+            if self.faulty(Fault::FinalExponentiationIsOne) {
+                return Ok(TargetField::<Self>::ONE);
+            }
+            Self::bn254_final_exponentiation(target)
+        }
+    }
+
+    /// Hashes whole challenges at once, as a host function would.
+    impl KeccakHooks for TestHooks {
+        type KeccakState = Vec<u8>;
+
+        fn keccak_256(&self, data: Vec<u8>) -> [u8; 32] {
+            self.keccak_calls.fetch_add(1, Ordering::Relaxed);
+            Keccak256::digest(&data).into()
+        }
+    }
+
+    impl CurveHooks for TestHooks {
+        fn bn254_multi_miller_loop(
+            g1: impl Iterator<Item = G1Prepared<Self>>,
+            g2: impl Iterator<Item = G2Prepared<Self>>,
+        ) -> Result<TargetField<Self>, ()> {
+            <() as CurveHooks>::bn254_multi_miller_loop(
+                g1.map(|p| bn::G1Prepared::from(to_reference_g1(&p.0))),
+                g2.map(|q| bn::G2Prepared {
+                    ell_coeffs: q.ell_coeffs,
+                    infinity: q.infinity,
+                }),
+            )
+        }
+
+        fn bn254_final_exponentiation(target: TargetField<Self>) -> Result<TargetField<Self>, ()> {
+            <() as CurveHooks>::bn254_final_exponentiation(target)
+        }
+
+        fn bn254_msm_g1(bases: &[G1<Self>], scalars: &[Fr]) -> Result<G1Projective<Self>, ()> {
+            let bases = bases.iter().map(to_reference_g1).collect::<Vec<_>>();
+            <() as CurveHooks>::bn254_msm_g1(&bases, scalars)
+                .map(|point| from_reference_g1(&point.into_affine()).into())
+        }
+
+        fn bn254_msm_g2(bases: &[G2<Self>], scalars: &[Fr]) -> Result<G2Projective<Self>, ()> {
+            let bases = bases.iter().map(to_reference_g2).collect::<Vec<_>>();
+            <() as CurveHooks>::bn254_msm_g2(&bases, scalars)
+                .map(|point| from_reference_g2(&point.into_affine()).into())
+        }
+
+        fn bn254_mul_projective_g1(
+            base: &G1Projective<Self>,
+            scalar: &[u64],
+        ) -> Result<G1Projective<Self>, ()> {
+            <() as CurveHooks>::bn254_mul_projective_g1(
+                &to_reference_g1(&base.into_affine()).into(),
+                scalar,
+            )
+            .map(|point| from_reference_g1(&point.into_affine()).into())
+        }
+
+        fn bn254_mul_projective_g2(
+            base: &G2Projective<Self>,
+            scalar: &[u64],
+        ) -> Result<G2Projective<Self>, ()> {
+            <() as CurveHooks>::bn254_mul_projective_g2(
+                &to_reference_g2(&base.into_affine()).into(),
+                scalar,
+            )
+            .map(|point| from_reference_g2(&point.into_affine()).into())
+        }
+    }
+}
@@ -0,0 +1,421 @@
+// Copyright 2024 Horizen Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0 or MIT
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Conformance checks for third-party [`CurveHooks`] implementations, available with the
+//! `hooks-conformance` feature.
+//!
+//! Every check compares the hooks under test against the reference `()` hooks, first on
+//! known answers and then on values drawn from `rng`, and panics on the first mismatch.
+//! They are meant to be called from the tests of the crate that implements the hooks:
+//!
+//! ```ignore
+//! #[test]
+//! fn host_hooks_conform() {
+//!     ultraplonk_no_std::hooks_conformance::check_all(&HostHooks::new(), &mut test_rng());
+//! }
+//! ```
+
+use alloc::{format, vec::Vec};
+
+use ark_bn254_ext::{Config, CurveHooks};
+use ark_ec::{pairing::Pairing, AffineRepr, CurveGroup, VariableBaseMSM};
+use ark_ff::{Field, MontFp, One};
+use ark_models_ext::bn::{G1Prepared, G2Prepared};
+use ark_std::{rand::RngCore, UniformRand};
+
+use crate::{
+    errors::VerifyError,
+    hooks::{
+        from_reference_g1, from_reference_g2, to_reference_g1, to_reference_g2, VerifierHooks,
+    },
+    key::VerificationKey,
+    verify_with_hooks, Bn254, Fq, Fr, PublicInput, G1, G2, PUBS_SIZE,
+};
+
+type G2Projective<H> = <Bn254<H> as Pairing>::G2;
+
+/// Number of random cases of each randomized check.
+pub const RANDOM_CASES: usize = 8;
+
+/// The bb fixtures `(version, raw vk, proof)`: each proof is prefixed by its public input.
+pub const BB_FIXTURES: &[(&str, &[u8], &[u8])] = &[
+    (
+        "v0.31.0",
+        include_bytes!("../tests/resources/v0.31.0/vk.bin"),
+        include_bytes!("../tests/resources/v0.31.0/proof.bin"),
+    ),
+    (
+        "v0.32.0",
+        include_bytes!("../tests/resources/v0.32.0/vk.bin"),
+        include_bytes!("../tests/resources/v0.32.0/proof.bin"),
+    ),
+    (
+        "v0.33.0",
+        include_bytes!("../tests/resources/v0.33.0/vk.bin"),
+        include_bytes!("../tests/resources/v0.33.0/proof.bin"),
+    ),
+    (
+        "v0.34.0",
+        include_bytes!("../tests/resources/v0.34.0/vk.bin"),
+        include_bytes!("../tests/resources/v0.34.0/proof.bin"),
+    ),
+    (
+        "v0.35.0",
+        include_bytes!("../tests/resources/v0.35.0/vk.bin"),
+        include_bytes!("../tests/resources/v0.35.0/proof.bin"),
+    ),
+    (
+        "v0.36.0",
+        include_bytes!("../tests/resources/v0.36.0/vk.bin"),
+        include_bytes!("../tests/resources/v0.36.0/proof.bin"),
+    ),
+    (
+        "v0.37.0",
+        include_bytes!("../tests/resources/v0.37.0/vk.bin"),
+        include_bytes!("../tests/resources/v0.37.0/proof.bin"),
+    ),
+    (
+        "v0.38.0",
+        include_bytes!("../tests/resources/v0.38.0/vk.bin"),
+        include_bytes!("../tests/resources/v0.38.0/proof.bin"),
+    ),
+    (
+        "v0.39.0",
+        include_bytes!("../tests/resources/v0.39.0/vk.bin"),
+        include_bytes!("../tests/resources/v0.39.0/proof.bin"),
+    ),
+    (
+        "v1.0.0-beta.0",
+        include_bytes!("../tests/resources/v1.0.0-beta.0/vk.bin"),
+        include_bytes!("../tests/resources/v1.0.0-beta.0/proof.bin"),
+    ),
+    (
+        "v1.0.0-beta.1",
+        include_bytes!("../tests/resources/v1.0.0-beta.1/vk.bin"),
+        include_bytes!("../tests/resources/v1.0.0-beta.1/proof.bin"),
+    ),
+];
+
+// 2.[1]_1
+const G1_DOUBLE_GENERATOR_X: Fq =
+    MontFp!("1368015179489954701390400359078579693043519447331113978918064868415326638035");
+const G1_DOUBLE_GENERATOR_Y: Fq =
+    MontFp!("9918110051302171585080402603319702774565515993150576347155970296011118125764");
+
+/// Runs every check of this module.
+pub fn check_all<H: VerifierHooks, R: RngCore>(hooks: &H, rng: &mut R) {
+    check_msm_g1(hooks, rng);
+    check_g1_arithmetic::<H, R>(rng);
+    check_g2_arithmetic::<H, R>(rng);
+    check_pairing(hooks, rng);
+    check_verify(hooks);
+}
+
+/// Checks `bn254_msm_g1`, both through [`CurveHooks`] and through [`VerifierHooks`].
+pub fn check_msm_g1<H: VerifierHooks, R: RngCore>(hooks: &H, rng: &mut R) {
+    let generator = G1::<H>::generator();
+
+    // Known answers
+    let known_answers: [(Vec<G1<H>>, Vec<Fr>, G1<()>); 4] = [
+        (Vec::new(), Vec::new(), G1::<()>::zero()),
+        (
+            [generator].to_vec(),
+            [Fr::ONE].to_vec(),
+            G1::<()>::generator(),
+        ),
+        (
+            [generator, generator].to_vec(),
+            [Fr::ONE, Fr::ONE].to_vec(),
+            G1::<()>::new_unchecked(G1_DOUBLE_GENERATOR_X, G1_DOUBLE_GENERATOR_Y),
+        ),
+        (
+            [generator, generator].to_vec(),
+            [Fr::ONE, -Fr::ONE].to_vec(),
+            G1::<()>::zero(),
+        ),
+    ];
+    for (bases, scalars, expected) in known_answers {
+        check_msm_g1_case(hooks, &bases, &scalars, Some(expected));
+    }
+
+    // Random cases, of increasing size
+    for size in 1..=RANDOM_CASES {
+        let scalars = (0..size).map(|_| Fr::rand(rng)).collect::<Vec<Fr>>();
+        let bases = (0..size)
+            .map(|_| from_reference_g1::<H>(&random_g1(rng)))
+            .collect::<Vec<G1<H>>>();
+        check_msm_g1_case(hooks, &bases, &scalars, None);
+    }
+}
+
+/// Checks the G1 additions and scalar multiplications performed through the hooks.
+pub fn check_g1_arithmetic<H: CurveHooks, R: RngCore>(rng: &mut R) {
+    for _ in 0..RANDOM_CASES {
+        let point = random_g1(rng);
+        let other = random_g1(rng);
+        let scalar = Fr::rand(rng);
+
+        let expected = ((point + other) * scalar).into_affine();
+        let actual = ((from_reference_g1::<H>(&point) + from_reference_g1::<H>(&other)) * scalar)
+            .into_affine();
+        assert_eq!(
+            to_reference_g1(&actual),
+            expected,
+            "G1 scalar multiplication mismatch: ({point} + {other}) * {scalar}"
+        );
+    }
+}
+
+/// Checks the G2 scalar multiplications and MSMs performed through the hooks.
+pub fn check_g2_arithmetic<H: CurveHooks, R: RngCore>(rng: &mut R) {
+    // Known answer: [r - 1]_2 = -[1]_2
+    let actual = (G2::<H>::generator() * -Fr::ONE).into_affine();
+    assert_eq!(
+        to_reference_g2(&actual),
+        -G2::<()>::generator(),
+        "G2 scalar multiplication mismatch: -[1]_2"
+    );
+
+    for size in 1..=RANDOM_CASES {
+        let scalars = (0..size).map(|_| Fr::rand(rng)).collect::<Vec<Fr>>();
+        let bases = (0..size).map(|_| random_g2(rng)).collect::<Vec<G2<()>>>();
+
+        let expected = G2Projective::<()>::msm(&bases, &scalars)
+            .expect("same length")
+            .into_affine();
+        let actual = G2Projective::<H>::msm(
+            &bases
+                .iter()
+                .map(from_reference_g2::<H>)
+                .collect::<Vec<G2<H>>>(),
+            &scalars,
+        )
+        .expect("same length")
+        .into_affine();
+        assert_eq!(
+            to_reference_g2(&actual),
+            expected,
+            "G2 MSM mismatch: bases {bases:?}, scalars {scalars:?}"
+        );
+
+        let expected = (bases[0] * scalars[0]).into_affine();
+        let actual = (from_reference_g2::<H>(&bases[0]) * scalars[0]).into_affine();
+        assert_eq!(
+            to_reference_g2(&actual),
+            expected,
+            "G2 scalar multiplication mismatch: {} * {}",
+            bases[0],
+            scalars[0]
+        );
+    }
+}
+
+/// Checks `bn254_multi_miller_loop` and `bn254_final_exponentiation`, both through
+/// [`CurveHooks`] and through [`VerifierHooks`].
+pub fn check_pairing<H: VerifierHooks, R: RngCore>(hooks: &H, rng: &mut R) {
+    // Known answer: e([1]_1, [1]_2) * e(-[1]_1, [1]_2) == 1
+    let g1 = [G1::<()>::generator(), -G1::<()>::generator()];
+    let g2 = [G2::<()>::generator(), G2::<()>::generator()];
+    assert!(
+        pairing_product(hooks, &g1, &g2).is_one(),
+        "e([1]_1, [1]_2) * e(-[1]_1, [1]_2) != 1"
+    );
+
+    for _ in 0..RANDOM_CASES {
+        // Bilinearity: e(a.P, b.Q) * e(-ab.P, Q) == 1
+        let (a, b) = (Fr::rand(rng), Fr::rand(rng));
+        let (p, q) = (random_g1(rng), random_g2(rng));
+        let g1 = [(p * a).into_affine(), (-(p * (a * b))).into_affine()];
+        let g2 = [(q * b).into_affine(), q];
+        assert!(
+            pairing_product(hooks, &g1, &g2).is_one(),
+            "e(a.P, b.Q) * e(-ab.P, Q) != 1 for P = {p}, Q = {q}, a = {a}, b = {b}"
+        );
+
+        // Same output as the reference hooks, before and after the final exponentiation
+        let g1 = [random_g1(rng), random_g1(rng)];
+        let g2 = [random_g2(rng), random_g2(rng)];
+        let expected = <() as CurveHooks>::bn254_multi_miller_loop(
+            g1.iter().map(|p| G1Prepared::from(*p)),
+            g2.iter().map(|q| G2Prepared::from(*q)),
+        )
+        .expect("reference hooks never fail");
+        let actual = H::bn254_multi_miller_loop(
+            g1.iter()
+                .map(|p| G1Prepared::from(from_reference_g1::<H>(p))),
+            g2.iter()
+                .map(|q| G2Prepared::from(from_reference_g2::<H>(q))),
+        )
+        .expect("multi Miller loop failed");
+        assert_eq!(
+            actual, expected,
+            "multi Miller loop mismatch: g1 {g1:?}, g2 {g2:?}"
+        );
+
+        let expected = <() as CurveHooks>::bn254_final_exponentiation(expected)
+            .expect("reference hooks never fail");
+        assert_eq!(
+            H::bn254_final_exponentiation(actual).expect("final exponentiation failed"),
+            expected,
+            "final exponentiation mismatch: g1 {g1:?}, g2 {g2:?}"
+        );
+        assert_eq!(
+            pairing_product(hooks, &g1, &g2),
+            expected,
+            "pairing mismatch: g1 {g1:?}, g2 {g2:?}"
+        );
+    }
+}
+
+/// Runs the whole verification of every [`BB_FIXTURES`] proof with `hooks`, and checks that
+/// the same proofs are rejected once their public input is changed.
+pub fn check_verify<H: VerifierHooks>(hooks: &H) {
+    for (version, raw_vk, raw_proof) in BB_FIXTURES {
+        let (vk, _) = VerificationKey::<H>::try_from_raw_bytes(raw_vk)
+            .unwrap_or_else(|e| panic!("{version}: invalid fixture vk: {e}"));
+        let vk = vk.as_solidity_bytes();
+        let (pubs, proof) = raw_proof.split_at(PUBS_SIZE);
+        let pubs: PublicInput = pubs.try_into().expect("PUBS_SIZE bytes");
+
+        assert_eq!(
+            verify_with_hooks(hooks, &vk, proof, &[pubs]),
+            Ok(()),
+            "{version}: valid proof rejected"
+        );
+
+        let mut wrong_pubs = pubs;
+        wrong_pubs[PUBS_SIZE - 1] ^= 1;
+        assert_eq!(
+            verify_with_hooks(hooks, &vk, proof, &[wrong_pubs]),
+            Err(VerifyError::VerificationError),
+            "{version}: proof with a wrong public input accepted"
+        );
+    }
+}
+
+fn check_msm_g1_case<H: VerifierHooks>(
+    hooks: &H,
+    bases: &[G1<H>],
+    scalars: &[Fr],
+    expected: Option<G1<()>>,
+) {
+    let reference = <() as CurveHooks>::bn254_msm_g1(
+        &bases.iter().map(to_reference_g1).collect::<Vec<G1<()>>>(),
+        scalars,
+    )
+    .expect("reference hooks never fail")
+    .into_affine();
+    if let Some(expected) = expected {
+        assert_eq!(reference, expected, "reference MSM known answer mismatch");
+    }
+
+    let context = format!("bases {bases:?}, scalars {scalars:?}");
+    let actual = H::bn254_msm_g1(bases, scalars)
+        .unwrap_or_else(|_| panic!("bn254_msm_g1 failed: {context}"))
+        .into_affine();
+    assert_eq!(
+        to_reference_g1(&actual),
+        reference,
+        "bn254_msm_g1 mismatch: {context}"
+    );
+
+    let actual = hooks
+        .msm_g1(bases, scalars)
+        .unwrap_or_else(|_| panic!("VerifierHooks::msm_g1 failed: {context}"))
+        .into_affine();
+    assert_eq!(
+        to_reference_g1(&actual),
+        reference,
+        "VerifierHooks::msm_g1 mismatch: {context}"
+    );
+}
+
+// Product of the pairings of the couples, through the VerifierHooks of `hooks`.
+fn pairing_product<H: VerifierHooks>(
+    hooks: &H,
+    g1: &[G1<()>],
+    g2: &[G2<()>],
+) -> <Bn254<H> as Pairing>::TargetField {
+    let miller_loop = hooks
+        .multi_miller_loop(
+            g1.iter()
+                .map(|p| G1Prepared::<Config<H>>::from(from_reference_g1::<H>(p))),
+            g2.iter()
+                .map(|q| G2Prepared::<Config<H>>::from(from_reference_g2::<H>(q))),
+        )
+        .expect("VerifierHooks::multi_miller_loop failed");
+    hooks
+        .final_exponentiation(miller_loop)
+        .expect("VerifierHooks::final_exponentiation failed")
+}
+
+fn random_g1<R: RngCore>(rng: &mut R) -> G1<()> {
+    (G1::<()>::generator() * Fr::rand(rng)).into_affine()
+}
+
+fn random_g2<R: RngCore>(rng: &mut R) -> G2<()> {
+    (G2::<()>::generator() * Fr::rand(rng)).into_affine()
+}
+
+#[cfg(test)]
+mod should {
+    use super::*;
+    use crate::hooks::testing::{Fault, TestHooks};
+    use rstest::rstest;
+
+    #[rstest]
+    fn accept_the_reference_hooks() {
+        check_all(&(), &mut ark_std::test_rng());
+    }
+
+    #[rstest]
+    fn know_the_double_of_the_generator() {
+        assert_eq!(
+            (G1::<()>::generator() + G1::<()>::generator()).into_affine(),
+            G1::<()>::new_unchecked(G1_DOUBLE_GENERATOR_X, G1_DOUBLE_GENERATOR_Y)
+        );
+    }
+
+    mod reject {
+        use super::*;
+
+        #[rstest]
+        #[should_panic(expected = "VerifierHooks::msm_g1 mismatch")]
+        fn an_msm_off_by_a_generator() {
+            check_all(
+                &TestHooks::with_fault(Fault::MsmOffByGenerator),
+                &mut ark_std::test_rng(),
+            );
+        }
+
+        #[rstest]
+        #[should_panic(expected = "e([1]_1, [1]_2) * e(-[1]_1, [1]_2) != 1")]
+        fn a_wrong_miller_loop() {
+            check_all(
+                &TestHooks::with_fault(Fault::WrongMillerLoop),
+                &mut ark_std::test_rng(),
+            );
+        }
+
+        #[rstest]
+        #[should_panic(expected = "pairing mismatch")]
+        fn a_final_exponentiation_returning_one() {
+            check_all(
+                &TestHooks::with_fault(Fault::FinalExponentiationIsOne),
+                &mut ark_std::test_rng(),
+            );
+        }
+    }
+}
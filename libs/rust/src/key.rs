@@ -25,11 +25,13 @@ use alloc::{
 use crate::{
     constants::{self, MAX_LOG2_CIRCUIT_SIZE},
     errors::GroupError,
+    fixed_base::{FixedBaseTables, FIXED_BASES},
     utils::{read_fq_util, read_g1_util, IntoBytes, IntoU256},
-    Fq2, Fr, G1, G2, U256, VK_SIZE,
+    DecodingMode, Fr, G1, U256, VK_SIZE,
 };
 
 use ark_bn254_ext::{CurveHooks, Fq};
+use ark_ec::AffineRepr;
 use ark_ff::{BigInt, PrimeField};
 use snafu::Snafu;
 
@@ -49,9 +51,15 @@ pub enum VerificationKeyError {
     #[snafu(display("Point for field '{field:?}' is not on curve"))]
     PointNotOnCurve { field: &'static str },
 
+    #[snafu(display("Coordinate of field '{field:?}' is not a member of Fq"))]
+    NotMember { field: &'static str },
+
+    #[snafu(display("Point for field '{field:?}' is the point at infinity"))]
+    PointAtInfinity { field: &'static str },
+
     // #[snafu(display("Point for field '{}' is not in the correct subgroup", field))]
     // PointNotInCorrectSubgroup { field: &'static str },
-    #[snafu(display("Invalid circuit type. Expected: 2"))]
+    #[snafu(display("Invalid circuit type"))]
     InvalidCircuitType,
 
     #[snafu(display("Invalid circuit size"))]
@@ -72,8 +80,50 @@ pub enum VerificationKeyError {
     #[snafu(display("Unexpected commitment key: {key:?}. Expected: {expected:?}"))]
     UnexpectedCommitmentKey { key: String, expected: String },
 
-    #[snafu(display("Recursion is not supported"))]
-    RecursionNotSupported,
+    #[snafu(display("Invalid recursive proof flag"))]
+    InvalidRecursiveProofFlag,
+
+    #[snafu(display("Invalid recursive proof public input indices"))]
+    InvalidRecursiveProofIndices,
+
+    #[snafu(display("Invalid recursive circuit flag"))]
+    InvalidRecursiveCircuitFlag,
+
+    #[snafu(display("Unknown raw verification key format of {size:?} bytes"))]
+    UnknownRawFormat { size: usize },
+
+    #[snafu(display("Unexpected {size:?} bytes after the verification key"))]
+    TrailingBytes { size: usize },
+}
+
+/// The barretenberg composer a verification key was generated with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CircuitType {
+    Standard = 0,
+    Turbo = 1,
+    Ultra = 2,
+}
+
+impl TryFrom<u32> for CircuitType {
+    type Error = VerificationKeyError;
+
+    fn try_from(value: u32) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(CircuitType::Standard),
+            1 => Ok(CircuitType::Turbo),
+            2 => Ok(CircuitType::Ultra),
+            _ => Err(VerificationKeyError::InvalidCircuitType),
+        }
+    }
+}
+
+impl CircuitType {
+    /// Reads the circuit type from the first word of a Solidity encoded verification key.
+    pub fn from_solidity_bytes(bytes: &[u8]) -> Result<Self, VerificationKeyError> {
+        let (circuit_type, _) =
+            get_u32(bytes).map_err(|_| VerificationKeyError::InvalidCircuitType)?;
+        CircuitType::try_from(circuit_type)
+    }
 }
 
 #[derive(Debug, Hash, Eq, PartialEq)]
@@ -162,6 +212,11 @@ impl CommitmentField {
     }
 }
 
+/// An UltraPlonk verification key.
+///
+/// Only [`CircuitType::Ultra`] keys are parsed, from both the Solidity and the raw bb
+/// formats: Standard and Turbo keys only exist in their Solidity format, and are parsed by
+/// the [`crate::legacy`] verifiers.
 #[derive(PartialEq, Eq, Debug)]
 pub struct VerificationKey<H: CurveHooks> {
     pub circuit_type: u32,
@@ -192,6 +247,9 @@ pub struct VerificationKey<H: CurveHooks> {
     pub id_4: G1<H>,
     pub contains_recursive_proof: bool,
     pub recursive_proof_indices: u32,
+    /// The `is_recursive_circuit` flag of the raw barretenberg formats. The Solidity
+    /// layout doesn't carry it.
+    pub is_recursive_circuit: bool,
 }
 
 impl<H: CurveHooks> VerificationKey<H> {
@@ -252,6 +310,65 @@ impl<H: CurveHooks> VerificationKey<H> {
         out
     }
 
+    /// Serializes the key in the raw barretenberg `format`. The legacy format can't carry
+    /// a recursive proof.
+    pub fn as_raw_bytes(&self, format: RawVkFormat) -> Result<Vec<u8>, VerificationKeyError> {
+        const NUM_COMMITMENTS: u32 = 23;
+        if format == RawVkFormat::Legacy && self.contains_recursive_proof {
+            return Err(VerificationKeyError::InvalidRecursiveProofIndices);
+        }
+
+        let mut out = Vec::new();
+        out.extend(self.circuit_type.to_be_bytes());
+        out.extend(self.circuit_size.to_be_bytes());
+        out.extend(self.num_public_inputs.to_be_bytes());
+        out.extend(NUM_COMMITMENTS.to_be_bytes());
+        write_commitment::<H>(&mut out, &CommitmentField::ID_1, &self.id_1);
+        write_commitment::<H>(&mut out, &CommitmentField::ID_2, &self.id_2);
+        write_commitment::<H>(&mut out, &CommitmentField::ID_3, &self.id_3);
+        write_commitment::<H>(&mut out, &CommitmentField::ID_4, &self.id_4);
+        write_commitment::<H>(&mut out, &CommitmentField::Q_1, &self.q_1);
+        write_commitment::<H>(&mut out, &CommitmentField::Q_2, &self.q_2);
+        write_commitment::<H>(&mut out, &CommitmentField::Q_3, &self.q_3);
+        write_commitment::<H>(&mut out, &CommitmentField::Q_4, &self.q_4);
+        write_commitment::<H>(&mut out, &CommitmentField::Q_ARITHMETIC, &self.q_arithmetic);
+        write_commitment::<H>(&mut out, &CommitmentField::Q_AUX, &self.q_aux);
+        write_commitment::<H>(&mut out, &CommitmentField::Q_C, &self.q_c);
+        write_commitment::<H>(&mut out, &CommitmentField::Q_ELLIPTIC, &self.q_elliptic);
+        write_commitment::<H>(&mut out, &CommitmentField::Q_M, &self.q_m);
+        write_commitment::<H>(&mut out, &CommitmentField::Q_SORT, &self.q_sort);
+        write_commitment::<H>(&mut out, &CommitmentField::SIGMA_1, &self.sigma_1);
+        write_commitment::<H>(&mut out, &CommitmentField::SIGMA_2, &self.sigma_2);
+        write_commitment::<H>(&mut out, &CommitmentField::SIGMA_3, &self.sigma_3);
+        write_commitment::<H>(&mut out, &CommitmentField::SIGMA_4, &self.sigma_4);
+        write_commitment::<H>(&mut out, &CommitmentField::TABLE_1, &self.table_1);
+        write_commitment::<H>(&mut out, &CommitmentField::TABLE_2, &self.table_2);
+        write_commitment::<H>(&mut out, &CommitmentField::TABLE_3, &self.table_3);
+        write_commitment::<H>(&mut out, &CommitmentField::TABLE_4, &self.table_4);
+        write_commitment::<H>(&mut out, &CommitmentField::TABLE_TYPE, &self.table_type);
+        out.push(self.contains_recursive_proof as u8);
+
+        let mut indices = Vec::new();
+        if self.contains_recursive_proof {
+            indices.extend(
+                (0..constants::AGGREGATION_OBJECT_SIZE as u32)
+                    .map(|i| self.recursive_proof_indices.wrapping_add(i)),
+            );
+        }
+        match format {
+            RawVkFormat::Legacy => (),
+            RawVkFormat::V0_31 => out.extend((indices.len() as u32).to_be_bytes()),
+            // bb always writes the whole array: fill it with zeros
+            RawVkFormat::V0_33 => indices.resize(constants::AGGREGATION_OBJECT_SIZE, 0),
+        }
+        indices
+            .iter()
+            .for_each(|index| out.extend(index.to_be_bytes()));
+
+        out.push(self.is_recursive_circuit as u8);
+        Ok(out)
+    }
+
     pub fn try_from_solidity_bytes(bytes: &[u8]) -> Result<Self, VerificationKeyError> {
         if bytes.len() != VK_SIZE {
             Err(VerificationKeyError::BufferTooShort)?;
@@ -371,16 +488,15 @@ impl<H: CurveHooks> VerificationKey<H> {
             })?;
 
         let (contains_recursive_proof, bytes) =
-            get_bool(bytes).map_err(|_| VerificationKeyError::RecursionNotSupported)?;
-        if contains_recursive_proof {
-            Err(VerificationKeyError::RecursionNotSupported)?
-        }
+            get_bool(bytes).map_err(|_| VerificationKeyError::InvalidRecursiveProofFlag)?;
 
         let (recursive_proof_indices, _) =
-            get_u32(bytes).map_err(|_| VerificationKeyError::RecursionNotSupported)?;
-        if recursive_proof_indices != 0 {
-            Err(VerificationKeyError::RecursionNotSupported)?
-        }
+            get_u32(bytes).map_err(|_| VerificationKeyError::InvalidRecursiveProofIndices)?;
+        check_recursive_proof_indices(
+            num_public_inputs,
+            contains_recursive_proof,
+            recursive_proof_indices,
+        )?;
 
         Ok(Self {
             circuit_type,
@@ -411,18 +527,37 @@ impl<H: CurveHooks> VerificationKey<H> {
             id_4,
             contains_recursive_proof,
             recursive_proof_indices,
+            is_recursive_circuit: false,
         })
     }
 }
 
+// The aggregation object must fit in the public inputs. Keys that don't carry a recursive
+// proof must not point anywhere.
+fn check_recursive_proof_indices(
+    num_public_inputs: u32,
+    contains_recursive_proof: bool,
+    recursive_proof_indices: u32,
+) -> Result<(), VerificationKeyError> {
+    let in_bounds = recursive_proof_indices
+        .checked_add(constants::AGGREGATION_OBJECT_SIZE as u32)
+        .is_some_and(|end| end <= num_public_inputs);
+
+    match (contains_recursive_proof, recursive_proof_indices) {
+        (true, _) if in_bounds => Ok(()),
+        (false, 0) => Ok(()),
+        _ => Err(VerificationKeyError::InvalidRecursiveProofIndices),
+    }
+}
+
 fn get_u256(bytes: &[u8]) -> Result<U256, ()> {
     <&[u8; 32]>::try_from(bytes)
         .map_err(|_| ())
         .map(IntoU256::into_u256)
 }
 
-fn get_u32(bytes: &[u8]) -> Result<(u32, &[u8]), ()> {
-    let out = get_u256(&bytes[..32])?;
+pub(crate) fn get_u32(bytes: &[u8]) -> Result<(u32, &[u8]), ()> {
+    let out = get_u256(bytes.get(..32).ok_or(())?)?;
     if out < U256::from(u32::MAX) {
         let mut data = [0u8; 4];
         data.copy_from_slice(&bytes[28..32]);
@@ -433,7 +568,7 @@ fn get_u32(bytes: &[u8]) -> Result<(u32, &[u8]), ()> {
 }
 
 fn get_bool(bytes: &[u8]) -> Result<(bool, &[u8]), ()> {
-    let out = get_u256(&bytes[..32])?;
+    let out = get_u256(bytes.get(..32).ok_or(())?)?;
     if out == U256::from(1u32) {
         Ok((true, &bytes[32..]))
     } else if out == U256::from(0u32) {
@@ -443,7 +578,7 @@ fn get_bool(bytes: &[u8]) -> Result<(bool, &[u8]), ()> {
     }
 }
 
-fn get_g1<H: CurveHooks>(data: &[u8]) -> Result<(G1<H>, &[u8]), ()> {
+pub(crate) fn get_g1<H: CurveHooks>(data: &[u8]) -> Result<(G1<H>, &[u8]), ()> {
     if data.len() < 64 {
         return Err(());
     }
@@ -495,18 +630,40 @@ pub struct PreparedVerificationKey<H: CurveHooks> {
     pub id_4: G1<H>,
     pub contains_recursive_proof: bool,
     pub recursive_proof_indices: u32,
+    /// Kept from the [`VerificationKey`], for it to be converted back as it was parsed.
+    pub is_recursive_circuit: bool,
     pub work_root: Fr,
     pub work_root_inverse: Fr,
     pub domain_inverse: Fr,
+    /// Window tables of [`Self::fixed_bases`], used by the final MSM when present. Only set
+    /// by [`Self::with_fixed_base_tables`], from this very key.
+    pub(crate) fixed_base_tables: Option<FixedBaseTables<H>>,
 }
 
-impl<H: CurveHooks> From<&VerificationKey<H>> for PreparedVerificationKey<H> {
-    fn from(vk: &VerificationKey<H>) -> Self {
-        let log2_circuit_size = ark_std::log2(vk.circuit_size as usize);
-        let work_root = constants::root_of_unity(log2_circuit_size).unwrap();
-        let work_root_inverse = constants::inverse_root_of_unity(log2_circuit_size).unwrap();
-        let domain_inverse = constants::domain_inverse(log2_circuit_size).unwrap();
-        PreparedVerificationKey {
+impl<H: CurveHooks> VerificationKey<H> {
+    /// Checks the invariants that the parsers enforce beyond the encoding of the points:
+    /// circuit type and size, and aggregation object indices. Keys built field by field
+    /// should go through it.
+    pub fn check(&self) -> Result<(), VerificationKeyError> {
+        if self.circuit_type != CircuitType::Ultra as u32 {
+            return Err(VerificationKeyError::InvalidCircuitType);
+        }
+        if !self.circuit_size.is_power_of_two()
+            || self.circuit_size > 2u32.pow(MAX_LOG2_CIRCUIT_SIZE)
+        {
+            return Err(VerificationKeyError::InvalidCircuitSize);
+        }
+        check_recursive_proof_indices(
+            self.num_public_inputs,
+            self.contains_recursive_proof,
+            self.recursive_proof_indices,
+        )
+    }
+}
+
+impl<H: CurveHooks> From<&PreparedVerificationKey<H>> for VerificationKey<H> {
+    fn from(vk: &PreparedVerificationKey<H>) -> Self {
+        VerificationKey {
             circuit_type: vk.circuit_type,
             circuit_size: vk.circuit_size,
             num_public_inputs: vk.num_public_inputs,
@@ -535,19 +692,197 @@ impl<H: CurveHooks> From<&VerificationKey<H>> for PreparedVerificationKey<H> {
             id_4: vk.id_4,
             contains_recursive_proof: vk.contains_recursive_proof,
             recursive_proof_indices: vk.recursive_proof_indices,
+            is_recursive_circuit: vk.is_recursive_circuit,
+        }
+    }
+}
+
+/// Computes the domain values of the key. The conversion stays serial with the `parallel`
+/// feature: it's a few field operations, and a key is meant to be prepared once per circuit.
+impl<H: CurveHooks> TryFrom<&VerificationKey<H>> for PreparedVerificationKey<H> {
+    type Error = VerificationKeyError;
+
+    fn try_from(vk: &VerificationKey<H>) -> Result<Self, Self::Error> {
+        let (work_root, work_root_inverse, domain_inverse) = evaluation_domain(vk.circuit_size)?;
+        Ok(PreparedVerificationKey {
+            circuit_type: vk.circuit_type,
+            circuit_size: vk.circuit_size,
+            num_public_inputs: vk.num_public_inputs,
+            q_1: vk.q_1,
+            q_2: vk.q_2,
+            q_3: vk.q_3,
+            q_4: vk.q_4,
+            q_m: vk.q_m,
+            q_c: vk.q_c,
+            q_arithmetic: vk.q_arithmetic,
+            q_aux: vk.q_aux,
+            q_elliptic: vk.q_elliptic,
+            q_sort: vk.q_sort,
+            sigma_1: vk.sigma_1,
+            sigma_2: vk.sigma_2,
+            sigma_3: vk.sigma_3,
+            sigma_4: vk.sigma_4,
+            table_1: vk.table_1,
+            table_2: vk.table_2,
+            table_3: vk.table_3,
+            table_4: vk.table_4,
+            table_type: vk.table_type,
+            id_1: vk.id_1,
+            id_2: vk.id_2,
+            id_3: vk.id_3,
+            id_4: vk.id_4,
+            contains_recursive_proof: vk.contains_recursive_proof,
+            recursive_proof_indices: vk.recursive_proof_indices,
+            is_recursive_circuit: vk.is_recursive_circuit,
             work_root,
             work_root_inverse,
             domain_inverse,
+            fixed_base_tables: None,
+        })
+    }
+}
+
+/// The evaluation domain of a circuit of `circuit_size` gates: `(ω, ω⁻¹, n⁻¹)`.
+pub(crate) fn evaluation_domain(circuit_size: u32) -> Result<(Fr, Fr, Fr), VerificationKeyError> {
+    if !circuit_size.is_power_of_two() {
+        return Err(VerificationKeyError::InvalidCircuitSize);
+    }
+    let log2_circuit_size = circuit_size.trailing_zeros();
+
+    constants::root_of_unity(log2_circuit_size)
+        .zip(constants::inverse_root_of_unity(log2_circuit_size))
+        .zip(constants::domain_inverse(log2_circuit_size))
+        .map(|((work_root, work_root_inverse), domain_inverse)| {
+            (work_root, work_root_inverse, domain_inverse)
+        })
+        .ok_or(VerificationKeyError::InvalidCircuitSize)
+}
+
+impl<H: CurveHooks> PreparedVerificationKey<H> {
+    /// The bases of the final MSM that only depend on the circuit, in the order they are
+    /// multiplied: the commitments of the key and the G1 generator.
+    pub fn fixed_bases(&self) -> [G1<H>; FIXED_BASES] {
+        [
+            self.q_1,
+            self.q_2,
+            self.q_3,
+            self.q_4,
+            self.q_m,
+            self.q_c,
+            self.q_arithmetic,
+            self.q_sort,
+            self.q_elliptic,
+            self.q_aux,
+            self.sigma_1,
+            self.sigma_2,
+            self.sigma_3,
+            self.sigma_4,
+            self.table_1,
+            self.table_2,
+            self.table_3,
+            self.table_4,
+            self.table_type,
+            self.id_1,
+            self.id_2,
+            self.id_3,
+            self.id_4,
+            G1::<H>::generator(),
+        ]
+    }
+}
+
+// Size of the raw vk fields that all the formats share: header and commitments.
+const RAW_VK_COMMON_SIZE: usize = 1713;
+
+/// The layouts of the raw verification key written by barretenberg (`bb write_vk`).
+///
+/// They only differ in how the aggregation object public input indices are stored after
+/// the commitments.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RawVkFormat {
+    /// bb before v0.31: no indices (1715 bytes).
+    Legacy,
+    /// bb v0.31 and v0.32: length-prefixed indices vector (1719 bytes with no indices).
+    V0_31,
+    /// bb v0.33 onwards: fixed-size indices array (1779 bytes).
+    V0_33,
+}
+
+impl RawVkFormat {
+    /// Detects the format of `raw_vk` from the indices that follow the commitments. A length
+    /// prefix matching the rest of the key identifies [`Self::V0_31`]; the size of the key
+    /// only decides when both layouts fit.
+    pub fn detect(raw_vk: &[u8]) -> Result<Self, VerificationKeyError> {
+        const INDICES_SIZE: usize = constants::AGGREGATION_OBJECT_SIZE * 4;
+
+        // The indices, followed by the is_recursive_circuit flag
+        let tail = raw_vk
+            .get(RAW_VK_COMMON_SIZE + 1..)
+            .filter(|tail| !tail.is_empty())
+            .ok_or(VerificationKeyError::BufferTooShort)?;
+        let indices_size = tail.len() - 1;
+        if indices_size == 0 {
+            return Ok(Self::Legacy);
+        }
+
+        let length_prefixed = read_indices_vector(tail)
+            .is_ok_and(|indices| indices.len().checked_add(4) == Some(indices_size));
+        match (length_prefixed, indices_size == INDICES_SIZE) {
+            // A vector of 15 indices has the size of the fixed array: it can only be the
+            // array if it holds the indices of a recursive proof
+            (true, true) => {
+                let contains_recursive_proof = raw_vk[RAW_VK_COMMON_SIZE] == 1;
+                if contains_recursive_proof
+                    && read_recursive_proof_indices(&tail[..INDICES_SIZE]).is_ok()
+                {
+                    Ok(Self::V0_33)
+                } else {
+                    Ok(Self::V0_31)
+                }
+            }
+            (true, false) => Ok(Self::V0_31),
+            (false, true) => Ok(Self::V0_33),
+            // A vector with a wrong length: the parser rejects it, as too short or followed
+            // by trailing bytes
+            (false, false) if indices_size >= 4 && indices_size % 4 == 0 => Ok(Self::V0_31),
+            (false, false) => Err(VerificationKeyError::UnknownRawFormat { size: raw_vk.len() }),
         }
     }
 }
 
-impl<H: CurveHooks> TryFrom<&[u8]> for VerificationKey<H> {
-    type Error = VerificationKeyError;
+impl<H: CurveHooks> VerificationKey<H> {
+    /// Parses a raw verification key written by barretenberg, detecting its format.
+    ///
+    /// Only UltraPlonk keys are supported: the raw Standard and Turbo layouts are not, and
+    /// are rejected with [`VerificationKeyError::InvalidCircuitType`].
+    pub fn try_from_raw_bytes(raw_vk: &[u8]) -> Result<(Self, RawVkFormat), VerificationKeyError> {
+        Self::try_from_raw_bytes_with_mode(raw_vk, DecodingMode::Strict)
+    }
 
-    fn try_from(raw_vk: &[u8]) -> Result<Self, Self::Error> {
-        const OFFSET_AFTER_COMMITMENTS: usize = 1713;
-        if raw_vk.len() < OFFSET_AFTER_COMMITMENTS + 2 {
+    /// Same as [`Self::try_from_raw_bytes`], but non canonical coordinates are accepted
+    /// when `mode` is [`DecodingMode::Lenient`].
+    pub fn try_from_raw_bytes_with_mode(
+        raw_vk: &[u8],
+        mode: DecodingMode,
+    ) -> Result<(Self, RawVkFormat), VerificationKeyError> {
+        let format = RawVkFormat::detect(raw_vk)?;
+        Ok((Self::parse_raw_bytes(raw_vk, format, mode)?, format))
+    }
+
+    /// Parses a raw verification key written by barretenberg in the given format.
+    pub fn try_from_raw_bytes_with_format(
+        raw_vk: &[u8],
+        format: RawVkFormat,
+    ) -> Result<Self, VerificationKeyError> {
+        Self::parse_raw_bytes(raw_vk, format, DecodingMode::Strict)
+    }
+
+    fn parse_raw_bytes(
+        raw_vk: &[u8],
+        format: RawVkFormat,
+        mode: DecodingMode,
+    ) -> Result<Self, VerificationKeyError> {
+        if raw_vk.len() < RAW_VK_COMMON_SIZE + 2 {
             return Err(VerificationKeyError::BufferTooShort);
         }
 
@@ -578,48 +913,63 @@ impl<H: CurveHooks> TryFrom<&[u8]> for VerificationKey<H> {
             _ => Err(VerificationKeyError::InvalidCommitmentsNumber)?,
         };
 
-        let (id_1, raw_vk) = read_commitment(&CommitmentField::ID_1, raw_vk)?;
-        let (id_2, raw_vk) = read_commitment(&CommitmentField::ID_2, raw_vk)?;
-        let (id_3, raw_vk) = read_commitment(&CommitmentField::ID_3, raw_vk)?;
-        let (id_4, raw_vk) = read_commitment(&CommitmentField::ID_4, raw_vk)?;
-        let (q_1, raw_vk) = read_commitment(&CommitmentField::Q_1, raw_vk)?;
-        let (q_2, raw_vk) = read_commitment(&CommitmentField::Q_2, raw_vk)?;
-        let (q_3, raw_vk) = read_commitment(&CommitmentField::Q_3, raw_vk)?;
-        let (q_4, raw_vk) = read_commitment(&CommitmentField::Q_4, raw_vk)?;
-        let (q_arithmetic, raw_vk) = read_commitment(&CommitmentField::Q_ARITHMETIC, raw_vk)?;
-        let (q_aux, raw_vk) = read_commitment(&CommitmentField::Q_AUX, raw_vk)?;
-        let (q_c, raw_vk) = read_commitment(&CommitmentField::Q_C, raw_vk)?;
-        let (q_elliptic, raw_vk) = read_commitment(&CommitmentField::Q_ELLIPTIC, raw_vk)?;
-        let (q_m, raw_vk) = read_commitment(&CommitmentField::Q_M, raw_vk)?;
-        let (q_sort, raw_vk) = read_commitment(&CommitmentField::Q_SORT, raw_vk)?;
-        let (sigma_1, raw_vk) = read_commitment(&CommitmentField::SIGMA_1, raw_vk)?;
-        let (sigma_2, raw_vk) = read_commitment(&CommitmentField::SIGMA_2, raw_vk)?;
-        let (sigma_3, raw_vk) = read_commitment(&CommitmentField::SIGMA_3, raw_vk)?;
-        let (sigma_4, raw_vk) = read_commitment(&CommitmentField::SIGMA_4, raw_vk)?;
-        let (table_1, raw_vk) = read_commitment(&CommitmentField::TABLE_1, raw_vk)?;
-        let (table_2, raw_vk) = read_commitment(&CommitmentField::TABLE_2, raw_vk)?;
-        let (table_3, raw_vk) = read_commitment(&CommitmentField::TABLE_3, raw_vk)?;
-        let (table_4, raw_vk) = read_commitment(&CommitmentField::TABLE_4, raw_vk)?;
-        let (table_type, raw_vk) = read_commitment(&CommitmentField::TABLE_TYPE, raw_vk)?;
-
-        // debug_assert_eq!(offset, OFFSET_AFTER_COMMITMENTS);
-
-        let (contains_recursive_proof, _raw_vk) = match read_bool(raw_vk) {
-            Ok((false, raw_vk)) => (false, raw_vk),
-            _ => Err(VerificationKeyError::RecursionNotSupported)?,
+        let (id_1, raw_vk) = read_commitment(&CommitmentField::ID_1, raw_vk, mode)?;
+        let (id_2, raw_vk) = read_commitment(&CommitmentField::ID_2, raw_vk, mode)?;
+        let (id_3, raw_vk) = read_commitment(&CommitmentField::ID_3, raw_vk, mode)?;
+        let (id_4, raw_vk) = read_commitment(&CommitmentField::ID_4, raw_vk, mode)?;
+        let (q_1, raw_vk) = read_commitment(&CommitmentField::Q_1, raw_vk, mode)?;
+        let (q_2, raw_vk) = read_commitment(&CommitmentField::Q_2, raw_vk, mode)?;
+        let (q_3, raw_vk) = read_commitment(&CommitmentField::Q_3, raw_vk, mode)?;
+        let (q_4, raw_vk) = read_commitment(&CommitmentField::Q_4, raw_vk, mode)?;
+        let (q_arithmetic, raw_vk) = read_commitment(&CommitmentField::Q_ARITHMETIC, raw_vk, mode)?;
+        let (q_aux, raw_vk) = read_commitment(&CommitmentField::Q_AUX, raw_vk, mode)?;
+        let (q_c, raw_vk) = read_commitment(&CommitmentField::Q_C, raw_vk, mode)?;
+        let (q_elliptic, raw_vk) = read_commitment(&CommitmentField::Q_ELLIPTIC, raw_vk, mode)?;
+        let (q_m, raw_vk) = read_commitment(&CommitmentField::Q_M, raw_vk, mode)?;
+        let (q_sort, raw_vk) = read_commitment(&CommitmentField::Q_SORT, raw_vk, mode)?;
+        let (sigma_1, raw_vk) = read_commitment(&CommitmentField::SIGMA_1, raw_vk, mode)?;
+        let (sigma_2, raw_vk) = read_commitment(&CommitmentField::SIGMA_2, raw_vk, mode)?;
+        let (sigma_3, raw_vk) = read_commitment(&CommitmentField::SIGMA_3, raw_vk, mode)?;
+        let (sigma_4, raw_vk) = read_commitment(&CommitmentField::SIGMA_4, raw_vk, mode)?;
+        let (table_1, raw_vk) = read_commitment(&CommitmentField::TABLE_1, raw_vk, mode)?;
+        let (table_2, raw_vk) = read_commitment(&CommitmentField::TABLE_2, raw_vk, mode)?;
+        let (table_3, raw_vk) = read_commitment(&CommitmentField::TABLE_3, raw_vk, mode)?;
+        let (table_4, raw_vk) = read_commitment(&CommitmentField::TABLE_4, raw_vk, mode)?;
+        let (table_type, raw_vk) = read_commitment(&CommitmentField::TABLE_TYPE, raw_vk, mode)?;
+
+        let (contains_recursive_proof, raw_vk) =
+            read_bool(raw_vk).map_err(|_| VerificationKeyError::InvalidRecursiveProofFlag)?;
+
+        let raw_indices = match format {
+            RawVkFormat::Legacy => &[][..],
+            RawVkFormat::V0_31 => read_indices_vector(raw_vk)?,
+            RawVkFormat::V0_33 => raw_vk
+                .get(..constants::AGGREGATION_OBJECT_SIZE * 4)
+                .ok_or(VerificationKeyError::InvalidRecursiveProofIndices)?,
         };
 
-        let recursive_proof_indices = 0;
-
-        // Note: Since we originally went back by one, I think we can skip the following:
+        // The indices are meaningful only when the key carries a recursive proof: otherwise
+        // bb may leave them uninitialized.
+        let recursive_proof_indices = if contains_recursive_proof {
+            read_recursive_proof_indices(raw_indices)?
+        } else {
+            0
+        };
+        check_recursive_proof_indices(
+            num_public_inputs,
+            contains_recursive_proof,
+            recursive_proof_indices,
+        )?;
 
-        // offset = raw_vk.len() - 1;
-        // let _is_recursive_circuit = read_bool_and_check(
-        //     raw_vk,
-        //     &mut offset,
-        //     false,
-        //     VerificationKeyError::RecursionNotSupported,
-        // )?;
+        let prefix_size = match format {
+            RawVkFormat::V0_31 => 4,
+            _ => 0,
+        };
+        let (is_recursive_circuit, raw_vk) = read_bool(&raw_vk[prefix_size + raw_indices.len()..])
+            .map_err(|_| VerificationKeyError::InvalidRecursiveCircuitFlag)?;
+        if !raw_vk.is_empty() {
+            return Err(VerificationKeyError::TrailingBytes { size: raw_vk.len() });
+        }
 
         Ok(VerificationKey::<H> {
             circuit_type,
@@ -650,34 +1000,86 @@ impl<H: CurveHooks> TryFrom<&[u8]> for VerificationKey<H> {
             id_4,
             contains_recursive_proof,
             recursive_proof_indices,
+            is_recursive_circuit,
         })
     }
 }
 
+impl<H: CurveHooks> TryFrom<&[u8]> for VerificationKey<H> {
+    type Error = VerificationKeyError;
+
+    fn try_from(raw_vk: &[u8]) -> Result<Self, Self::Error> {
+        Self::try_from_raw_bytes(raw_vk).map(|(vk, _)| vk)
+    }
+}
+
 fn read_u32(data: &[u8]) -> Result<(u32, &[u8]), ()> {
-    let value = u32::from_be_bytes(data[..4].try_into().map_err(|_| ())?);
+    let value = u32::from_be_bytes(data.get(..4).ok_or(())?.try_into().map_err(|_| ())?);
     Ok((value, &data[4..]))
 }
 
 fn read_bool(data: &[u8]) -> Result<(bool, &[u8]), ()> {
-    match data[0] {
-        1u8 => Ok((true, &data[1..])),
-        0u8 => Ok((false, &data[1..])),
+    match data.first() {
+        Some(1u8) => Ok((true, &data[1..])),
+        Some(0u8) => Ok((false, &data[1..])),
         _ => Err(()),
     }
 }
 
+// Returns the entries of a length-prefixed indices vector.
+fn read_indices_vector(data: &[u8]) -> Result<&[u8], VerificationKeyError> {
+    data.get(..4)
+        .map(|len| u32::from_be_bytes(len.try_into().unwrap()) as usize)
+        .and_then(|len| len.checked_mul(4))
+        .and_then(|size| data[4..].get(..size))
+        .ok_or(VerificationKeyError::InvalidRecursiveProofIndices)
+}
+
+// We only support contiguous indices, which is what bb produces, and return the first one.
+fn read_recursive_proof_indices(data: &[u8]) -> Result<u32, VerificationKeyError> {
+    const SIZE: usize = constants::AGGREGATION_OBJECT_SIZE;
+
+    if data.len() != SIZE * 4 {
+        Err(VerificationKeyError::InvalidRecursiveProofIndices)?
+    }
+
+    let indices: [u32; SIZE] =
+        core::array::from_fn(|i| u32::from_be_bytes(data[i * 4..(i + 1) * 4].try_into().unwrap()));
+    let first = indices[0];
+    if indices
+        .iter()
+        .enumerate()
+        .any(|(i, &index)| first.checked_add(i as u32) != Some(index))
+    {
+        Err(VerificationKeyError::InvalidRecursiveProofIndices)?
+    }
+
+    Ok(first)
+}
+
+fn write_commitment<H: CurveHooks>(out: &mut Vec<u8>, field: &CommitmentField, point: &G1<H>) {
+    let key = field.str();
+    out.extend((key.len() as u32).to_be_bytes());
+    out.extend(key.as_bytes());
+    out.extend(U256::from(point.x).into_bytes());
+    out.extend(U256::from(point.y).into_bytes());
+}
+
 fn read_commitment<'a, H: CurveHooks>(
     field: &CommitmentField,
     data: &'a [u8],
+    mode: DecodingMode,
 ) -> Result<(G1<H>, &'a [u8]), VerificationKeyError> {
     let expected = field.str();
-    let (key_size, data) = read_u32(data).unwrap();
+    let (key_size, data) = read_u32(data).map_err(|_| VerificationKeyError::BufferTooShort)?;
     let key_size = key_size as usize;
 
     if expected.len() != key_size {
         return Err(VerificationKeyError::InvalidCommitmentKey);
     }
+    if data.len() < key_size + 64 {
+        return Err(VerificationKeyError::BufferTooShort);
+    }
 
     let key = String::from_utf8(data[..key_size].to_vec())
         .map_err(|_| VerificationKeyError::InvalidCommitmentKey)?;
@@ -693,7 +1095,7 @@ fn read_commitment<'a, H: CurveHooks>(
     }
 
     Ok((
-        read_g1::<H>(&field, &data[key_size..key_size + 64])?,
+        read_g1::<H>(&field, &data[key_size..key_size + 64], mode)?,
         &data[key_size + 64..],
     ))
 }
@@ -701,9 +1103,12 @@ fn read_commitment<'a, H: CurveHooks>(
 fn read_g1<H: CurveHooks>(
     field: &CommitmentField,
     data: &[u8],
+    mode: DecodingMode,
 ) -> Result<G1<H>, VerificationKeyError> {
-    read_g1_util::<H>(data, false).map_err(|e| match e {
+    read_g1_util::<H>(data, false, mode).map_err(|e| match e {
         GroupError::NotOnCurve => VerificationKeyError::PointNotOnCurve { field: field.str() },
+        GroupError::NotMember => VerificationKeyError::NotMember { field: field.str() },
+        GroupError::PointAtInfinity => VerificationKeyError::PointAtInfinity { field: field.str() },
         // GroupError::NotInSubgroup => {
         //     VerificationKeyError::PointNotInCorrectSubgroup { field: field.str() }
         // }
@@ -717,26 +1122,10 @@ fn read_g1<H: CurveHooks>(
     })
 }
 
-// Parse point on G2
-pub(crate) fn read_g2<H: CurveHooks>(data: &[u8]) -> Result<G2<H>, ()> {
-    if data.len() != 128 {
-        return Err(());
-    }
-
-    let x_c0 = read_fq_util(&data[0..32]).expect("Parsing the SRS should always succeed!");
-    let x_c1 = read_fq_util(&data[32..64]).expect("Parsing the SRS should always succeed!");
-    let y_c0 = read_fq_util(&data[64..96]).expect("Parsing the SRS should always succeed!");
-    let y_c1 = read_fq_util(&data[96..128]).expect("Parsing the SRS should always succeed!");
-
-    let x = Fq2::new(x_c0, x_c1);
-    let y = Fq2::new(y_c0, y_c1);
-
-    Ok(G2::<H>::new(x, y))
-}
-
 #[cfg(test)]
 mod should {
     use super::*;
+    use ark_ff::BigInteger;
     use rstest::{fixture, rstest};
 
     #[fixture]
@@ -918,9 +1307,274 @@ mod should {
         pretty_assertions::assert_eq!(valid_vk, vk.as_slice())
     }
 
+    // Appends the aggregation object indices in the given layout to the commitments of a raw
+    // vk with 16 public inputs.
+    fn recursive_raw_vk(valid_raw_vk: [u8; 1715], length_prefixed: bool) -> Vec<u8> {
+        let mut raw_vk = valid_raw_vk[..1713].to_vec();
+        raw_vk[11] = 16;
+        raw_vk.push(1);
+        if length_prefixed {
+            raw_vk.extend(16u32.to_be_bytes());
+        }
+        (0u32..16).for_each(|i| raw_vk.extend(i.to_be_bytes()));
+        raw_vk.push(1);
+        raw_vk
+    }
+
+    #[rstest]
+    fn serialize_a_legacy_raw_vk(valid_raw_vk: [u8; 1715]) {
+        let vk = VerificationKey::<()>::try_from(&valid_raw_vk[..]).unwrap();
+
+        pretty_assertions::assert_eq!(
+            vk.as_raw_bytes(RawVkFormat::Legacy).unwrap(),
+            valid_raw_vk.to_vec()
+        );
+    }
+
+    #[rstest]
+    fn serialize_deserialize_raw_vk(
+        valid_raw_vk: [u8; 1715],
+        #[values(RawVkFormat::Legacy, RawVkFormat::V0_31, RawVkFormat::V0_33)] format: RawVkFormat,
+    ) {
+        let vk = VerificationKey::<()>::try_from(&valid_raw_vk[..]).unwrap();
+        let raw_vk = vk.as_raw_bytes(format).unwrap();
+
+        assert_eq!(
+            VerificationKey::<()>::try_from_raw_bytes(&raw_vk),
+            Ok((vk, format))
+        );
+    }
+
+    #[rstest]
+    fn serialize_a_recursive_raw_vk(
+        valid_raw_vk: [u8; 1715],
+        #[values(false, true)] length_prefixed: bool,
+    ) {
+        let raw_vk = recursive_raw_vk(valid_raw_vk, length_prefixed);
+        let (vk, format) = VerificationKey::<()>::try_from_raw_bytes(&raw_vk).unwrap();
+
+        pretty_assertions::assert_eq!(vk.as_raw_bytes(format).unwrap(), raw_vk);
+    }
+
+    #[rstest]
+    #[case::v0_31(include_bytes!("../tests/resources/v0.31.0/vk.bin"))]
+    #[case::v0_32(include_bytes!("../tests/resources/v0.32.0/vk.bin"))]
+    #[case::v0_33(include_bytes!("../tests/resources/v0.33.0/vk.bin"))]
+    #[case::v1_0_0_beta_1(include_bytes!("../tests/resources/v1.0.0-beta.1/vk.bin"))]
+    fn serialize_a_bb_raw_vk_back_to_its_format(#[case] raw_vk: &[u8]) {
+        let (vk, format) = VerificationKey::<()>::try_from_raw_bytes(raw_vk).unwrap();
+        let mut expected = raw_vk.to_vec();
+        if format == RawVkFormat::V0_33 && !vk.contains_recursive_proof {
+            // bb leaves these indices uninitialized: they are written back as zeros
+            expected[RAW_VK_COMMON_SIZE + 1..RAW_VK_COMMON_SIZE + 1 + 64].fill(0);
+        }
+
+        let serialized = vk.as_raw_bytes(format).unwrap();
+
+        pretty_assertions::assert_eq!(serialized, expected);
+        assert_eq!(
+            VerificationKey::<()>::try_from_raw_bytes(&serialized),
+            Ok((vk, format))
+        );
+    }
+
+    #[rstest]
+    fn keep_the_recursive_circuit_flag_of_a_raw_vk(valid_raw_vk: [u8; 1715]) {
+        let mut raw_vk = valid_raw_vk;
+        raw_vk[1714] = 1;
+
+        let vk = VerificationKey::<()>::try_from(&raw_vk[..]).unwrap();
+
+        assert!(vk.is_recursive_circuit);
+        pretty_assertions::assert_eq!(
+            vk.as_raw_bytes(RawVkFormat::Legacy).unwrap(),
+            raw_vk.to_vec()
+        );
+    }
+
+    #[rstest]
+    fn keep_the_recursive_circuit_flag_through_a_prepared_vk(
+        valid_raw_vk: [u8; 1715],
+        #[values(false, true)] is_recursive_circuit: bool,
+    ) {
+        let mut raw_vk = valid_raw_vk;
+        raw_vk[1714] = is_recursive_circuit as u8;
+        let vk = VerificationKey::<()>::try_from(&raw_vk[..]).unwrap();
+
+        let prepared_vk = PreparedVerificationKey::try_from(&vk).unwrap();
+
+        assert_eq!(prepared_vk.is_recursive_circuit, is_recursive_circuit);
+        assert_eq!(VerificationKey::from(&prepared_vk), vk);
+        pretty_assertions::assert_eq!(
+            VerificationKey::from(&prepared_vk)
+                .as_raw_bytes(RawVkFormat::Legacy)
+                .unwrap(),
+            raw_vk.to_vec()
+        );
+    }
+
+    #[rstest]
+    fn deserialize_serialize_recursive_solidity_vk(valid_vk: [u8; VK_SIZE]) {
+        let mut recursive_vk = [0u8; VK_SIZE];
+        recursive_vk.copy_from_slice(&valid_vk);
+        recursive_vk[95] = 16;
+        recursive_vk[VK_SIZE - 33] = 1;
+
+        let deserialized_vk =
+            VerificationKey::<()>::try_from_solidity_bytes(&recursive_vk).unwrap();
+        assert!(deserialized_vk.contains_recursive_proof);
+        assert_eq!(deserialized_vk.recursive_proof_indices, 0);
+
+        let vk = deserialized_vk.as_solidity_bytes();
+        pretty_assertions::assert_eq!(recursive_vk, vk.as_slice())
+    }
+
+    #[rstest]
+    fn deserialize_recursive_raw_vk(
+        valid_raw_vk: [u8; 1715],
+        #[values(false, true)] length_prefixed: bool,
+    ) {
+        let raw_vk = recursive_raw_vk(valid_raw_vk, length_prefixed);
+
+        let vk = VerificationKey::<()>::try_from(&raw_vk[..]).unwrap();
+        assert!(vk.contains_recursive_proof);
+        assert_eq!(vk.recursive_proof_indices, 0);
+    }
+
+    #[rstest]
+    fn detect_the_raw_vk_format(valid_raw_vk: [u8; 1715]) {
+        assert_eq!(RawVkFormat::detect(&valid_raw_vk), Ok(RawVkFormat::Legacy));
+        assert_eq!(
+            RawVkFormat::detect(&recursive_raw_vk(valid_raw_vk, true)),
+            Ok(RawVkFormat::V0_31)
+        );
+        assert_eq!(
+            RawVkFormat::detect(&recursive_raw_vk(valid_raw_vk, false)),
+            Ok(RawVkFormat::V0_33)
+        );
+    }
+
+    #[rstest]
+    fn detect_a_v0_31_raw_vk_with_the_size_of_a_v0_33_one(valid_raw_vk: [u8; 1715]) {
+        let mut raw_vk = valid_raw_vk[..1713].to_vec();
+        raw_vk.push(0);
+        raw_vk.extend(15u32.to_be_bytes());
+        (0u32..15).for_each(|i| raw_vk.extend((100 + i).to_be_bytes()));
+        raw_vk.push(0);
+        assert_eq!(raw_vk.len(), recursive_raw_vk(valid_raw_vk, false).len());
+
+        assert_eq!(RawVkFormat::detect(&raw_vk), Ok(RawVkFormat::V0_31));
+    }
+
+    #[rstest]
+    fn detect_a_v0_33_raw_vk_starting_like_a_length_prefix(valid_raw_vk: [u8; 1715]) {
+        let mut raw_vk = valid_raw_vk[..1713].to_vec();
+        raw_vk[11] = 31;
+        raw_vk.push(1);
+        (15u32..31).for_each(|i| raw_vk.extend(i.to_be_bytes()));
+        raw_vk.push(0);
+
+        assert_eq!(RawVkFormat::detect(&raw_vk), Ok(RawVkFormat::V0_33));
+        let vk = VerificationKey::<()>::try_from(&raw_vk[..]).unwrap();
+        assert_eq!(vk.recursive_proof_indices, 15);
+    }
+
+    #[rstest]
+    fn ignore_recursive_proof_indices_of_a_non_recursive_raw_vk(valid_raw_vk: [u8; 1715]) {
+        let mut raw_vk = recursive_raw_vk(valid_raw_vk, false);
+        raw_vk[1713] = 0;
+        raw_vk[1714..1714 + 64].fill(0xff);
+
+        let vk = VerificationKey::<()>::try_from(&raw_vk[..]).unwrap();
+        assert!(!vk.contains_recursive_proof);
+        assert_eq!(vk.recursive_proof_indices, 0);
+    }
+
+    #[rstest]
+    fn read_the_circuit_type_of_a_vk(valid_vk: [u8; VK_SIZE]) {
+        assert_eq!(
+            CircuitType::from_solidity_bytes(&valid_vk).unwrap(),
+            CircuitType::Ultra
+        );
+    }
+
+    // Adds the Fq modulus to the 32 bytes big-endian value in `data`.
+    fn add_fq_modulus(data: &mut [u8]) {
+        let mut value = <[u8; 32]>::try_from(&*data).unwrap().into_u256();
+        assert!(!value.add_with_carry(&Fq::MODULUS));
+        data.copy_from_slice(&value.into_bytes());
+    }
+
+    #[rstest]
+    fn parse_a_non_canonical_raw_vk_in_lenient_mode(valid_raw_vk: [u8; 1715]) {
+        let mut raw_vk = valid_raw_vk;
+        add_fq_modulus(&mut raw_vk[24..24 + 32]);
+
+        assert_eq!(
+            VerificationKey::<()>::try_from_raw_bytes_with_mode(&raw_vk, DecodingMode::Lenient),
+            VerificationKey::<()>::try_from_raw_bytes(&valid_raw_vk)
+        );
+    }
+
     mod reject {
         use super::*;
 
+        #[rstest]
+        fn a_bb_raw_vk_followed_by_junk(
+            #[values(
+                &include_bytes!("../tests/resources/v0.31.0/vk.bin")[..],
+                &include_bytes!("../tests/resources/v0.32.0/vk.bin")[..],
+                &include_bytes!("../tests/resources/v0.33.0/vk.bin")[..],
+                &include_bytes!("../tests/resources/v0.34.0/vk.bin")[..],
+                &include_bytes!("../tests/resources/v0.35.0/vk.bin")[..],
+                &include_bytes!("../tests/resources/v0.36.0/vk.bin")[..],
+                &include_bytes!("../tests/resources/v0.37.0/vk.bin")[..],
+                &include_bytes!("../tests/resources/v0.38.0/vk.bin")[..],
+                &include_bytes!("../tests/resources/v0.39.0/vk.bin")[..],
+                &include_bytes!("../tests/resources/v1.0.0-beta.0/vk.bin")[..],
+                &include_bytes!("../tests/resources/v1.0.0-beta.1/vk.bin")[..]
+            )]
+            raw_vk: &[u8],
+            #[values(1, 4, 7, 64)] junk_size: usize,
+        ) {
+            let (_, format) = VerificationKey::<()>::try_from_raw_bytes(raw_vk).unwrap();
+            let mut junk_vk = raw_vk.to_vec();
+            junk_vk.extend((0..junk_size).map(|i| i as u8));
+
+            assert!(VerificationKey::<()>::try_from_raw_bytes(&junk_vk).is_err());
+            assert_eq!(
+                VerificationKey::<()>::try_from_raw_bytes_with_format(&junk_vk, format),
+                Err(VerificationKeyError::TrailingBytes { size: junk_size })
+            );
+        }
+
+        #[rstest]
+        fn a_raw_vk_with_an_empty_indices_vector_followed_by_junk(valid_raw_vk: [u8; 1715]) {
+            let mut raw_vk = valid_raw_vk[..1714].to_vec();
+            raw_vk.extend(0u32.to_be_bytes());
+            raw_vk.push(0);
+            raw_vk.extend([0xff; 8]);
+
+            assert_eq!(
+                VerificationKey::<()>::try_from(&raw_vk[..]),
+                Err(VerificationKeyError::TrailingBytes { size: 8 })
+            );
+        }
+
+        #[rstest]
+        fn to_prepare_a_vk_with_an_invalid_circuit_size(
+            valid_vk: [u8; VK_SIZE],
+            #[values(0, 3, 1 << 29, u32::MAX)] circuit_size: u32,
+        ) {
+            let mut vk = VerificationKey::<()>::try_from_solidity_bytes(&valid_vk).unwrap();
+            vk.circuit_size = circuit_size;
+
+            assert_eq!(
+                PreparedVerificationKey::try_from(&vk).unwrap_err(),
+                VerificationKeyError::InvalidCircuitSize
+            );
+        }
+
         #[rstest]
         fn a_vk_from_a_short_buffer() {
             let invalid_vk = [0u8; 10];
@@ -941,6 +1595,17 @@ mod should {
             );
         }
 
+        #[rstest]
+        fn an_unknown_circuit_type(valid_vk: [u8; VK_SIZE]) {
+            let mut invalid_vk = valid_vk;
+            invalid_vk[31] = 3;
+
+            assert_eq!(
+                CircuitType::from_solidity_bytes(&invalid_vk).unwrap_err(),
+                VerificationKeyError::InvalidCircuitType
+            );
+        }
+
         #[rstest]
         fn a_vk_with_invalid_circuit_type(valid_vk: [u8; VK_SIZE]) {
             let mut invalid_vk = [0u8; VK_SIZE];
@@ -1069,7 +1734,7 @@ mod should {
         fn a_raw_vk_with_a_point_not_on_curve(valid_raw_vk: [u8; 1715]) {
             let mut invalid_vk = [0u8; 1715];
             invalid_vk.copy_from_slice(&valid_raw_vk);
-            invalid_vk[24..24 + 64].fill(0);
+            invalid_vk[24..24 + 32].fill(0);
 
             assert_eq!(
                 VerificationKey::<()>::try_from(&invalid_vk[..]).unwrap_err(),
@@ -1078,14 +1743,48 @@ mod should {
         }
 
         #[rstest]
-        fn a_vk_containing_a_recursive_proof(valid_vk: [u8; VK_SIZE]) {
+        fn a_raw_vk_with_a_point_at_infinity(valid_raw_vk: [u8; 1715]) {
+            let mut invalid_vk = valid_raw_vk;
+            invalid_vk[24..24 + 64].fill(0);
+
+            assert_eq!(
+                VerificationKey::<()>::try_from(&invalid_vk[..]).unwrap_err(),
+                VerificationKeyError::PointAtInfinity { field: "ID_1" }
+            );
+        }
+
+        #[rstest]
+        fn a_raw_vk_with_a_non_canonical_coordinate(valid_raw_vk: [u8; 1715]) {
+            let mut invalid_vk = valid_raw_vk;
+            add_fq_modulus(&mut invalid_vk[24 + 32..24 + 64]);
+
+            assert_eq!(
+                VerificationKey::<()>::try_from(&invalid_vk[..]).unwrap_err(),
+                VerificationKeyError::NotMember { field: "ID_1" }
+            );
+        }
+
+        #[rstest]
+        fn a_vk_with_an_invalid_recursive_proof_flag(valid_vk: [u8; VK_SIZE]) {
+            let mut invalid_vk = [0u8; VK_SIZE];
+            invalid_vk.copy_from_slice(&valid_vk);
+            invalid_vk[VK_SIZE - 33] = 2;
+
+            assert_eq!(
+                VerificationKey::<()>::try_from_solidity_bytes(&invalid_vk[..]).unwrap_err(),
+                VerificationKeyError::InvalidRecursiveProofFlag
+            );
+        }
+
+        #[rstest]
+        fn a_vk_whose_aggregation_object_exceeds_the_public_inputs(valid_vk: [u8; VK_SIZE]) {
             let mut invalid_vk = [0u8; VK_SIZE];
             invalid_vk.copy_from_slice(&valid_vk);
             invalid_vk[VK_SIZE - 33] = 1;
 
             assert_eq!(
                 VerificationKey::<()>::try_from_solidity_bytes(&invalid_vk[..]).unwrap_err(),
-                VerificationKeyError::RecursionNotSupported
+                VerificationKeyError::InvalidRecursiveProofIndices
             );
         }
 
@@ -1097,7 +1796,7 @@ mod should {
 
             assert_eq!(
                 VerificationKey::<()>::try_from_solidity_bytes(&invalid_vk[..]).unwrap_err(),
-                VerificationKeyError::RecursionNotSupported
+                VerificationKeyError::InvalidRecursiveProofIndices
             );
         }
 
@@ -1109,19 +1808,74 @@ mod should {
 
             assert_eq!(
                 VerificationKey::<()>::try_from_solidity_bytes(&invalid_vk[..]).unwrap_err(),
-                VerificationKeyError::RecursionNotSupported
+                VerificationKeyError::InvalidRecursiveProofIndices
             );
         }
 
         #[rstest]
-        fn a_raw_vk_containing_a_recursive_proof(valid_raw_vk: [u8; 1715]) {
+        fn a_raw_vk_containing_a_recursive_proof_without_indices(valid_raw_vk: [u8; 1715]) {
             let mut invalid_vk = [0u8; 1715];
             invalid_vk.copy_from_slice(&valid_raw_vk);
             invalid_vk[1713] = 1; // VK_SIZE - 33
 
             assert_eq!(
                 VerificationKey::<()>::try_from(&invalid_vk[..]).unwrap_err(),
-                VerificationKeyError::RecursionNotSupported
+                VerificationKeyError::InvalidRecursiveProofIndices
+            );
+        }
+
+        #[rstest]
+        fn to_serialize_a_recursive_vk_in_the_legacy_raw_format(valid_raw_vk: [u8; 1715]) {
+            let raw_vk = recursive_raw_vk(valid_raw_vk, false);
+            let vk = VerificationKey::<()>::try_from(&raw_vk[..]).unwrap();
+
+            assert_eq!(
+                vk.as_raw_bytes(RawVkFormat::Legacy).unwrap_err(),
+                VerificationKeyError::InvalidRecursiveProofIndices
+            );
+        }
+
+        #[rstest]
+        fn a_raw_vk_with_an_invalid_recursive_circuit_flag(valid_raw_vk: [u8; 1715]) {
+            let mut invalid_vk = valid_raw_vk;
+            invalid_vk[1714] = 2;
+
+            assert_eq!(
+                VerificationKey::<()>::try_from(&invalid_vk[..]).unwrap_err(),
+                VerificationKeyError::InvalidRecursiveCircuitFlag
+            );
+        }
+
+        #[rstest]
+        fn a_raw_vk_of_unknown_format(valid_raw_vk: [u8; 1715]) {
+            let mut invalid_vk = valid_raw_vk.to_vec();
+            invalid_vk.push(0);
+
+            assert_eq!(
+                VerificationKey::<()>::try_from(&invalid_vk[..]).unwrap_err(),
+                VerificationKeyError::UnknownRawFormat { size: 1716 }
+            );
+        }
+
+        #[rstest]
+        fn a_raw_vk_with_non_contiguous_recursive_proof_indices(valid_raw_vk: [u8; 1715]) {
+            let mut invalid_vk = recursive_raw_vk(valid_raw_vk, false);
+            invalid_vk[1714 + 4 * 15 + 3] = 0;
+
+            assert_eq!(
+                VerificationKey::<()>::try_from(&invalid_vk[..]).unwrap_err(),
+                VerificationKeyError::InvalidRecursiveProofIndices
+            );
+        }
+
+        #[rstest]
+        fn a_raw_vk_with_a_wrong_number_of_recursive_proof_indices(valid_raw_vk: [u8; 1715]) {
+            let mut invalid_vk = recursive_raw_vk(valid_raw_vk, true);
+            invalid_vk[1714 + 3] = 15;
+
+            assert_eq!(
+                VerificationKey::<()>::try_from(&invalid_vk[..]).unwrap_err(),
+                VerificationKeyError::InvalidRecursiveProofIndices
             );
         }
 
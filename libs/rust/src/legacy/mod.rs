@@ -0,0 +1,410 @@
+// Copyright 2022 Aztec
+// Copyright 2024 Horizen Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0 or MIT
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Verifiers for circuits built with the barretenberg Standard (`circuit_type == 0`) and
+//! Turbo (`circuit_type == 1`) composers.
+//!
+//! Both follow the UltraPlonk verifier of the crate root: the keys and the proofs use the
+//! same Solidity word layout, the challenges are derived with the same transcript
+//! and the quotient is evaluated by the verifier and batched in a single KZG opening. They
+//! only differ in their wires, selectors and widgets. Neither composer has lookups nor
+//! identity permutation commitments: wire `i` is mapped to the coset `kᵢ.H`.
+//!
+//! The key and proof layouts are derived from the UltraPlonk Solidity one, not from keys
+//! and proofs written by bb: there are no Standard or Turbo fixtures in the repo, and the
+//! verifiers are only covered by synthetic tests. Treat them as experimental. Raw bb keys
+//! of these composers are not supported, and every entry point of the crate root
+//! ([`crate::verify`], [`crate::verify_prepared`], [`crate::verify_batch`],
+//! [`crate::precheck`], [`crate::stepwise`], ...) is UltraPlonk only: legacy proofs are
+//! only verified when explicitly asked for, through [`standard::verify`] and
+//! [`turbo::verify`].
+
+pub mod standard;
+pub mod turbo;
+
+use alloc::vec::Vec;
+
+use ark_bn254_ext::{Config, CurveHooks};
+use ark_ec::{short_weierstrass::SWCurveConfig, CurveGroup};
+use ark_ff::{Field, MontFp};
+use ark_models_ext::bn::BnConfig;
+
+use crate::{
+    compute_coset_public_input_delta,
+    errors::VerifyError,
+    generate_gamma_challenge, generate_initial_challenge,
+    hooks::VerifierHooks,
+    key::evaluation_domain,
+    transcript::Transcript,
+    utils::{IntoBytes, IntoFr},
+    Fr, G1, U256,
+};
+
+/// The identity permutation cosets: wire `i` is mapped to `kᵢ.ωʲ`.
+const COSETS: [Fr; 4] = [MontFp!("1"), MontFp!("5"), MontFp!("6"), MontFp!("7")];
+
+/// The coset that public inputs are copied to.
+const EXTERNAL_COSET: Fr = MontFp!("12");
+
+#[derive(Debug)]
+pub(crate) struct Challenges {
+    alpha: Fr,
+    beta: Fr,
+    gamma: Fr,
+    zeta: Fr,
+    zeta_pow_n: Fr,
+}
+
+/// A polynomial opened by the batched KZG proof: its commitment, its evaluation at ζ
+/// and, for the shifted polynomials, its evaluation at ζω.
+pub(crate) struct Opening<H: CurveHooks> {
+    commitment: G1<H>,
+    eval: Fr,
+    omega_eval: Option<Fr>,
+}
+
+impl<H: CurveHooks> Opening<H> {
+    fn new(commitment: G1<H>, eval: Fr) -> Self {
+        Opening {
+            commitment,
+            eval,
+            omega_eval: None,
+        }
+    }
+
+    fn shifted(commitment: G1<H>, eval: Fr, omega_eval: Fr) -> Self {
+        Opening {
+            commitment,
+            eval,
+            omega_eval: Some(omega_eval),
+        }
+    }
+}
+
+/// The domain values of a circuit of `circuit_size` gates: `(ω, ω⁻¹, n⁻¹)`.
+fn domain(circuit_size: u32) -> Result<(Fr, Fr, Fr), VerifyError> {
+    evaluation_domain(circuit_size).map_err(|_| VerifyError::KeyError)
+}
+
+fn hash_points<H: CurveHooks, T: Transcript<H>>(hasher: &mut T, points: &[G1<H>]) {
+    for point in points {
+        hasher.update(point.y.into_bytes());
+        hasher.update(point.x.into_bytes());
+    }
+}
+
+/// Generates the `β, γ, α, ζ` challenges and returns them along with the current
+/// transcript state, which seeds the nu challenges.
+fn generate_challenges<H: CurveHooks, T: Transcript<H>>(
+    hooks: &H,
+    circuit_size: u32,
+    num_public_inputs: u32,
+    public_inputs: &[U256],
+    wires: &[G1<H>],
+    z: &G1<H>,
+    quotient: &[G1<H>],
+) -> (Challenges, [u8; 32]) {
+    let challenge = generate_initial_challenge::<H, T>(hooks, circuit_size, num_public_inputs);
+
+    let mut hasher = T::new();
+    hasher.update(challenge);
+    for input in public_inputs {
+        hasher.update(input.into_bytes());
+    }
+    hash_points(&mut hasher, wires);
+    let challenge: [u8; 32] = hasher.finalize(hooks);
+    let beta = challenge.into_fr();
+
+    let challenge = generate_gamma_challenge::<H, T>(hooks, &challenge);
+    let gamma = challenge.into_fr();
+
+    let mut hasher = T::new();
+    hasher.update(challenge);
+    hash_points(&mut hasher, &[*z]);
+    let challenge: [u8; 32] = hasher.finalize(hooks);
+    let alpha = challenge.into_fr();
+
+    let mut hasher = T::new();
+    hasher.update(challenge);
+    hash_points(&mut hasher, quotient);
+    let challenge: [u8; 32] = hasher.finalize(hooks);
+    let zeta = challenge.into_fr();
+
+    // compute zeta^n, where n is a power of 2
+    let mut zeta_pow_n = zeta;
+    let mut count = 1;
+    while count < circuit_size {
+        zeta_pow_n.square_in_place();
+        count <<= 1;
+    }
+
+    (
+        Challenges {
+            alpha,
+            beta,
+            gamma,
+            zeta,
+            zeta_pow_n,
+        },
+        challenge,
+    )
+}
+
+/// Computes `[public_input_delta, zero_poly, zero_poly_inverse, l_start, l_end]`. As for
+/// UltraPlonk, the last 4 roots are cut out of Z_H.
+fn compute_lagrange_and_vanishing_poly(
+    challenges: &Challenges,
+    work_root: Fr,
+    work_root_inverse: Fr,
+    domain_inverse: Fr,
+    public_inputs: &[U256],
+) -> Result<[Fr; 5], VerifyError> {
+    let (delta_numerator, delta_denominator) = compute_coset_public_input_delta(
+        public_inputs,
+        &work_root,
+        &challenges.beta,
+        &challenges.gamma,
+        COSETS[0],
+        EXTERNAL_COSET,
+    )?;
+
+    let vanishing_numerator = challenges.zeta_pow_n - Fr::ONE;
+
+    let mut root = -work_root_inverse;
+    let mut vanishing_denominator = challenges.zeta + root;
+    for _ in 0..3 {
+        root *= work_root_inverse;
+        vanishing_denominator *= challenges.zeta + root;
+    }
+
+    let lagrange_numerator = vanishing_numerator * domain_inverse;
+    let l_start_denominator = challenges.zeta - Fr::ONE;
+    let l_end_denominator = work_root.square().square() * work_root * challenges.zeta - Fr::ONE;
+
+    let mut inverses = [
+        delta_denominator,
+        vanishing_denominator,
+        vanishing_numerator,
+        l_start_denominator,
+        l_end_denominator,
+    ];
+
+    ark_ff::fields::batch_inversion(&mut inverses);
+
+    Ok([
+        delta_numerator * inverses[0],
+        vanishing_numerator * inverses[1],
+        vanishing_denominator * inverses[2],
+        lagrange_numerator * inverses[3],
+        lagrange_numerator * inverses[4],
+    ])
+}
+
+/// The copy constraints identity over `wires.len()` wires, whose identity permutations are
+/// the [`COSETS`]. Uses 3 powers of alpha.
+#[allow(clippy::too_many_arguments)]
+fn compute_permutation_widget_evaluation(
+    challenges: &Challenges,
+    mut alpha_base: Fr,
+    wires: &[Fr],
+    sigmas: &[Fr],
+    z_eval: Fr,
+    z_omega_eval: Fr,
+    l_start: &Fr,
+    l_end: &Fr,
+    public_input_delta: &Fr,
+) -> (Fr, Fr) {
+    let mut numerator = z_eval;
+    let mut denominator = z_omega_eval;
+    for ((wire, sigma), k) in wires.iter().zip(sigmas).zip(COSETS) {
+        numerator *= *wire + challenges.gamma + challenges.beta * k * challenges.zeta;
+        denominator *= *wire + challenges.gamma + challenges.beta * sigma;
+    }
+
+    let mut result = alpha_base * (numerator - denominator);
+
+    alpha_base *= challenges.alpha;
+    result += alpha_base * l_end * (z_omega_eval - public_input_delta);
+    alpha_base *= challenges.alpha;
+    let permutation_identity = result + alpha_base * l_start * (z_eval - Fr::ONE);
+    alpha_base *= challenges.alpha;
+
+    (permutation_identity, alpha_base)
+}
+
+/// Derives the nu challenges from `c_current` and all the claimed evaluations, then
+/// batches `openings` and the quotient commitments `quotient` into the
+/// `(PAIRING_LHS, PAIRING_RHS)` couple, with `PAIRING_LHS` already negated.
+#[allow(clippy::too_many_arguments)]
+fn compute_pairing_points<H: VerifierHooks, T: Transcript<H>>(
+    hooks: &H,
+    challenges: &Challenges,
+    c_current: &[u8; 32],
+    quotient: &[G1<H>],
+    quotient_eval: &Fr,
+    openings: &[Opening<H>],
+    pi_z: &G1<H>,
+    pi_z_omega: &G1<H>,
+    work_root: &Fr,
+) -> Result<(G1<H>, G1<H>), VerifyError> {
+    // nu = H(c_current, t(ζ), evaluations at ζ, evaluations at ζω)
+    let mut hasher = T::new();
+    hasher.update(c_current);
+    hasher.update(quotient_eval.into_bytes());
+    for opening in openings {
+        hasher.update(opening.eval.into_bytes());
+    }
+    for omega_eval in openings.iter().filter_map(|opening| opening.omega_eval) {
+        hasher.update(omega_eval.into_bytes());
+    }
+    let challenge: [u8; 32] = hasher.finalize(hooks);
+
+    let c_v = (0..openings.len())
+        .map(|i| {
+            if i == 0 {
+                challenge.into_fr()
+            } else {
+                let hash = T::new()
+                    .chain_update(challenge)
+                    .chain_update([i as u8])
+                    .finalize(hooks);
+                hash.into_fr()
+            }
+        })
+        .collect::<Vec<Fr>>();
+
+    let mut hasher = T::new();
+    hasher.update(challenge);
+    hash_points(&mut hasher, &[*pi_z, *pi_z_omega]);
+    let hash: [u8; 32] = hasher.finalize(hooks);
+    let c_u = hash.into_fr();
+    let u_plus_one = c_u + Fr::ONE;
+
+    let mut bases = Vec::with_capacity(quotient.len() + openings.len() + 3);
+    let mut scalars = Vec::with_capacity(quotient.len() + openings.len() + 3);
+
+    // [T1] + ζⁿ.[T2] + ζ²ⁿ.[T3] + ...
+    let mut zeta_pow = Fr::ONE;
+    for t in quotient {
+        bases.push(*t);
+        scalars.push(zeta_pow);
+        zeta_pow *= challenges.zeta_pow_n;
+    }
+
+    let mut batch_evaluation = *quotient_eval;
+    for (opening, c_v) in openings.iter().zip(c_v) {
+        bases.push(opening.commitment);
+        match opening.omega_eval {
+            Some(omega_eval) => {
+                scalars.push(c_v * u_plus_one);
+                batch_evaluation += c_v * (omega_eval * c_u + opening.eval);
+            }
+            None => {
+                scalars.push(c_v);
+                batch_evaluation += c_v * opening.eval;
+            }
+        }
+    }
+
+    bases.push(<<Config<H> as BnConfig>::G1Config as SWCurveConfig>::GENERATOR);
+    scalars.push(-batch_evaluation);
+    bases.push(*pi_z);
+    scalars.push(challenges.zeta);
+    bases.push(*pi_z_omega);
+    scalars.push(challenges.zeta * c_u * work_root);
+
+    let pairing_rhs = hooks
+        .msm_g1(&bases, &scalars)
+        .map_err(|_| VerifyError::CurveHooksError)?;
+
+    // PAIRING_LHS = [PI_Z] + [PI_Z_OMEGA] * u
+    let mut pairing_lhs = hooks
+        .msm_g1(&[*pi_z_omega, *pi_z], &[c_u, Fr::ONE])
+        .map_err(|_| VerifyError::CurveHooksError)?;
+    pairing_lhs.y = -pairing_lhs.y;
+
+    Ok((pairing_lhs.into_affine(), pairing_rhs.into_affine()))
+}
+
+/// `δ.(δ - 1).(δ - 2).(δ - 3)`: vanishes iff `δ` is a base 4 digit.
+fn quad_identity(delta: Fr) -> Fr {
+    delta * (delta - Fr::ONE) * (delta - MontFp!("2")) * (delta - MontFp!("3"))
+}
+
+#[cfg(test)]
+mod should {
+    use super::*;
+    use ark_ff::AdditiveGroup;
+    use rstest::rstest;
+
+    fn challenges() -> Challenges {
+        Challenges {
+            alpha: MontFp!("3"),
+            beta: MontFp!("5"),
+            gamma: MontFp!("7"),
+            zeta: MontFp!("11"),
+            zeta_pow_n: MontFp!("13"),
+        }
+    }
+
+    #[rstest]
+    fn accept_only_base_4_digits_as_quads() {
+        for digit in 0u64..4 {
+            assert_eq!(quad_identity(Fr::from(digit)), Fr::ZERO);
+        }
+        assert_ne!(quad_identity(Fr::from(4u64)), Fr::ZERO);
+        assert_ne!(quad_identity(-Fr::ONE), Fr::ZERO);
+    }
+
+    #[rstest]
+    fn satisfy_the_permutation_identity_with_an_identity_permutation() {
+        let challenges = challenges();
+        let wires = [MontFp!("17"), MontFp!("19"), MontFp!("23")];
+        let sigmas = core::array::from_fn::<Fr, 3, _>(|i| COSETS[i] * challenges.zeta);
+
+        let (identity, alpha_base) = compute_permutation_widget_evaluation(
+            &challenges,
+            challenges.alpha,
+            &wires,
+            &sigmas,
+            Fr::ONE,
+            Fr::ONE,
+            &Fr::ZERO,
+            &Fr::ZERO,
+            &Fr::ONE,
+        );
+
+        assert_eq!(identity, Fr::ZERO);
+        assert_eq!(alpha_base, challenges.alpha.pow([4]));
+    }
+
+    #[rstest]
+    fn have_no_public_input_delta_without_public_inputs() {
+        let challenges = challenges();
+        let (work_root, work_root_inverse, domain_inverse) = domain(16).unwrap();
+
+        let [public_input_delta, _, _, _, _] = compute_lagrange_and_vanishing_poly(
+            &challenges,
+            work_root,
+            work_root_inverse,
+            domain_inverse,
+            &[],
+        )
+        .unwrap();
+
+        assert_eq!(public_input_delta, Fr::ONE);
+    }
+}
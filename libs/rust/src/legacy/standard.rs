@@ -0,0 +1,566 @@
+// Copyright 2022 Aztec
+// Copyright 2024 Horizen Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0 or MIT
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Standard PLONK: 3 wires and the `q_m.w₁.w₂ + q₁.w₁ + q₂.w₂ + q₃.w₃ + q_c` gate.
+
+use alloc::vec::Vec;
+
+use ark_bn254_ext::CurveHooks;
+
+use super::{
+    compute_lagrange_and_vanishing_poly, compute_pairing_points,
+    compute_permutation_widget_evaluation, domain, generate_challenges, Challenges, Opening,
+};
+use crate::{
+    check_public_input_number,
+    constants::MAX_LOG2_CIRCUIT_SIZE,
+    errors::VerifyError,
+    hooks::VerifierHooks,
+    key::{get_g1, get_u32, CircuitType, VerificationKeyError},
+    pairing_check,
+    proof::{read_proof_fq, read_proof_g1, ProofError},
+    transcript::{Keccak256Transcript, Transcript},
+    utils::{IntoFr, IntoU256},
+    DecodingMode, Fq, Fr, PreparedSrs, Public, G1, U256,
+};
+
+pub const PROOF_SIZE: usize = 992; // = 31 * 32 bytes
+pub const VK_SIZE: usize = 608; // = 3 * 32 + 8 * 64 bytes
+
+#[derive(PartialEq, Eq, Debug)]
+pub struct VerificationKey<H: CurveHooks> {
+    pub circuit_size: u32,
+    pub num_public_inputs: u32,
+    pub q_1: G1<H>,
+    pub q_2: G1<H>,
+    pub q_3: G1<H>,
+    pub q_m: G1<H>,
+    pub q_c: G1<H>,
+    pub sigma_1: G1<H>,
+    pub sigma_2: G1<H>,
+    pub sigma_3: G1<H>,
+}
+
+impl<H: CurveHooks> VerificationKey<H> {
+    pub fn try_from_solidity_bytes(bytes: &[u8]) -> Result<Self, VerificationKeyError> {
+        if bytes.len() != VK_SIZE {
+            Err(VerificationKeyError::BufferTooShort)?;
+        }
+
+        let bytes = match get_u32(bytes) {
+            Ok((circuit_type, bytes)) if circuit_type == CircuitType::Standard as u32 => bytes,
+            _ => Err(VerificationKeyError::InvalidCircuitType)?,
+        };
+
+        let (circuit_size, bytes) = match get_u32(bytes) {
+            Ok((circuit_size, bytes))
+                if circuit_size.is_power_of_two()
+                    && circuit_size <= 2u32.pow(MAX_LOG2_CIRCUIT_SIZE) =>
+            {
+                (circuit_size, bytes)
+            }
+            _ => Err(VerificationKeyError::InvalidCircuitSize)?,
+        };
+
+        let (num_public_inputs, bytes) =
+            get_u32(bytes).map_err(|_| VerificationKeyError::InvalidNumberOfPublicInputs)?;
+
+        let (q_1, bytes) = read_commitment::<H>("Q_1", bytes)?;
+        let (q_2, bytes) = read_commitment::<H>("Q_2", bytes)?;
+        let (q_3, bytes) = read_commitment::<H>("Q_3", bytes)?;
+        let (q_m, bytes) = read_commitment::<H>("Q_M", bytes)?;
+        let (q_c, bytes) = read_commitment::<H>("Q_C", bytes)?;
+        let (sigma_1, bytes) = read_commitment::<H>("SIGMA_1", bytes)?;
+        let (sigma_2, bytes) = read_commitment::<H>("SIGMA_2", bytes)?;
+        let (sigma_3, _) = read_commitment::<H>("SIGMA_3", bytes)?;
+
+        Ok(VerificationKey {
+            circuit_size,
+            num_public_inputs,
+            q_1,
+            q_2,
+            q_3,
+            q_m,
+            q_c,
+            sigma_1,
+            sigma_2,
+            sigma_3,
+        })
+    }
+}
+
+pub(super) fn read_commitment<'a, H: CurveHooks>(
+    field: &'static str,
+    bytes: &'a [u8],
+) -> Result<(G1<H>, &'a [u8]), VerificationKeyError> {
+    get_g1::<H>(bytes).map_err(|_| VerificationKeyError::PointNotOnCurve { field })
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Proof<H: CurveHooks> {
+    pub w1: G1<H>,
+    pub w2: G1<H>,
+    pub w3: G1<H>,
+    pub z: G1<H>,
+    pub t1: G1<H>,
+    pub t2: G1<H>,
+    pub t3: G1<H>,
+    pub w1_eval: Fq,
+    pub w2_eval: Fq,
+    pub w3_eval: Fq,
+    pub z_eval: Fq,
+    pub q1_eval: Fq,
+    pub q2_eval: Fq,
+    pub q3_eval: Fq,
+    pub qm_eval: Fq,
+    pub qc_eval: Fq,
+    pub sigma1_eval: Fq,
+    pub sigma2_eval: Fq,
+    pub sigma3_eval: Fq,
+    pub z_omega_eval: Fq,
+    pub pi_z: G1<H>,
+    pub pi_z_omega: G1<H>,
+}
+
+impl<H: CurveHooks> TryFrom<&[u8]> for Proof<H> {
+    type Error = ProofError;
+
+    fn try_from(proof: &[u8]) -> Result<Self, ProofError> {
+        Self::try_from_bytes_with_mode(proof, DecodingMode::Strict)
+    }
+}
+
+impl<H: CurveHooks> Proof<H> {
+    /// Parses `proof`, rejecting non canonical encodings unless `mode` is
+    /// [`DecodingMode::Lenient`].
+    pub fn try_from_bytes_with_mode(proof: &[u8], mode: DecodingMode) -> Result<Self, ProofError> {
+        if proof.len() != PROOF_SIZE {
+            return Err(ProofError::IncorrectBufferSize {
+                expected_size: PROOF_SIZE,
+                actual_size: proof.len(),
+            });
+        }
+
+        let mut offset = 0;
+
+        let w1 = read_proof_g1::<H>(proof, &mut offset, mode)?;
+        let w2 = read_proof_g1::<H>(proof, &mut offset, mode)?;
+        let w3 = read_proof_g1::<H>(proof, &mut offset, mode)?;
+        let z = read_proof_g1::<H>(proof, &mut offset, mode)?;
+        let t1 = read_proof_g1::<H>(proof, &mut offset, mode)?;
+        let t2 = read_proof_g1::<H>(proof, &mut offset, mode)?;
+        let t3 = read_proof_g1::<H>(proof, &mut offset, mode)?;
+        let w1_eval = read_proof_fq(proof, &mut offset, mode)?;
+        let w2_eval = read_proof_fq(proof, &mut offset, mode)?;
+        let w3_eval = read_proof_fq(proof, &mut offset, mode)?;
+        let z_eval = read_proof_fq(proof, &mut offset, mode)?;
+        let q1_eval = read_proof_fq(proof, &mut offset, mode)?;
+        let q2_eval = read_proof_fq(proof, &mut offset, mode)?;
+        let q3_eval = read_proof_fq(proof, &mut offset, mode)?;
+        let qm_eval = read_proof_fq(proof, &mut offset, mode)?;
+        let qc_eval = read_proof_fq(proof, &mut offset, mode)?;
+        let sigma1_eval = read_proof_fq(proof, &mut offset, mode)?;
+        let sigma2_eval = read_proof_fq(proof, &mut offset, mode)?;
+        let sigma3_eval = read_proof_fq(proof, &mut offset, mode)?;
+        let z_omega_eval = read_proof_fq(proof, &mut offset, mode)?;
+        let pi_z = read_proof_g1::<H>(proof, &mut offset, mode)?;
+        let pi_z_omega = read_proof_g1::<H>(proof, &mut offset, mode)?;
+
+        Ok(Self {
+            w1,
+            w2,
+            w3,
+            z,
+            t1,
+            t2,
+            t3,
+            w1_eval,
+            w2_eval,
+            w3_eval,
+            z_eval,
+            q1_eval,
+            q2_eval,
+            q3_eval,
+            qm_eval,
+            qc_eval,
+            sigma1_eval,
+            sigma2_eval,
+            sigma3_eval,
+            z_omega_eval,
+            pi_z,
+            pi_z_omega,
+        })
+    }
+}
+
+/// Verifies a Standard PLONK `raw_proof` against the Solidity encoded `raw_vk`.
+pub fn verify<H: VerifierHooks + Default>(
+    raw_vk: &[u8],
+    raw_proof: &[u8],
+    pubs: &Public,
+) -> Result<(), VerifyError> {
+    verify_raw::<H, Keccak256Transcript<H>>(
+        &H::default(),
+        raw_vk,
+        raw_proof,
+        pubs,
+        DecodingMode::Strict,
+        &PreparedSrs::default(),
+    )
+}
+
+pub(crate) fn verify_raw<H: VerifierHooks, T: Transcript<H>>(
+    hooks: &H,
+    raw_vk: &[u8],
+    raw_proof: &[u8],
+    pubs: &Public,
+    mode: DecodingMode,
+    srs: &PreparedSrs<H>,
+) -> Result<(), VerifyError> {
+    let vk =
+        VerificationKey::<H>::try_from_solidity_bytes(raw_vk).map_err(|_| VerifyError::KeyError)?;
+
+    let proof = Proof::<H>::try_from_bytes_with_mode(raw_proof, mode)
+        .map_err(|_| VerifyError::InvalidProofError)?;
+
+    let public_inputs = &pubs
+        .iter()
+        .map(|pi_bytes| pi_bytes.into_u256())
+        .collect::<Vec<U256>>();
+
+    verify_proof::<H, T>(hooks, &vk, &proof, public_inputs, srs)
+}
+
+/// Verifies `proof` against `vk` and the big-endian `public_inputs`, deriving the challenges
+/// with the `T` transcript.
+pub fn verify_proof<H: VerifierHooks, T: Transcript<H>>(
+    hooks: &H,
+    vk: &VerificationKey<H>,
+    proof: &Proof<H>,
+    public_inputs: &[U256],
+    srs: &PreparedSrs<H>,
+) -> Result<(), VerifyError> {
+    check_public_input_number(vk.num_public_inputs, public_inputs)?;
+    let (work_root, work_root_inverse, domain_inverse) = domain(vk.circuit_size)?;
+
+    // Generate Challenges:
+    let (challenges, c_current) = generate_challenges::<H, T>(
+        hooks,
+        vk.circuit_size,
+        vk.num_public_inputs,
+        public_inputs,
+        &[proof.w1, proof.w2, proof.w3],
+        &proof.z,
+        &[proof.t1, proof.t2, proof.t3],
+    );
+
+    // Lagrange poly and vanishing poly fractions
+    let [public_input_delta, _zero_poly, zero_poly_inverse, l_start, l_end] =
+        compute_lagrange_and_vanishing_poly(
+            &challenges,
+            work_root,
+            work_root_inverse,
+            domain_inverse,
+            public_inputs,
+        )?;
+
+    // Standard Widgets:
+
+    // 1. Permutation Widget Evaluation
+    let (permutation_identity, alpha_base) = compute_permutation_widget_evaluation(
+        &challenges,
+        challenges.alpha,
+        &[
+            proof.w1_eval.into_fr(),
+            proof.w2_eval.into_fr(),
+            proof.w3_eval.into_fr(),
+        ],
+        &[
+            proof.sigma1_eval.into_fr(),
+            proof.sigma2_eval.into_fr(),
+            proof.sigma3_eval.into_fr(),
+        ],
+        proof.z_eval.into_fr(),
+        proof.z_omega_eval.into_fr(),
+        &l_start,
+        &l_end,
+        &public_input_delta,
+    );
+
+    // 2. Arithmetic Widget Evaluation
+    let (arithmetic_identity, _alpha_base) =
+        compute_arithmetic_widget_evaluation::<H>(proof, &challenges, alpha_base);
+
+    // Quotient Evaluation
+    let quotient_eval = (permutation_identity + arithmetic_identity) * zero_poly_inverse;
+
+    // Compute pairing points
+    let openings = [
+        Opening::new(proof.w1, proof.w1_eval.into_fr()),
+        Opening::new(proof.w2, proof.w2_eval.into_fr()),
+        Opening::new(proof.w3, proof.w3_eval.into_fr()),
+        Opening::shifted(
+            proof.z,
+            proof.z_eval.into_fr(),
+            proof.z_omega_eval.into_fr(),
+        ),
+        Opening::new(vk.q_1, proof.q1_eval.into_fr()),
+        Opening::new(vk.q_2, proof.q2_eval.into_fr()),
+        Opening::new(vk.q_3, proof.q3_eval.into_fr()),
+        Opening::new(vk.q_m, proof.qm_eval.into_fr()),
+        Opening::new(vk.q_c, proof.qc_eval.into_fr()),
+        Opening::new(vk.sigma_1, proof.sigma1_eval.into_fr()),
+        Opening::new(vk.sigma_2, proof.sigma2_eval.into_fr()),
+        Opening::new(vk.sigma_3, proof.sigma3_eval.into_fr()),
+    ];
+    let (pairing_lhs, pairing_rhs) = compute_pairing_points::<H, T>(
+        hooks,
+        &challenges,
+        &c_current,
+        &[proof.t1, proof.t2, proof.t3],
+        &quotient_eval,
+        &openings,
+        &proof.pi_z,
+        &proof.pi_z_omega,
+        &work_root,
+    )?;
+
+    // Check pairing relation
+    if pairing_check(hooks, pairing_lhs, pairing_rhs, srs)? {
+        Ok(())
+    } else {
+        Err(VerifyError::VerificationError)
+    }
+}
+
+fn compute_arithmetic_widget_evaluation<H: CurveHooks>(
+    proof: &Proof<H>,
+    challenges: &Challenges,
+    mut alpha_base: Fr,
+) -> (Fr, Fr) {
+    let w1_eval = proof.w1_eval.into_fr();
+    let w2_eval = proof.w2_eval.into_fr();
+    let w3_eval = proof.w3_eval.into_fr();
+
+    let identity = proof.qm_eval.into_fr() * w1_eval * w2_eval
+        + proof.q1_eval.into_fr() * w1_eval
+        + proof.q2_eval.into_fr() * w2_eval
+        + proof.q3_eval.into_fr() * w3_eval
+        + proof.qc_eval.into_fr();
+
+    let arithmetic_identity = alpha_base * identity;
+
+    // update alpha
+    alpha_base *= challenges.alpha;
+
+    (arithmetic_identity, alpha_base)
+}
+
+#[cfg(test)]
+pub(crate) mod should {
+    use super::*;
+    use crate::utils::IntoBytes;
+    use ark_ec::AffineRepr;
+    use ark_ff::{AdditiveGroup, Field, MontFp};
+    use rstest::{fixture, rstest};
+
+    // Encodes `point` as the `x || y` words of a Solidity verification key.
+    pub(crate) fn write_vk_g1(out: &mut Vec<u8>, point: &G1<()>) {
+        out.extend(point.x.into_bytes());
+        out.extend(point.y.into_bytes());
+    }
+
+    // Encodes `point` as the `y || x` words of a Solidity proof.
+    pub(crate) fn write_proof_g1(out: &mut Vec<u8>, point: &G1<()>) {
+        out.extend(point.y.into_bytes());
+        out.extend(point.x.into_bytes());
+    }
+
+    pub(crate) fn write_word(out: &mut Vec<u8>, value: u32) {
+        out.extend([0u8; 28]);
+        out.extend(value.to_be_bytes());
+    }
+
+    #[fixture]
+    fn valid_vk() -> Vec<u8> {
+        let mut out = Vec::with_capacity(VK_SIZE);
+        write_word(&mut out, CircuitType::Standard as u32);
+        write_word(&mut out, 16);
+        write_word(&mut out, 1);
+        for _ in 0..8 {
+            write_vk_g1(&mut out, &G1::<()>::generator());
+        }
+        out
+    }
+
+    #[fixture]
+    fn valid_proof() -> Vec<u8> {
+        let mut out = Vec::with_capacity(PROOF_SIZE);
+        for _ in 0..7 {
+            write_proof_g1(&mut out, &G1::<()>::generator());
+        }
+        for i in 0..13u32 {
+            write_word(&mut out, i);
+        }
+        for _ in 0..2 {
+            write_proof_g1(&mut out, &G1::<()>::generator());
+        }
+        out
+    }
+
+    #[rstest]
+    fn parse_a_well_formed_vk(valid_vk: Vec<u8>) {
+        let vk = VerificationKey::<()>::try_from_solidity_bytes(&valid_vk).unwrap();
+
+        assert_eq!(vk.circuit_size, 16);
+        assert_eq!(vk.num_public_inputs, 1);
+        assert_eq!(vk.sigma_3, G1::<()>::generator());
+    }
+
+    #[rstest]
+    fn parse_a_well_formed_proof(valid_proof: Vec<u8>) {
+        let proof = Proof::<()>::try_from(&valid_proof[..]).unwrap();
+
+        assert_eq!(proof.w1_eval, Fq::ZERO);
+        assert_eq!(proof.z_omega_eval, Fq::from(12u64));
+        assert_eq!(proof.pi_z_omega, G1::<()>::generator());
+    }
+
+    #[rstest]
+    fn satisfy_the_arithmetic_identity_with_a_multiplication_gate(valid_proof: Vec<u8>) {
+        // w₁.w₂ - w₃ = 0
+        let mut proof = Proof::<()>::try_from(&valid_proof[..]).unwrap();
+        proof.w1_eval = MontFp!("3");
+        proof.w2_eval = MontFp!("5");
+        proof.w3_eval = MontFp!("15");
+        proof.qm_eval = Fq::ONE;
+        proof.q1_eval = Fq::ZERO;
+        proof.q2_eval = Fq::ZERO;
+        proof.q3_eval = -Fq::ONE;
+        proof.qc_eval = Fq::ZERO;
+        let challenges = Challenges {
+            alpha: MontFp!("2"),
+            beta: Fr::ONE,
+            gamma: Fr::ONE,
+            zeta: Fr::ONE,
+            zeta_pow_n: Fr::ONE,
+        };
+
+        let (identity, alpha_base) =
+            compute_arithmetic_widget_evaluation(&proof, &challenges, Fr::ONE);
+
+        assert_eq!(identity, Fr::ZERO);
+        assert_eq!(alpha_base, challenges.alpha);
+    }
+
+    mod reject {
+        use super::*;
+
+        #[rstest]
+        fn a_vk_from_a_short_buffer(valid_vk: Vec<u8>) {
+            assert_eq!(
+                VerificationKey::<()>::try_from_solidity_bytes(&valid_vk[1..]).unwrap_err(),
+                VerificationKeyError::BufferTooShort
+            );
+        }
+
+        #[rstest]
+        fn a_vk_with_another_circuit_type(mut valid_vk: Vec<u8>) {
+            valid_vk[31] = CircuitType::Turbo as u8;
+
+            assert_eq!(
+                VerificationKey::<()>::try_from_solidity_bytes(&valid_vk).unwrap_err(),
+                VerificationKeyError::InvalidCircuitType
+            );
+        }
+
+        #[rstest]
+        fn a_vk_with_a_circuit_size_not_power_of_two(mut valid_vk: Vec<u8>) {
+            valid_vk[63] = 15;
+
+            assert_eq!(
+                VerificationKey::<()>::try_from_solidity_bytes(&valid_vk).unwrap_err(),
+                VerificationKeyError::InvalidCircuitSize
+            );
+        }
+
+        #[rstest]
+        fn a_vk_with_a_point_not_on_curve(mut valid_vk: Vec<u8>) {
+            valid_vk[VK_SIZE - 1] ^= 1;
+
+            assert_eq!(
+                VerificationKey::<()>::try_from_solidity_bytes(&valid_vk).unwrap_err(),
+                VerificationKeyError::PointNotOnCurve { field: "SIGMA_3" }
+            );
+        }
+
+        #[rstest]
+        fn a_proof_from_a_short_buffer(valid_proof: Vec<u8>) {
+            assert_eq!(
+                Proof::<()>::try_from(&valid_proof[1..]).unwrap_err(),
+                ProofError::IncorrectBufferSize {
+                    expected_size: PROOF_SIZE,
+                    actual_size: PROOF_SIZE - 1,
+                }
+            );
+        }
+
+        #[rstest]
+        fn a_proof_with_a_point_not_on_curve(mut valid_proof: Vec<u8>) {
+            valid_proof[63] ^= 1;
+
+            assert_eq!(
+                Proof::<()>::try_from(&valid_proof[..]).unwrap_err(),
+                ProofError::PointNotOnCurve
+            );
+        }
+
+        #[rstest]
+        fn a_proof_with_the_wrong_number_of_public_inputs(valid_vk: Vec<u8>, valid_proof: Vec<u8>) {
+            assert!(matches!(
+                verify::<()>(&valid_vk, &valid_proof, &[]).unwrap_err(),
+                VerifyError::PublicInputError { .. }
+            ));
+        }
+
+        #[rstest]
+        fn a_proof_not_matching_the_vk(valid_vk: Vec<u8>, valid_proof: Vec<u8>) {
+            assert_eq!(
+                verify::<()>(&valid_vk, &valid_proof, &[[0u8; 32]]).unwrap_err(),
+                VerifyError::VerificationError
+            );
+        }
+
+        #[rstest]
+        fn a_standard_vk_passed_to_the_ultra_verifier(valid_vk: Vec<u8>, valid_proof: Vec<u8>) {
+            assert_eq!(
+                crate::verify::<()>(&valid_vk, &valid_proof, &[[0u8; 32]]).unwrap_err(),
+                VerifyError::KeyError
+            );
+        }
+
+        #[rstest]
+        fn an_ultra_vk() {
+            let mut raw_vk = valid_vk();
+            raw_vk[31] = CircuitType::Ultra as u8;
+
+            assert_eq!(
+                verify::<()>(&raw_vk, &valid_proof(), &[[0u8; 32]]).unwrap_err(),
+                VerifyError::KeyError
+            );
+        }
+    }
+}
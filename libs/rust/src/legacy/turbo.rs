@@ -0,0 +1,807 @@
+// Copyright 2022 Aztec
+// Copyright 2024 Horizen Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0 or MIT
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! TurboPLONK: 4 wires, the turbo arithmetic gate and the fixed-base scalar multiplication,
+//! range and logic widgets.
+
+use alloc::vec::Vec;
+
+use ark_bn254_ext::CurveHooks;
+use ark_ff::{AdditiveGroup, Field, MontFp};
+
+use super::{
+    compute_lagrange_and_vanishing_poly, compute_pairing_points,
+    compute_permutation_widget_evaluation, domain, generate_challenges, quad_identity,
+    standard::read_commitment, Challenges, Opening,
+};
+use crate::{
+    check_public_input_number,
+    constants::MAX_LOG2_CIRCUIT_SIZE,
+    errors::VerifyError,
+    hooks::VerifierHooks,
+    key::{get_u32, CircuitType, VerificationKeyError},
+    pairing_check,
+    proof::{read_proof_fq, read_proof_g1, ProofError},
+    transcript::{Keccak256Transcript, Transcript},
+    utils::{IntoFr, IntoU256},
+    DecodingMode, Fq, Fr, PreparedSrs, Public, G1, U256,
+};
+
+pub const PROOF_SIZE: usize = 1504; // = 47 * 32 bytes
+pub const VK_SIZE: usize = 1056; // = 3 * 32 + 15 * 64 bytes
+
+#[derive(PartialEq, Eq, Debug)]
+pub struct VerificationKey<H: CurveHooks> {
+    pub circuit_size: u32,
+    pub num_public_inputs: u32,
+    pub q_1: G1<H>,
+    pub q_2: G1<H>,
+    pub q_3: G1<H>,
+    pub q_4: G1<H>,
+    pub q_5: G1<H>,
+    pub q_m: G1<H>,
+    pub q_c: G1<H>,
+    pub q_arithmetic: G1<H>,
+    pub q_fixed_base: G1<H>,
+    pub q_range: G1<H>,
+    pub q_logic: G1<H>,
+    pub sigma_1: G1<H>,
+    pub sigma_2: G1<H>,
+    pub sigma_3: G1<H>,
+    pub sigma_4: G1<H>,
+}
+
+impl<H: CurveHooks> VerificationKey<H> {
+    pub fn try_from_solidity_bytes(bytes: &[u8]) -> Result<Self, VerificationKeyError> {
+        if bytes.len() != VK_SIZE {
+            Err(VerificationKeyError::BufferTooShort)?;
+        }
+
+        let bytes = match get_u32(bytes) {
+            Ok((circuit_type, bytes)) if circuit_type == CircuitType::Turbo as u32 => bytes,
+            _ => Err(VerificationKeyError::InvalidCircuitType)?,
+        };
+
+        let (circuit_size, bytes) = match get_u32(bytes) {
+            Ok((circuit_size, bytes))
+                if circuit_size.is_power_of_two()
+                    && circuit_size <= 2u32.pow(MAX_LOG2_CIRCUIT_SIZE) =>
+            {
+                (circuit_size, bytes)
+            }
+            _ => Err(VerificationKeyError::InvalidCircuitSize)?,
+        };
+
+        let (num_public_inputs, bytes) =
+            get_u32(bytes).map_err(|_| VerificationKeyError::InvalidNumberOfPublicInputs)?;
+
+        let (q_1, bytes) = read_commitment::<H>("Q_1", bytes)?;
+        let (q_2, bytes) = read_commitment::<H>("Q_2", bytes)?;
+        let (q_3, bytes) = read_commitment::<H>("Q_3", bytes)?;
+        let (q_4, bytes) = read_commitment::<H>("Q_4", bytes)?;
+        let (q_5, bytes) = read_commitment::<H>("Q_5", bytes)?;
+        let (q_m, bytes) = read_commitment::<H>("Q_M", bytes)?;
+        let (q_c, bytes) = read_commitment::<H>("Q_C", bytes)?;
+        let (q_arithmetic, bytes) = read_commitment::<H>("Q_ARITHMETIC", bytes)?;
+        let (q_fixed_base, bytes) = read_commitment::<H>("Q_FIXED_BASE", bytes)?;
+        let (q_range, bytes) = read_commitment::<H>("Q_RANGE", bytes)?;
+        let (q_logic, bytes) = read_commitment::<H>("Q_LOGIC", bytes)?;
+        let (sigma_1, bytes) = read_commitment::<H>("SIGMA_1", bytes)?;
+        let (sigma_2, bytes) = read_commitment::<H>("SIGMA_2", bytes)?;
+        let (sigma_3, bytes) = read_commitment::<H>("SIGMA_3", bytes)?;
+        let (sigma_4, _) = read_commitment::<H>("SIGMA_4", bytes)?;
+
+        Ok(VerificationKey {
+            circuit_size,
+            num_public_inputs,
+            q_1,
+            q_2,
+            q_3,
+            q_4,
+            q_5,
+            q_m,
+            q_c,
+            q_arithmetic,
+            q_fixed_base,
+            q_range,
+            q_logic,
+            sigma_1,
+            sigma_2,
+            sigma_3,
+            sigma_4,
+        })
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Proof<H: CurveHooks> {
+    pub w1: G1<H>,
+    pub w2: G1<H>,
+    pub w3: G1<H>,
+    pub w4: G1<H>,
+    pub z: G1<H>,
+    pub t1: G1<H>,
+    pub t2: G1<H>,
+    pub t3: G1<H>,
+    pub t4: G1<H>,
+    pub w1_eval: Fq,
+    pub w2_eval: Fq,
+    pub w3_eval: Fq,
+    pub w4_eval: Fq,
+    pub z_eval: Fq,
+    pub q1_eval: Fq,
+    pub q2_eval: Fq,
+    pub q3_eval: Fq,
+    pub q4_eval: Fq,
+    pub q5_eval: Fq,
+    pub qm_eval: Fq,
+    pub qc_eval: Fq,
+    pub q_arith_eval: Fq,
+    pub q_fixed_base_eval: Fq,
+    pub q_range_eval: Fq,
+    pub q_logic_eval: Fq,
+    pub sigma1_eval: Fq,
+    pub sigma2_eval: Fq,
+    pub sigma3_eval: Fq,
+    pub sigma4_eval: Fq,
+    pub w1_omega_eval: Fq,
+    pub w2_omega_eval: Fq,
+    pub w3_omega_eval: Fq,
+    pub w4_omega_eval: Fq,
+    pub z_omega_eval: Fq,
+    pub pi_z: G1<H>,
+    pub pi_z_omega: G1<H>,
+}
+
+impl<H: CurveHooks> TryFrom<&[u8]> for Proof<H> {
+    type Error = ProofError;
+
+    fn try_from(proof: &[u8]) -> Result<Self, ProofError> {
+        Self::try_from_bytes_with_mode(proof, DecodingMode::Strict)
+    }
+}
+
+impl<H: CurveHooks> Proof<H> {
+    /// Parses `proof`, rejecting non canonical encodings unless `mode` is
+    /// [`DecodingMode::Lenient`].
+    pub fn try_from_bytes_with_mode(proof: &[u8], mode: DecodingMode) -> Result<Self, ProofError> {
+        if proof.len() != PROOF_SIZE {
+            return Err(ProofError::IncorrectBufferSize {
+                expected_size: PROOF_SIZE,
+                actual_size: proof.len(),
+            });
+        }
+
+        let mut offset = 0;
+
+        let w1 = read_proof_g1::<H>(proof, &mut offset, mode)?;
+        let w2 = read_proof_g1::<H>(proof, &mut offset, mode)?;
+        let w3 = read_proof_g1::<H>(proof, &mut offset, mode)?;
+        let w4 = read_proof_g1::<H>(proof, &mut offset, mode)?;
+        let z = read_proof_g1::<H>(proof, &mut offset, mode)?;
+        let t1 = read_proof_g1::<H>(proof, &mut offset, mode)?;
+        let t2 = read_proof_g1::<H>(proof, &mut offset, mode)?;
+        let t3 = read_proof_g1::<H>(proof, &mut offset, mode)?;
+        let t4 = read_proof_g1::<H>(proof, &mut offset, mode)?;
+        let w1_eval = read_proof_fq(proof, &mut offset, mode)?;
+        let w2_eval = read_proof_fq(proof, &mut offset, mode)?;
+        let w3_eval = read_proof_fq(proof, &mut offset, mode)?;
+        let w4_eval = read_proof_fq(proof, &mut offset, mode)?;
+        let z_eval = read_proof_fq(proof, &mut offset, mode)?;
+        let q1_eval = read_proof_fq(proof, &mut offset, mode)?;
+        let q2_eval = read_proof_fq(proof, &mut offset, mode)?;
+        let q3_eval = read_proof_fq(proof, &mut offset, mode)?;
+        let q4_eval = read_proof_fq(proof, &mut offset, mode)?;
+        let q5_eval = read_proof_fq(proof, &mut offset, mode)?;
+        let qm_eval = read_proof_fq(proof, &mut offset, mode)?;
+        let qc_eval = read_proof_fq(proof, &mut offset, mode)?;
+        let q_arith_eval = read_proof_fq(proof, &mut offset, mode)?;
+        let q_fixed_base_eval = read_proof_fq(proof, &mut offset, mode)?;
+        let q_range_eval = read_proof_fq(proof, &mut offset, mode)?;
+        let q_logic_eval = read_proof_fq(proof, &mut offset, mode)?;
+        let sigma1_eval = read_proof_fq(proof, &mut offset, mode)?;
+        let sigma2_eval = read_proof_fq(proof, &mut offset, mode)?;
+        let sigma3_eval = read_proof_fq(proof, &mut offset, mode)?;
+        let sigma4_eval = read_proof_fq(proof, &mut offset, mode)?;
+        let w1_omega_eval = read_proof_fq(proof, &mut offset, mode)?;
+        let w2_omega_eval = read_proof_fq(proof, &mut offset, mode)?;
+        let w3_omega_eval = read_proof_fq(proof, &mut offset, mode)?;
+        let w4_omega_eval = read_proof_fq(proof, &mut offset, mode)?;
+        let z_omega_eval = read_proof_fq(proof, &mut offset, mode)?;
+        let pi_z = read_proof_g1::<H>(proof, &mut offset, mode)?;
+        let pi_z_omega = read_proof_g1::<H>(proof, &mut offset, mode)?;
+
+        Ok(Self {
+            w1,
+            w2,
+            w3,
+            w4,
+            z,
+            t1,
+            t2,
+            t3,
+            t4,
+            w1_eval,
+            w2_eval,
+            w3_eval,
+            w4_eval,
+            z_eval,
+            q1_eval,
+            q2_eval,
+            q3_eval,
+            q4_eval,
+            q5_eval,
+            qm_eval,
+            qc_eval,
+            q_arith_eval,
+            q_fixed_base_eval,
+            q_range_eval,
+            q_logic_eval,
+            sigma1_eval,
+            sigma2_eval,
+            sigma3_eval,
+            sigma4_eval,
+            w1_omega_eval,
+            w2_omega_eval,
+            w3_omega_eval,
+            w4_omega_eval,
+            z_omega_eval,
+            pi_z,
+            pi_z_omega,
+        })
+    }
+}
+
+/// Verifies a TurboPLONK `raw_proof` against the Solidity encoded `raw_vk`.
+pub fn verify<H: VerifierHooks + Default>(
+    raw_vk: &[u8],
+    raw_proof: &[u8],
+    pubs: &Public,
+) -> Result<(), VerifyError> {
+    verify_raw::<H, Keccak256Transcript<H>>(
+        &H::default(),
+        raw_vk,
+        raw_proof,
+        pubs,
+        DecodingMode::Strict,
+        &PreparedSrs::default(),
+    )
+}
+
+pub(crate) fn verify_raw<H: VerifierHooks, T: Transcript<H>>(
+    hooks: &H,
+    raw_vk: &[u8],
+    raw_proof: &[u8],
+    pubs: &Public,
+    mode: DecodingMode,
+    srs: &PreparedSrs<H>,
+) -> Result<(), VerifyError> {
+    let vk =
+        VerificationKey::<H>::try_from_solidity_bytes(raw_vk).map_err(|_| VerifyError::KeyError)?;
+
+    let proof = Proof::<H>::try_from_bytes_with_mode(raw_proof, mode)
+        .map_err(|_| VerifyError::InvalidProofError)?;
+
+    let public_inputs = &pubs
+        .iter()
+        .map(|pi_bytes| pi_bytes.into_u256())
+        .collect::<Vec<U256>>();
+
+    verify_proof::<H, T>(hooks, &vk, &proof, public_inputs, srs)
+}
+
+/// Verifies `proof` against `vk` and the big-endian `public_inputs`, deriving the challenges
+/// with the `T` transcript.
+pub fn verify_proof<H: VerifierHooks, T: Transcript<H>>(
+    hooks: &H,
+    vk: &VerificationKey<H>,
+    proof: &Proof<H>,
+    public_inputs: &[U256],
+    srs: &PreparedSrs<H>,
+) -> Result<(), VerifyError> {
+    check_public_input_number(vk.num_public_inputs, public_inputs)?;
+    let (work_root, work_root_inverse, domain_inverse) = domain(vk.circuit_size)?;
+
+    // Generate Challenges:
+    let (challenges, c_current) = generate_challenges::<H, T>(
+        hooks,
+        vk.circuit_size,
+        vk.num_public_inputs,
+        public_inputs,
+        &[proof.w1, proof.w2, proof.w3, proof.w4],
+        &proof.z,
+        &[proof.t1, proof.t2, proof.t3, proof.t4],
+    );
+
+    // Lagrange poly and vanishing poly fractions
+    let [public_input_delta, _zero_poly, zero_poly_inverse, l_start, l_end] =
+        compute_lagrange_and_vanishing_poly(
+            &challenges,
+            work_root,
+            work_root_inverse,
+            domain_inverse,
+            public_inputs,
+        )?;
+
+    // TurboPlonk Widgets:
+
+    // 1. Permutation Widget Evaluation
+    let (permutation_identity, alpha_base) = compute_permutation_widget_evaluation(
+        &challenges,
+        challenges.alpha,
+        &[
+            proof.w1_eval.into_fr(),
+            proof.w2_eval.into_fr(),
+            proof.w3_eval.into_fr(),
+            proof.w4_eval.into_fr(),
+        ],
+        &[
+            proof.sigma1_eval.into_fr(),
+            proof.sigma2_eval.into_fr(),
+            proof.sigma3_eval.into_fr(),
+            proof.sigma4_eval.into_fr(),
+        ],
+        proof.z_eval.into_fr(),
+        proof.z_omega_eval.into_fr(),
+        &l_start,
+        &l_end,
+        &public_input_delta,
+    );
+
+    // 2. Arithmetic Widget Evaluation
+    let (arithmetic_identity, alpha_base) =
+        compute_arithmetic_widget_evaluation::<H>(proof, &challenges, alpha_base);
+
+    // 3. Fixed Base Widget Evaluation
+    let (fixed_base_identity, alpha_base) =
+        compute_fixed_base_widget_evaluation::<H>(proof, &challenges, alpha_base);
+
+    // 4. Range Widget Evaluation
+    let (range_identity, alpha_base) =
+        compute_range_widget_evaluation::<H>(proof, &challenges, alpha_base);
+
+    // 5. Logic Widget Evaluation
+    let (logic_identity, _alpha_base) =
+        compute_logic_widget_evaluation::<H>(proof, &challenges, alpha_base);
+
+    // Quotient Evaluation
+    let quotient_eval = (permutation_identity
+        + arithmetic_identity
+        + fixed_base_identity
+        + range_identity
+        + logic_identity)
+        * zero_poly_inverse;
+
+    // Compute pairing points
+    let openings = [
+        Opening::shifted(
+            proof.w1,
+            proof.w1_eval.into_fr(),
+            proof.w1_omega_eval.into_fr(),
+        ),
+        Opening::shifted(
+            proof.w2,
+            proof.w2_eval.into_fr(),
+            proof.w2_omega_eval.into_fr(),
+        ),
+        Opening::shifted(
+            proof.w3,
+            proof.w3_eval.into_fr(),
+            proof.w3_omega_eval.into_fr(),
+        ),
+        Opening::shifted(
+            proof.w4,
+            proof.w4_eval.into_fr(),
+            proof.w4_omega_eval.into_fr(),
+        ),
+        Opening::shifted(
+            proof.z,
+            proof.z_eval.into_fr(),
+            proof.z_omega_eval.into_fr(),
+        ),
+        Opening::new(vk.q_1, proof.q1_eval.into_fr()),
+        Opening::new(vk.q_2, proof.q2_eval.into_fr()),
+        Opening::new(vk.q_3, proof.q3_eval.into_fr()),
+        Opening::new(vk.q_4, proof.q4_eval.into_fr()),
+        Opening::new(vk.q_5, proof.q5_eval.into_fr()),
+        Opening::new(vk.q_m, proof.qm_eval.into_fr()),
+        Opening::new(vk.q_c, proof.qc_eval.into_fr()),
+        Opening::new(vk.q_arithmetic, proof.q_arith_eval.into_fr()),
+        Opening::new(vk.q_fixed_base, proof.q_fixed_base_eval.into_fr()),
+        Opening::new(vk.q_range, proof.q_range_eval.into_fr()),
+        Opening::new(vk.q_logic, proof.q_logic_eval.into_fr()),
+        Opening::new(vk.sigma_1, proof.sigma1_eval.into_fr()),
+        Opening::new(vk.sigma_2, proof.sigma2_eval.into_fr()),
+        Opening::new(vk.sigma_3, proof.sigma3_eval.into_fr()),
+        Opening::new(vk.sigma_4, proof.sigma4_eval.into_fr()),
+    ];
+    let (pairing_lhs, pairing_rhs) = compute_pairing_points::<H, T>(
+        hooks,
+        &challenges,
+        &c_current,
+        &[proof.t1, proof.t2, proof.t3, proof.t4],
+        &quotient_eval,
+        &openings,
+        &proof.pi_z,
+        &proof.pi_z_omega,
+        &work_root,
+    )?;
+
+    // Check pairing relation
+    if pairing_check(hooks, pairing_lhs, pairing_rhs, srs)? {
+        Ok(())
+    } else {
+        Err(VerifyError::VerificationError)
+    }
+}
+
+/// q_arith.(q_m.w₁.w₂ + q₁.w₁ + q₂.w₂ + q₃.w₃ + q₄.w₄ + q₅.w₄.(w₄ - 1).(w₄ - 2) + q_c)
+fn compute_arithmetic_widget_evaluation<H: CurveHooks>(
+    proof: &Proof<H>,
+    challenges: &Challenges,
+    mut alpha_base: Fr,
+) -> (Fr, Fr) {
+    let w1_eval = proof.w1_eval.into_fr();
+    let w2_eval = proof.w2_eval.into_fr();
+    let w3_eval = proof.w3_eval.into_fr();
+    let w4_eval = proof.w4_eval.into_fr();
+
+    let identity = proof.qm_eval.into_fr() * w1_eval * w2_eval
+        + proof.q1_eval.into_fr() * w1_eval
+        + proof.q2_eval.into_fr() * w2_eval
+        + proof.q3_eval.into_fr() * w3_eval
+        + proof.q4_eval.into_fr() * w4_eval
+        + proof.q5_eval.into_fr() * w4_eval * (w4_eval - Fr::ONE) * (w4_eval - MontFp!("2"))
+        + proof.qc_eval.into_fr();
+
+    let arithmetic_identity = alpha_base * proof.q_arith_eval.into_fr() * identity;
+
+    // update alpha
+    alpha_base *= challenges.alpha;
+
+    (arithmetic_identity, alpha_base)
+}
+
+/// One step of the fixed-base scalar multiplication ladder. `w₄` accumulates the scalar
+/// in base 4 with signed digits `δ = w₄(ωX) - 4.w₄ ∈ {±1, ±3}`, `(w₁, w₂)` accumulates the
+/// point and `w₃(ωX)` holds the x coordinate of the point added by the step, whose
+/// coordinates are `x_α = q₁.δ² + q₂` and `y_α = (q₃.x_α + q₄).δ`.
+fn compute_fixed_base_widget_evaluation<H: CurveHooks>(
+    proof: &Proof<H>,
+    challenges: &Challenges,
+    mut alpha_base: Fr,
+) -> (Fr, Fr) {
+    let x_1 = proof.w1_eval.into_fr();
+    let y_1 = proof.w2_eval.into_fr();
+    let x_2 = proof.w1_omega_eval.into_fr();
+    let y_2 = proof.w2_omega_eval.into_fr();
+    let x_alpha = proof.w3_omega_eval.into_fr();
+
+    let delta = proof.w4_omega_eval.into_fr() - proof.w4_eval.into_fr() * MontFp!("4");
+    let delta_squared = delta.square();
+    let y_alpha = (proof.q3_eval.into_fr() * x_alpha + proof.q4_eval.into_fr()) * delta;
+
+    // (δ² - 1).(δ² - 9) = 0
+    let accumulator_identity = (delta_squared - Fr::ONE) * (delta_squared - MontFp!("9"));
+
+    // x_α = q₁.δ² + q₂
+    let x_alpha_identity =
+        proof.q1_eval.into_fr() * delta_squared + proof.q2_eval.into_fr() - x_alpha;
+
+    // (x₂ + x₁ + x_α).(x_α - x₁)² - (y_α - y₁)² = 0
+    let x_accumulator_identity =
+        (x_2 + x_1 + x_alpha) * (x_alpha - x_1).square() - (y_alpha - y_1).square();
+
+    // (y₂ + y₁).(x_α - x₁) - (y_α - y₁).(x₁ - x₂) = 0
+    let y_accumulator_identity = (y_2 + y_1) * (x_alpha - x_1) - (y_alpha - y_1) * (x_1 - x_2);
+
+    let mut identity = accumulator_identity;
+    let mut alpha = challenges.alpha;
+    identity += x_alpha_identity * alpha;
+    alpha *= challenges.alpha;
+    identity += x_accumulator_identity * alpha;
+    alpha *= challenges.alpha;
+    identity += y_accumulator_identity * alpha;
+
+    let fixed_base_identity = alpha_base * proof.q_fixed_base_eval.into_fr() * identity;
+
+    // update alpha
+    alpha_base *= alpha * challenges.alpha;
+
+    (fixed_base_identity, alpha_base)
+}
+
+/// Checks that the differences of a base 4 accumulator, read across
+/// `w₄, w₃, w₂, w₁, w₄(ωX)`, are base 4 digits.
+fn compute_range_widget_evaluation<H: CurveHooks>(
+    proof: &Proof<H>,
+    challenges: &Challenges,
+    mut alpha_base: Fr,
+) -> (Fr, Fr) {
+    let four: Fr = MontFp!("4");
+    let w1_eval = proof.w1_eval.into_fr();
+    let w2_eval = proof.w2_eval.into_fr();
+    let w3_eval = proof.w3_eval.into_fr();
+    let w4_eval = proof.w4_eval.into_fr();
+
+    let deltas = [
+        w3_eval - w4_eval * four,
+        w2_eval - w3_eval * four,
+        w1_eval - w2_eval * four,
+        proof.w4_omega_eval.into_fr() - w1_eval * four,
+    ];
+
+    let mut identity = Fr::ZERO;
+    let mut alpha = Fr::ONE;
+    for delta in deltas {
+        identity += quad_identity(delta) * alpha;
+        alpha *= challenges.alpha;
+    }
+
+    let range_identity = alpha_base * proof.q_range_eval.into_fr() * identity;
+
+    // update alpha
+    alpha_base *= alpha;
+
+    (range_identity, alpha_base)
+}
+
+/// AND (`q_c = 1`) or XOR (`q_c = -1`) of two base 4 accumulators `w₁` and `w₂` into `w₃`,
+/// one quad per gate. `w₄` holds the product of the input quads `a` and `b`, so that the
+/// output quad is checked with
+/// `a & b = w₄.(4.w₄² - 18.w₄.s + 45.w₄ + 18.s² - 81.s + 83) / 6` and `a ^ b = s - 2.(a & b)`,
+/// where `s = a + b`.
+fn compute_logic_widget_evaluation<H: CurveHooks>(
+    proof: &Proof<H>,
+    challenges: &Challenges,
+    mut alpha_base: Fr,
+) -> (Fr, Fr) {
+    let four: Fr = MontFp!("4");
+    let delta_a = proof.w1_omega_eval.into_fr() - proof.w1_eval.into_fr() * four;
+    let delta_b = proof.w2_omega_eval.into_fr() - proof.w2_eval.into_fr() * four;
+    let delta_c = proof.w3_omega_eval.into_fr() - proof.w3_eval.into_fr() * four;
+    let product = proof.w4_eval.into_fr();
+    let selector = proof.qc_eval.into_fr();
+
+    let sum = delta_a + delta_b;
+    let and_times_6 = product
+        * (product.square() * four - product * sum * MontFp!("18")
+            + product * MontFp!("45")
+            + sum.square() * MontFp!("18")
+            - sum * MontFp!("81")
+            + MontFp!("83"));
+
+    // 12.c = 2.(a & b).(3.q_c - 1) + 6.s.(1 - q_c)
+    let output_identity = delta_c * MontFp!("12")
+        - and_times_6 * (selector * MontFp!("3") - Fr::ONE)
+        - sum * MontFp!("6") * (Fr::ONE - selector);
+
+    let mut identity = quad_identity(delta_a);
+    let mut alpha = challenges.alpha;
+    identity += quad_identity(delta_b) * alpha;
+    alpha *= challenges.alpha;
+    identity += (product - delta_a * delta_b) * alpha;
+    alpha *= challenges.alpha;
+    identity += output_identity * alpha;
+
+    let logic_identity = alpha_base * proof.q_logic_eval.into_fr() * identity;
+
+    // update alpha
+    alpha_base *= alpha * challenges.alpha;
+
+    (logic_identity, alpha_base)
+}
+
+#[cfg(test)]
+mod should {
+    use super::*;
+    use crate::legacy::standard::should::{write_proof_g1, write_vk_g1, write_word};
+    use ark_ec::AffineRepr;
+    use rstest::{fixture, rstest};
+
+    #[fixture]
+    fn valid_vk() -> Vec<u8> {
+        let mut out = Vec::with_capacity(VK_SIZE);
+        write_word(&mut out, CircuitType::Turbo as u32);
+        write_word(&mut out, 16);
+        write_word(&mut out, 0);
+        for _ in 0..15 {
+            write_vk_g1(&mut out, &G1::<()>::generator());
+        }
+        out
+    }
+
+    #[fixture]
+    fn valid_proof() -> Vec<u8> {
+        let mut out = Vec::with_capacity(PROOF_SIZE);
+        for _ in 0..9 {
+            write_proof_g1(&mut out, &G1::<()>::generator());
+        }
+        for _ in 0..25 {
+            write_word(&mut out, 0);
+        }
+        for _ in 0..2 {
+            write_proof_g1(&mut out, &G1::<()>::generator());
+        }
+        out
+    }
+
+    #[fixture]
+    fn challenges() -> Challenges {
+        Challenges {
+            alpha: MontFp!("2"),
+            beta: Fr::ONE,
+            gamma: Fr::ONE,
+            zeta: Fr::ONE,
+            zeta_pow_n: Fr::ONE,
+        }
+    }
+
+    fn fq(value: u64) -> Fq {
+        Fq::from(value)
+    }
+
+    #[rstest]
+    fn parse_a_well_formed_vk(valid_vk: Vec<u8>) {
+        let vk = VerificationKey::<()>::try_from_solidity_bytes(&valid_vk).unwrap();
+
+        assert_eq!(vk.circuit_size, 16);
+        assert_eq!(vk.num_public_inputs, 0);
+        assert_eq!(vk.sigma_4, G1::<()>::generator());
+    }
+
+    #[rstest]
+    fn parse_a_well_formed_proof(valid_proof: Vec<u8>) {
+        let proof = Proof::<()>::try_from(&valid_proof[..]).unwrap();
+
+        assert_eq!(proof.z_omega_eval, Fq::ZERO);
+        assert_eq!(proof.pi_z_omega, G1::<()>::generator());
+    }
+
+    #[rstest]
+    fn satisfy_the_logic_identity_for_every_quad(valid_proof: Vec<u8>, challenges: Challenges) {
+        let mut proof = Proof::<()>::try_from(&valid_proof[..]).unwrap();
+        proof.q_logic_eval = Fq::ONE;
+
+        let operations: [(Fq, fn(u64, u64) -> u64); 2] =
+            [(Fq::ONE, |a, b| a & b), (-Fq::ONE, |a, b| a ^ b)];
+
+        for (selector, op) in operations {
+            for a in 0..4u64 {
+                for b in 0..4u64 {
+                    // accumulators of 1, 2 and op(1, 2) before this quad
+                    proof.w1_eval = fq(1);
+                    proof.w2_eval = fq(2);
+                    proof.w3_eval = fq(op(1, 2));
+                    proof.w1_omega_eval = fq(4 + a);
+                    proof.w2_omega_eval = fq(8 + b);
+                    proof.w3_omega_eval = fq(4 * op(1, 2) + op(a, b));
+                    proof.w4_eval = fq(a * b);
+                    proof.qc_eval = selector;
+
+                    let (identity, _) =
+                        compute_logic_widget_evaluation(&proof, &challenges, Fr::ONE);
+
+                    assert_eq!(identity, Fr::ZERO, "a = {a}, b = {b}");
+                }
+            }
+        }
+    }
+
+    #[rstest]
+    fn satisfy_the_range_identity_with_base_4_digits(valid_proof: Vec<u8>, challenges: Challenges) {
+        // the digits 2, 3, 0 and 1 accumulated on top of 1
+        let mut proof = Proof::<()>::try_from(&valid_proof[..]).unwrap();
+        proof.q_range_eval = Fq::ONE;
+        proof.w4_eval = fq(1);
+        proof.w3_eval = fq(6);
+        proof.w2_eval = fq(27);
+        proof.w1_eval = fq(108);
+        proof.w4_omega_eval = fq(433);
+
+        let (identity, alpha_base) = compute_range_widget_evaluation(&proof, &challenges, Fr::ONE);
+
+        assert_eq!(identity, Fr::ZERO);
+        assert_eq!(alpha_base, challenges.alpha.pow([4]));
+
+        proof.w4_omega_eval = fq(436);
+        let (identity, _) = compute_range_widget_evaluation(&proof, &challenges, Fr::ONE);
+
+        assert_ne!(identity, Fr::ZERO);
+    }
+
+    #[rstest]
+    fn use_4_powers_of_alpha_in_the_fixed_base_widget(
+        valid_proof: Vec<u8>,
+        challenges: Challenges,
+    ) {
+        let proof = Proof::<()>::try_from(&valid_proof[..]).unwrap();
+
+        let (identity, alpha_base) =
+            compute_fixed_base_widget_evaluation(&proof, &challenges, Fr::ONE);
+
+        // q_fixed_base = 0
+        assert_eq!(identity, Fr::ZERO);
+        assert_eq!(alpha_base, challenges.alpha.pow([4]));
+    }
+
+    mod reject {
+        use super::*;
+
+        #[rstest]
+        fn a_vk_with_another_circuit_type(mut valid_vk: Vec<u8>) {
+            valid_vk[31] = CircuitType::Standard as u8;
+
+            assert_eq!(
+                VerificationKey::<()>::try_from_solidity_bytes(&valid_vk).unwrap_err(),
+                VerificationKeyError::InvalidCircuitType
+            );
+        }
+
+        #[rstest]
+        fn a_vk_with_a_point_not_on_curve(mut valid_vk: Vec<u8>) {
+            valid_vk[VK_SIZE - 1] ^= 1;
+
+            assert_eq!(
+                VerificationKey::<()>::try_from_solidity_bytes(&valid_vk).unwrap_err(),
+                VerificationKeyError::PointNotOnCurve { field: "SIGMA_4" }
+            );
+        }
+
+        #[rstest]
+        fn a_proof_from_a_short_buffer(valid_proof: Vec<u8>) {
+            assert_eq!(
+                Proof::<()>::try_from(&valid_proof[1..]).unwrap_err(),
+                ProofError::IncorrectBufferSize {
+                    expected_size: PROOF_SIZE,
+                    actual_size: PROOF_SIZE - 1,
+                }
+            );
+        }
+
+        #[rstest]
+        fn an_unsatisfied_logic_gate(valid_proof: Vec<u8>, challenges: Challenges) {
+            // 1 & 3 != 3
+            let mut proof = Proof::<()>::try_from(&valid_proof[..]).unwrap();
+            proof.q_logic_eval = Fq::ONE;
+            proof.qc_eval = Fq::ONE;
+            proof.w1_omega_eval = fq(1);
+            proof.w2_omega_eval = fq(3);
+            proof.w3_omega_eval = fq(3);
+            proof.w4_eval = fq(3);
+
+            let (identity, _) = compute_logic_widget_evaluation(&proof, &challenges, Fr::ONE);
+
+            assert_ne!(identity, Fr::ZERO);
+        }
+
+        #[rstest]
+        fn a_proof_not_matching_the_vk(valid_vk: Vec<u8>, valid_proof: Vec<u8>) {
+            assert_eq!(
+                verify::<()>(&valid_vk, &valid_proof, &[]).unwrap_err(),
+                VerifyError::VerificationError
+            );
+        }
+
+        #[rstest]
+        fn a_turbo_vk_passed_to_the_ultra_verifier(valid_vk: Vec<u8>, valid_proof: Vec<u8>) {
+            assert_eq!(
+                crate::verify::<()>(&valid_vk, &valid_proof, &[]).unwrap_err(),
+                VerifyError::KeyError
+            );
+        }
+    }
+}
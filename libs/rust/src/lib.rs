@@ -17,33 +17,57 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 #![doc = include_str!("../README.md")]
 
+mod accumulator;
+mod batch;
 mod constants;
 pub mod errors;
+pub mod fixed_base;
+pub mod hooks;
+#[cfg(feature = "hooks-conformance")]
+pub mod hooks_conformance;
 pub mod key;
+pub mod legacy;
+#[cfg(feature = "parallel")]
+pub mod parallel;
+mod precheck;
 pub mod proof;
-mod srs;
+#[cfg(feature = "serde")]
+pub mod serialization;
+pub mod srs;
+pub mod stepwise;
+mod trace;
+pub mod transcript;
 mod types;
 mod utils;
 
 #[cfg(feature = "wasm")]
 pub mod wasm;
 
-
 use crate::{
-    key::{read_g2, PreparedVerificationKey, VerificationKey},
-    proof::Proof,
-    srs::SRS_G2,
+    key::{PreparedVerificationKey, VerificationKey},
+    proof::{PreparedProof, Proof},
 };
 use ark_bn254_ext::{Config, CurveHooks};
-use ark_ec::{
-    pairing::Pairing, short_weierstrass::SWCurveConfig, AdditiveGroup, AffineRepr, CurveGroup,
-};
-use ark_ff::{Field, MontConfig, MontFp, One};
-use ark_models_ext::bn::{BnConfig, G1Prepared, G2Prepared};
+use ark_ec::{short_weierstrass::SWCurveConfig, AdditiveGroup, AffineRepr, CurveGroup};
+use ark_ff::{Field, MontConfig, MontFp, One, PrimeField};
+use ark_models_ext::bn::{BnConfig, G1Prepared};
 use errors::VerifyError;
-use sha3::{Digest, Keccak256};
-use utils::{IntoBytes, IntoFr, IntoU256};
-
+use utils::{combine_limbs, IntoBytes, IntoFr, IntoU256};
+
+pub use accumulator::{verify_deferred, verify_deferred_with_transcript, PairingAccumulator};
+pub use batch::{verify_batch, verify_batch_with_srs, verify_batch_with_transcript, BatchItem};
+pub use fixed_base::FixedBaseTables;
+pub use hooks::VerifierHooks;
+#[cfg(feature = "parallel")]
+pub use parallel::{
+    verify_batch_parallel, verify_batch_parallel_with_srs, verify_many, verify_many_with_srs,
+};
+pub use precheck::{precheck, precheck_with_transcript, Prechecked};
+#[cfg(feature = "serde")]
+pub use serialization::PublicInputs;
+pub use srs::{PreparedSrs, Srs};
+pub use trace::{verify_with_trace, verify_with_trace_and_transcript, VerificationTrace};
+pub use transcript::{Keccak256Transcript, KeccakHooks, KeccakState, Transcript};
 pub use types::*;
 
 extern crate alloc;
@@ -56,6 +80,13 @@ pub const VK_SIZE: usize = 1632;
 const LIMB_SIZE: Fr = MontFp!("295147905179352825856"); // = 2 << 68
 const SUBLIMB_SHIFT: Fr = MontFp!("16384"); // 1 << 14 = 0x4000 = 16384
 
+// Layout of the final MSM bases: the proof commitments up to `z_lookup`, the
+// `PreparedVerificationKey::fixed_bases`, then `pi_z` and `pi_z_omega`.
+const PROOF_COMMITMENT_BASES: usize = 11;
+const FIXED_BASES_RANGE: core::ops::Range<usize> =
+    PROOF_COMMITMENT_BASES..PROOF_COMMITMENT_BASES + fixed_base::FIXED_BASES;
+const FINAL_MSM_SIZE: usize = FIXED_BASES_RANGE.end + 2;
+
 /// A single public input.
 pub type PublicInput = [u8; PUBS_SIZE];
 pub type Public = [PublicInput];
@@ -112,7 +143,7 @@ impl Challenges {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct NuChallenges {
     c_v: [Fr; 30],
     c_u: Fr,
@@ -123,12 +154,13 @@ impl NuChallenges {
         NuChallenges { c_v, c_u }
     }
 
-    fn challenge<H: CurveHooks>(
+    fn challenge<H: CurveHooks, T: Transcript<H>>(
+        hooks: &H,
         proof: &Proof<H>,
         c_current: &[u8],
         quotient_eval: &Fr,
     ) -> [u8; 32] {
-        Keccak256::new()
+        T::new()
             .chain_update(c_current)
             .chain_update(quotient_eval.into_bytes())
             .chain_update(proof.w1_eval.into_bytes())
@@ -172,39 +204,42 @@ impl NuChallenges {
             .chain_update(proof.table2_omega_eval.into_bytes())
             .chain_update(proof.table3_omega_eval.into_bytes())
             .chain_update(proof.table4_omega_eval.into_bytes())
-            .finalize()
-            .into()
+            .finalize(hooks)
     }
 
-    fn c_v(&challenge: &[u8; 32]) -> [Fr; 30] {
+    fn c_v<H, T: Transcript<H>>(hooks: &H, &challenge: &[u8; 32]) -> [Fr; 30] {
         core::array::from_fn(|i| {
             if i == 0 {
                 challenge.into_fr()
             } else {
-                let hash: [u8; 32] = Keccak256::new()
+                let hash = T::new()
                     .chain_update(challenge)
                     .chain_update([i as u8])
-                    .finalize()
-                    .into();
+                    .finalize(hooks);
                 hash.into_fr()
             }
         })
     }
 
-    fn c_u<H: CurveHooks>(challenge: &[u8; 32], pi_z: &G1<H>, pi_z_omega: &G1<H>) -> Fr {
-        let hash: [u8; 32] = Keccak256::new()
+    fn c_u<H: CurveHooks, T: Transcript<H>>(
+        hooks: &H,
+        challenge: &[u8; 32],
+        pi_z: &G1<H>,
+        pi_z_omega: &G1<H>,
+    ) -> Fr {
+        let hash = T::new()
             .chain_update(challenge)
             .chain_update(pi_z.y.into_bytes())
             .chain_update(pi_z.x.into_bytes())
             .chain_update(pi_z_omega.y.into_bytes())
             .chain_update(pi_z_omega.x.into_bytes())
-            .finalize()
-            .into();
+            .finalize(hooks);
 
         hash.into_fr()
     }
 
-    fn compute_challenges<H: CurveHooks>(
+    fn compute_challenges<H: CurveHooks, T: Transcript<H>>(
+        hooks: &H,
         proof: &Proof<H>,
         c_current: &[u8],
         quotient_eval: &Fr,
@@ -213,9 +248,9 @@ impl NuChallenges {
             return Err(());
         }
 
-        let challenge = Self::challenge(proof, c_current, quotient_eval);
-        let c_v = Self::c_v(&challenge);
-        let c_u = Self::c_u(&challenge, &proof.pi_z, &proof.pi_z_omega);
+        let challenge = Self::challenge::<H, T>(hooks, proof, c_current, quotient_eval);
+        let c_v = Self::c_v::<H, T>(hooks, &challenge);
+        let c_u = Self::c_u::<H, T>(hooks, &challenge, &proof.pi_z, &proof.pi_z_omega);
 
         Ok(Self::new(c_v, c_u))
     }
@@ -229,61 +264,335 @@ struct AuxiliaryEvaluations {
     aux_limb_accumulator_evaluation: Fr,
 }
 
-pub fn verify<H: CurveHooks + Default>(
+/// Verifies `raw_proof` against the Solidity encoded `raw_vk`. Proofs with a non canonical
+/// encoding are rejected: see [`DecodingMode::Strict`].
+///
+/// Only UltraPlonk keys are accepted: Standard and Turbo ones are rejected with
+/// [`VerifyError::KeyError`], and must be verified through [`legacy::standard::verify`] and
+/// [`legacy::turbo::verify`] instead.
+pub fn verify<H: VerifierHooks + Default>(
+    raw_vk: &[u8],
+    raw_proof: &[u8],
+    pubs: &Public,
+) -> Result<(), VerifyError> {
+    verify_with_hooks(&H::default(), raw_vk, raw_proof, pubs)
+}
+
+/// Same as [`verify`], but the curve operations are performed through the `hooks` instance.
+pub fn verify_with_hooks<H: VerifierHooks>(
+    hooks: &H,
+    raw_vk: &[u8],
+    raw_proof: &[u8],
+    pubs: &Public,
+) -> Result<(), VerifyError> {
+    verify_raw::<H, Keccak256Transcript<H>>(
+        hooks,
+        raw_vk,
+        raw_proof,
+        pubs,
+        DecodingMode::Strict,
+        &PreparedSrs::default(),
+    )
+}
+
+/// Same as [`verify`], but the Fiat-Shamir challenges are derived with the `T` transcript
+/// instead of Keccak-256: it must be the one the proof was generated with.
+pub fn verify_with_transcript<H: VerifierHooks + Default, T: Transcript<H>>(
+    raw_vk: &[u8],
+    raw_proof: &[u8],
+    pubs: &Public,
+) -> Result<(), VerifyError> {
+    verify_raw::<H, T>(
+        &H::default(),
+        raw_vk,
+        raw_proof,
+        pubs,
+        DecodingMode::Strict,
+        &PreparedSrs::default(),
+    )
+}
+
+/// Same as [`verify`], but `raw_proof` is decoded according to `mode`.
+pub fn verify_with_mode<H: VerifierHooks + Default>(
+    raw_vk: &[u8],
+    raw_proof: &[u8],
+    pubs: &Public,
+    mode: DecodingMode,
+) -> Result<(), VerifyError> {
+    verify_raw::<H, Keccak256Transcript<H>>(
+        &H::default(),
+        raw_vk,
+        raw_proof,
+        pubs,
+        mode,
+        &PreparedSrs::default(),
+    )
+}
+
+/// Same as [`verify`], but the final pairing is checked against `srs` instead of the
+/// Ignition setup. Build the [`PreparedSrs`] once and reuse it across verifications.
+pub fn verify_with_srs<H: VerifierHooks + Default>(
+    raw_vk: &[u8],
+    raw_proof: &[u8],
+    pubs: &Public,
+    srs: &PreparedSrs<H>,
+) -> Result<(), VerifyError> {
+    verify_raw::<H, Keccak256Transcript<H>>(
+        &H::default(),
+        raw_vk,
+        raw_proof,
+        pubs,
+        DecodingMode::Strict,
+        srs,
+    )
+}
+
+fn verify_raw<H: VerifierHooks, T: Transcript<H>>(
+    hooks: &H,
     raw_vk: &[u8],
     raw_proof: &[u8],
     pubs: &Public,
+    mode: DecodingMode,
+    srs: &PreparedSrs<H>,
 ) -> Result<(), VerifyError> {
     let vk =
         VerificationKey::<H>::try_from_solidity_bytes(raw_vk).map_err(|_| VerifyError::KeyError)?;
-    let prepared_vk = PreparedVerificationKey::<H>::from(&vk);
+    let prepared_vk =
+        PreparedVerificationKey::<H>::try_from(&vk).map_err(|_| VerifyError::KeyError)?;
 
-    let proof = Proof::<H>::try_from(raw_proof).map_err(|_| VerifyError::InvalidProofError)?;
+    let proof = Proof::<H>::try_from_bytes_with_mode(raw_proof, mode)
+        .map_err(|_| VerifyError::InvalidProofError)?;
+    let proof = PreparedProof::<H>::try_from_proof_with_mode(&proof, mode)
+        .map_err(|_| VerifyError::InvalidProofError)?;
 
-    check_public_input_number(&vk, pubs)?;
+    check_public_input_number(vk.num_public_inputs, pubs)?;
     let public_inputs = &pubs
         .iter()
         .map(|pi_bytes| pi_bytes.into_u256())
         .collect::<Vec<U256>>();
 
-    verify_with_prepared_vk(&prepared_vk, &proof, public_inputs)
+    verify_with_prepared_vk::<H, T>(hooks, &prepared_vk, &proof, public_inputs, srs)
 }
 
-fn verify_with_prepared_vk<H: CurveHooks>(
+/// Verifies `proof` against an already prepared verification key and typed public inputs.
+///
+/// Nothing is parsed here: callers can build the [`PreparedVerificationKey`] once per circuit
+/// and reuse it for every proof, skipping the key decoding and the root of unity lookups
+/// that [`verify`] performs on each call. Only UltraPlonk keys can be prepared: Standard and
+/// Turbo proofs go through the [`legacy`] verifiers.
+pub fn verify_prepared<H: VerifierHooks>(
+    hooks: &H,
     vk: &PreparedVerificationKey<H>,
     proof: &Proof<H>,
+    pubs: &[Fr],
+) -> Result<(), VerifyError> {
+    verify_deferred(hooks, vk, proof, pubs)?.finalize(hooks)
+}
+
+/// Same as [`verify_prepared`], but the final pairing is checked against `srs`.
+pub fn verify_prepared_with_srs<H: VerifierHooks>(
+    hooks: &H,
+    vk: &PreparedVerificationKey<H>,
+    proof: &Proof<H>,
+    pubs: &[Fr],
+    srs: &PreparedSrs<H>,
+) -> Result<(), VerifyError> {
+    verify_deferred(hooks, vk, proof, pubs)?.finalize_with_srs(hooks, srs)
+}
+
+fn verify_with_prepared_vk<H: VerifierHooks, T: Transcript<H>>(
+    hooks: &H,
+    vk: &PreparedVerificationKey<H>,
+    proof: &PreparedProof<'_, H>,
     public_inputs: &[U256],
+    srs: &PreparedSrs<H>,
 ) -> Result<(), VerifyError> {
+    let (pairing_lhs, pairing_rhs) =
+        prepare_pairing_points::<H, T>(hooks, vk, proof, public_inputs)?;
+
+    // Check pairing relation
+    if pairing_check(hooks, pairing_lhs, pairing_rhs, srs)? {
+        Ok(())
+    } else {
+        Err(VerifyError::VerificationError)
+    }
+}
+
+/// Runs the whole verification up to the final pairing and returns the
+/// `(PAIRING_LHS, PAIRING_RHS)` couple, with `PAIRING_LHS` already negated.
+fn prepare_pairing_points<H: VerifierHooks, T: Transcript<H>>(
+    hooks: &H,
+    vk: &PreparedVerificationKey<H>,
+    proof: &PreparedProof<'_, H>,
+    public_inputs: &[U256],
+) -> Result<(G1<H>, G1<H>), VerifyError> {
+    let trace = compute_verification_trace::<H, T>(hooks, vk, proof, public_inputs)?;
+    Ok((trace.pairing_lhs, trace.pairing_rhs))
+}
+
+/// Runs the whole verification up to the final pairing, recording every intermediate value.
+/// The `verified` flag of the returned trace is left unset.
+fn compute_verification_trace<H: VerifierHooks, T: Transcript<H>>(
+    hooks: &H,
+    vk: &PreparedVerificationKey<H>,
+    proof: &PreparedProof<'_, H>,
+    public_inputs: &[U256],
+) -> Result<VerificationTrace<H>, VerifyError> {
+    let challenge_round = compute_challenge_round::<H, T>(hooks, vk, &proof.proof, public_inputs)?;
+    let challenges = challenge_round.challenges(vk);
+    let widget_round = compute_widget_round::<H>(vk, proof, &challenges, public_inputs)?;
+    let (quotient_eval, nu_challenges) = compute_nu_round::<H, T>(
+        hooks,
+        &proof.proof,
+        &challenge_round.c_current,
+        &widget_round,
+    )?;
+
+    // Compute pairing points
+    let (pairing_lhs, pairing_rhs) = compute_pairing_points(
+        hooks,
+        proof,
+        vk,
+        &challenges,
+        &nu_challenges,
+        &quotient_eval,
+        challenge_round.recursive_points,
+    )?;
+
+    Ok(VerificationTrace {
+        eta: challenge_round.eta,
+        beta: challenge_round.beta,
+        gamma: challenge_round.gamma,
+        alpha: challenge_round.alpha,
+        zeta: challenge_round.zeta,
+        c_v: nu_challenges.c_v,
+        c_u: nu_challenges.c_u,
+        public_input_delta: widget_round.public_input_delta,
+        plookup_delta: widget_round.plookup_delta,
+        zero_poly: widget_round.zero_poly,
+        zero_poly_inverse: widget_round.zero_poly_inverse,
+        l_start: widget_round.l_start,
+        l_end: widget_round.l_end,
+        permutation_identity: widget_round.permutation_identity,
+        plookup_identity: widget_round.plookup_identity,
+        arithmetic_identity: widget_round.arithmetic_identity,
+        sort_identity: widget_round.sort_identity,
+        elliptic_identity: widget_round.elliptic_identity,
+        aux_identity: widget_round.aux_identity,
+        quotient_eval,
+        pairing_lhs,
+        pairing_rhs,
+        verified: false,
+    })
+}
+
+/// The challenges of the rounds before the opening, together with the aggregation object
+/// of the inner proof, if any.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct ChallengeRound<H: CurveHooks> {
+    recursive_points: Option<(G1<H>, G1<H>)>,
+    eta: Fr,
+    beta: Fr,
+    gamma: Fr,
+    alpha: Fr,
+    zeta: Fr,
+    // The zeta challenge bytes, that the nu challenges are chained to
+    c_current: [u8; 32],
+}
+
+impl<H: CurveHooks> ChallengeRound<H> {
+    fn challenges(&self, vk: &PreparedVerificationKey<H>) -> Challenges {
+        Challenges::new(
+            self.alpha,
+            self.beta,
+            self.gamma,
+            self.zeta,
+            self.eta,
+            vk.circuit_size,
+        )
+    }
+}
+
+/// The field evaluations and the widget identities, all taken at `zeta`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct WidgetRound {
+    public_input_delta: Fr,
+    plookup_delta: Fr,
+    zero_poly: Fr,
+    zero_poly_inverse: Fr,
+    l_start: Fr,
+    l_end: Fr,
+    permutation_identity: Fr,
+    plookup_identity: Fr,
+    arithmetic_identity: Fr,
+    sort_identity: Fr,
+    elliptic_identity: Fr,
+    aux_identity: Fr,
+}
+
+fn compute_challenge_round<H: CurveHooks, T: Transcript<H>>(
+    hooks: &H,
+    vk: &PreparedVerificationKey<H>,
+    proof: &Proof<H>,
+    public_inputs: &[U256],
+) -> Result<ChallengeRound<H>, VerifyError> {
+    // Read the aggregation object of the inner proof, if any
+    let recursive_points = if vk.contains_recursive_proof {
+        Some(read_aggregation_object::<H>(
+            public_inputs,
+            vk.recursive_proof_indices,
+        )?)
+    } else {
+        None
+    };
+
     // Generate Challenges:
-    let mut challenge = generate_initial_challenge(vk.circuit_size, vk.num_public_inputs);
-    challenge = generate_eta_challenge::<H>(proof, public_inputs, &challenge);
+    let mut challenge =
+        generate_initial_challenge::<H, T>(hooks, vk.circuit_size, vk.num_public_inputs);
+    challenge = generate_eta_challenge::<H, T>(hooks, proof, public_inputs, &challenge);
     let eta = challenge.into_fr();
-    challenge = generate_beta_challenge::<H>(proof, &challenge);
+    challenge = generate_beta_challenge::<H, T>(hooks, proof, &challenge);
     let beta = challenge.into_fr();
-    challenge = generate_gamma_challenge(&challenge);
+    challenge = generate_gamma_challenge::<H, T>(hooks, &challenge);
     let gamma = challenge.into_fr();
-    challenge = generate_alpha_challenge::<H>(proof, &challenge);
+    challenge = generate_alpha_challenge::<H, T>(hooks, proof, &challenge);
     let alpha = challenge.into_fr();
-    let alpha_base = alpha;
-    challenge = generate_zeta_challenge::<H>(proof, &challenge);
+    challenge = generate_zeta_challenge::<H, T>(hooks, proof, &challenge);
     let zeta = challenge.into_fr();
-    let c_current = challenge;
-    let challenges = Challenges::new(alpha, beta, gamma, zeta, eta, vk.circuit_size);
+
+    Ok(ChallengeRound {
+        recursive_points,
+        eta,
+        beta,
+        gamma,
+        alpha,
+        zeta,
+        c_current: challenge,
+    })
+}
+
+fn compute_widget_round<H: CurveHooks>(
+    vk: &PreparedVerificationKey<H>,
+    proof: &PreparedProof<'_, H>,
+    challenges: &Challenges,
+    public_inputs: &[U256],
+) -> Result<WidgetRound, VerifyError> {
+    let alpha_base = challenges.alpha;
 
     // Evaluate Field Operations:
 
     // Compute Public Input Delta
     let (delta_numerator, delta_denominator) =
-        compute_public_input_delta(public_inputs, &vk.work_root, &challenges)?;
+        compute_public_input_delta(public_inputs, &vk.work_root, challenges)?;
 
     // Plookup delta factor
     let (plookup_delta_numerator, plookup_delta_denominator) =
-        compute_plookup_delta_factor(vk.circuit_size, &challenges);
+        compute_plookup_delta_factor(vk.circuit_size, challenges);
 
     // Lagrange poly and vanishing poly fractions
-    let [public_input_delta, _zero_poly, zero_poly_inverse, plookup_delta, l_start, l_end] =
+    let [public_input_delta, zero_poly, zero_poly_inverse, plookup_delta, l_start, l_end] =
         compute_lagrange_and_vanishing_poly::<H>(
-            &challenges,
+            challenges,
             vk,
             &delta_numerator,
             &delta_denominator,
@@ -296,7 +605,7 @@ fn verify_with_prepared_vk<H: CurveHooks>(
     // 1. Permutation Widget Evaluation
     let (permutation_identity, alpha_base) = compute_permutation_widget_evaluation::<H>(
         proof,
-        &challenges,
+        challenges,
         alpha_base,
         &l_start,
         &l_end,
@@ -306,7 +615,7 @@ fn verify_with_prepared_vk<H: CurveHooks>(
     // 2. Plookup Widget Evaluation
     let (plookup_identity, alpha_base) = compute_plookup_widget_evaluation::<H>(
         proof,
-        &challenges,
+        challenges,
         alpha_base,
         &l_start,
         &l_end,
@@ -315,52 +624,103 @@ fn verify_with_prepared_vk<H: CurveHooks>(
 
     // 3. Arithmetic Widget Evaluation
     let (arithmetic_identity, alpha_base) =
-        compute_arithmetic_widget_evaluation::<H>(proof, &challenges, alpha_base);
+        compute_arithmetic_widget_evaluation::<H>(proof, challenges, alpha_base);
 
     // 4. Genpermsort Widget Evaluation
     let (sort_identity, alpha_base) =
-        compute_genpermsort_widget_evaluation::<H>(proof, &challenges, alpha_base);
+        compute_genpermsort_widget_evaluation::<H>(proof, challenges, alpha_base);
 
     // 5. Elliptic Widget Evaluation
     let (elliptic_identity, alpha_base) =
-        compute_elliptic_widget_evaluation::<H>(proof, &challenges, alpha_base);
+        compute_elliptic_widget_evaluation::<H>(proof, challenges, alpha_base);
 
     // 6. Auxiliary Widget Evaluation
     let (aux_identity, _alpha_base) =
-        compute_auxiliary_widget_evaluation::<H>(proof, &challenges, alpha_base);
+        compute_auxiliary_widget_evaluation::<H>(proof, challenges, alpha_base);
+
+    Ok(WidgetRound {
+        public_input_delta,
+        plookup_delta,
+        zero_poly,
+        zero_poly_inverse,
+        l_start,
+        l_end,
+        permutation_identity,
+        plookup_identity,
+        arithmetic_identity,
+        sort_identity,
+        elliptic_identity,
+        aux_identity,
+    })
+}
 
+/// Evaluates the quotient and generates the nu and separator challenges from it.
+fn compute_nu_round<H: CurveHooks, T: Transcript<H>>(
+    hooks: &H,
+    proof: &Proof<H>,
+    c_current: &[u8; 32],
+    widgets: &WidgetRound,
+) -> Result<(Fr, NuChallenges), VerifyError> {
     // Quotient Evaluation
     let quotient_eval = quotient_evaluation(
-        &permutation_identity,
-        &plookup_identity,
-        &arithmetic_identity,
-        &sort_identity,
-        &elliptic_identity,
-        &aux_identity,
-        &zero_poly_inverse,
+        &widgets.permutation_identity,
+        &widgets.plookup_identity,
+        &widgets.arithmetic_identity,
+        &widgets.sort_identity,
+        &widgets.elliptic_identity,
+        &widgets.aux_identity,
+        &widgets.zero_poly_inverse,
     );
 
     // Generate Nu and Separator Challenges
-    let nu_challenges = NuChallenges::compute_challenges(proof, &c_current, &quotient_eval)
-        .map_err(|_| VerifyError::OtherError)?;
+    let nu_challenges =
+        NuChallenges::compute_challenges::<H, T>(hooks, proof, c_current, &quotient_eval)
+            .map_err(|_| VerifyError::OtherError)?;
 
-    // Check pairing relation
-    if perform_final_checks::<H>(proof, vk, &challenges, &nu_challenges, &quotient_eval) {
-        Ok(())
-    } else {
-        Err(VerifyError::VerificationError)
+    Ok((quotient_eval, nu_challenges))
+}
+
+/// Reads the `(P1, P2)` couple that a recursive proof exposes as the 16 public inputs
+/// starting at `index`. Each coordinate is split in four 68-bit limbs, least significant first.
+fn read_aggregation_object<H: CurveHooks>(
+    public_inputs: &[U256],
+    index: u32,
+) -> Result<(G1<H>, G1<H>), VerifyError> {
+    let limbs = public_inputs
+        .get(index as usize..)
+        .and_then(|limbs| limbs.get(..constants::AGGREGATION_OBJECT_SIZE))
+        .ok_or(VerifyError::PublicInputError {
+            message: "Aggregation object out of the public inputs".to_string(),
+        })?;
+
+    let coordinates = limbs
+        .chunks_exact(4)
+        .map(|limbs| {
+            combine_limbs(limbs, constants::AGGREGATION_OBJECT_LIMB_BITS).ok_or(
+                VerifyError::PublicInputError {
+                    message: "Invalid aggregation object coordinate".to_string(),
+                },
+            )
+        })
+        .collect::<Result<Vec<Fq>, _>>()?;
+
+    let p1 = G1::<H>::new_unchecked(coordinates[0], coordinates[1]);
+    let p2 = G1::<H>::new_unchecked(coordinates[2], coordinates[3]);
+    if !p1.is_on_curve() || !p2.is_on_curve() {
+        return Err(VerifyError::PublicInputError {
+            message: "Aggregation object point not on curve".to_string(),
+        });
     }
+
+    Ok((p1, p2))
 }
 
-fn check_public_input_number<H: CurveHooks>(
-    vk: &VerificationKey<H>,
-    pubs: &[PublicInput],
-) -> Result<(), VerifyError> {
-    if vk.num_public_inputs != pubs.len() as u32 {
+fn check_public_input_number<T>(num_public_inputs: u32, pubs: &[T]) -> Result<(), VerifyError> {
+    if num_public_inputs != pubs.len() as u32 {
         Err(VerifyError::PublicInputError {
             message: format!(
                 "Provided public inputs length does not match. Expected: {}; Got: {}",
-                vk.num_public_inputs,
+                num_public_inputs,
                 pubs.len()
             ),
         })
@@ -370,21 +730,25 @@ fn check_public_input_number<H: CurveHooks>(
 }
 
 // Generate initial challenge
-fn generate_initial_challenge(n: u32, num_public_inputs: u32) -> [u8; 32] {
-    Keccak256::new()
+fn generate_initial_challenge<H, T: Transcript<H>>(
+    hooks: &H,
+    n: u32,
+    num_public_inputs: u32,
+) -> [u8; 32] {
+    T::new()
         .chain_update(n.to_be_bytes())
         .chain_update(num_public_inputs.to_be_bytes())
-        .finalize()
-        .into()
+        .finalize(hooks)
 }
 
 // Generate eta challenge
-fn generate_eta_challenge<H: CurveHooks>(
+fn generate_eta_challenge<H: CurveHooks, T: Transcript<H>>(
+    hooks: &H,
     proof: &Proof<H>,
     public_inputs: &[U256],
     initial_challenge: &[u8; 32],
 ) -> [u8; 32] {
-    let mut hasher = Keccak256::new();
+    let mut hasher = T::new();
 
     hasher.update(initial_challenge);
     for input in public_inputs {
@@ -397,45 +761,54 @@ fn generate_eta_challenge<H: CurveHooks>(
     hasher.update(proof.w3.y.into_bytes());
     hasher.update(proof.w3.x.into_bytes());
 
-    hasher.finalize().into()
+    hasher.finalize(hooks)
 }
 
 // Generate beta challenge
-fn generate_beta_challenge<H: CurveHooks>(proof: &Proof<H>, challenge: &[u8; 32]) -> [u8; 32] {
-    Keccak256::new()
+fn generate_beta_challenge<H: CurveHooks, T: Transcript<H>>(
+    hooks: &H,
+    proof: &Proof<H>,
+    challenge: &[u8; 32],
+) -> [u8; 32] {
+    T::new()
         .chain_update(challenge)
         .chain_update(proof.w4.y.into_bytes())
         .chain_update(proof.w4.x.into_bytes())
         .chain_update(proof.s.y.into_bytes())
         .chain_update(proof.s.x.into_bytes())
-        .finalize()
-        .into()
+        .finalize(hooks)
 }
 
 // Generate gamma challenge
-fn generate_gamma_challenge(challenge: &[u8; 32]) -> [u8; 32] {
-    Keccak256::new()
+fn generate_gamma_challenge<H, T: Transcript<H>>(hooks: &H, challenge: &[u8; 32]) -> [u8; 32] {
+    T::new()
         .chain_update(challenge)
         .chain_update([1u8])
-        .finalize()
-        .into()
+        .finalize(hooks)
 }
 
 // Generate alpha challenge
-fn generate_alpha_challenge<H: CurveHooks>(proof: &Proof<H>, challenge: &[u8; 32]) -> [u8; 32] {
-    Keccak256::new()
+fn generate_alpha_challenge<H: CurveHooks, T: Transcript<H>>(
+    hooks: &H,
+    proof: &Proof<H>,
+    challenge: &[u8; 32],
+) -> [u8; 32] {
+    T::new()
         .chain_update(challenge)
         .chain_update(proof.z.y.into_bytes())
         .chain_update(proof.z.x.into_bytes())
         .chain_update(proof.z_lookup.y.into_bytes())
         .chain_update(proof.z_lookup.x.into_bytes())
-        .finalize()
-        .into()
+        .finalize(hooks)
 }
 
 // Generate zeta challenge
-fn generate_zeta_challenge<H: CurveHooks>(proof: &Proof<H>, challenge: &[u8; 32]) -> [u8; 32] {
-    Keccak256::new()
+fn generate_zeta_challenge<H: CurveHooks, T: Transcript<H>>(
+    hooks: &H,
+    proof: &Proof<H>,
+    challenge: &[u8; 32],
+) -> [u8; 32] {
+    T::new()
         .chain_update(challenge)
         .chain_update(proof.t1.y.into_bytes())
         .chain_update(proof.t1.x.into_bytes())
@@ -445,8 +818,7 @@ fn generate_zeta_challenge<H: CurveHooks>(proof: &Proof<H>, challenge: &[u8; 32]
         .chain_update(proof.t3.x.into_bytes())
         .chain_update(proof.t4.y.into_bytes())
         .chain_update(proof.t4.x.into_bytes())
-        .finalize()
-        .into()
+        .finalize(hooks)
 }
 
 /// Compute Public Input Delta:
@@ -456,19 +828,39 @@ fn compute_public_input_delta(
     work_root: &Fr,
     challenges: &Challenges,
 ) -> Result<(Fr, Fr), VerifyError> {
+    // σ(i) = 0x05.ωⁱ, σ'(i) = 0x0c.ωⁱ
+    compute_coset_public_input_delta(
+        public_inputs,
+        work_root,
+        &challenges.beta,
+        &challenges.gamma,
+        MontFp!("5"),
+        MontFp!("12"),
+    )
+}
+
+/// Same as [`compute_public_input_delta`], with σ(i) = k.ωⁱ and σ'(i) = k'.ωⁱ.
+fn compute_coset_public_input_delta(
+    public_inputs: &[U256],
+    work_root: &Fr,
+    beta: &Fr,
+    gamma: &Fr,
+    k: Fr,
+    k_external: Fr,
+) -> Result<(Fr, Fr), VerifyError> {
+    check_public_inputs_in_fr(public_inputs)?;
+
     let mut numerator_value = Fr::ONE;
     let mut denominator_value = Fr::ONE;
-    let mut valid_inputs = true;
 
-    // root_1 = β * 0x05
-    let mut root_1 = challenges.beta * MontFp!("5"); // k1.β
+    // root_1 = β * k
+    let mut root_1 = *beta * k;
 
-    // root_2 = β * 0x0c
-    let mut root_2 = challenges.beta * MontFp!("12");
+    // root_2 = β * k'
+    let mut root_2 = *beta * k_external;
 
     for &input in public_inputs {
-        valid_inputs &= input < FrConfig::MODULUS;
-        let temp = input.into_fr() + challenges.gamma;
+        let temp = input.into_fr() + gamma;
         numerator_value *= root_1 + temp;
         denominator_value *= root_2 + temp;
 
@@ -476,13 +868,17 @@ fn compute_public_input_delta(
         root_2 *= work_root;
     }
 
-    if !valid_inputs {
-        return Err(VerifyError::PublicInputError {
+    Ok((numerator_value, denominator_value))
+}
+
+fn check_public_inputs_in_fr(public_inputs: &[U256]) -> Result<(), VerifyError> {
+    if public_inputs.iter().all(|input| *input < FrConfig::MODULUS) {
+        Ok(())
+    } else {
+        Err(VerifyError::PublicInputError {
             message: "Found public input greater than scalar field modulus".to_string(),
-        });
+        })
     }
-
-    Ok((numerator_value, denominator_value))
 }
 
 /// Compute Plookup delta factor [γ(1 + β)]^{n-k},
@@ -567,34 +963,34 @@ fn compute_lagrange_and_vanishing_poly<H: CurveHooks>(
 }
 
 fn compute_permutation_widget_evaluation<H: CurveHooks>(
-    proof: &Proof<H>,
+    proof: &PreparedProof<'_, H>,
     challenges: &Challenges,
     mut alpha_base: Fr,
     l_start: &Fr,
     l_end: &Fr,
     public_input_delta: &Fr,
 ) -> (Fr, Fr) {
-    let w1_eval = proof.w1_eval.into_fr();
-    let w2_eval = proof.w2_eval.into_fr();
-    let w3_eval = proof.w3_eval.into_fr();
-    let w4_eval = proof.w4_eval.into_fr();
+    let w1_eval = proof.w1_eval;
+    let w2_eval = proof.w2_eval;
+    let w3_eval = proof.w3_eval;
+    let w4_eval = proof.w4_eval;
 
-    let z_eval = proof.z_eval.into_fr();
-    let z_omega_eval = proof.z_omega_eval.into_fr();
+    let z_eval = proof.z_eval;
+    let z_omega_eval = proof.z_omega_eval;
 
-    let mut t1 = (w1_eval + challenges.gamma + challenges.beta * proof.id1_eval.into_fr())
-        * (w2_eval + challenges.gamma + challenges.beta * proof.id2_eval.into_fr());
+    let mut t1 = (w1_eval + challenges.gamma + challenges.beta * proof.id1_eval)
+        * (w2_eval + challenges.gamma + challenges.beta * proof.id2_eval);
 
-    let mut t2 = (w3_eval + challenges.gamma + challenges.beta * proof.id3_eval.into_fr())
-        * (w4_eval + challenges.gamma + challenges.beta * proof.id4_eval.into_fr());
+    let mut t2 = (w3_eval + challenges.gamma + challenges.beta * proof.id3_eval)
+        * (w4_eval + challenges.gamma + challenges.beta * proof.id4_eval);
 
     let mut result = alpha_base * z_eval * t1 * t2;
 
-    t1 = (w1_eval + challenges.gamma + challenges.beta * proof.sigma1_eval.into_fr())
-        * (w2_eval + challenges.gamma + challenges.beta * proof.sigma2_eval.into_fr());
+    t1 = (w1_eval + challenges.gamma + challenges.beta * proof.sigma1_eval)
+        * (w2_eval + challenges.gamma + challenges.beta * proof.sigma2_eval);
 
-    t2 = (w3_eval + challenges.gamma + challenges.beta * proof.sigma3_eval.into_fr())
-        * (w4_eval + challenges.gamma + challenges.beta * proof.sigma4_eval.into_fr());
+    t2 = (w3_eval + challenges.gamma + challenges.beta * proof.sigma3_eval)
+        * (w4_eval + challenges.gamma + challenges.beta * proof.sigma4_eval);
 
     result -= alpha_base * z_omega_eval * t1 * t2;
 
@@ -608,46 +1004,44 @@ fn compute_permutation_widget_evaluation<H: CurveHooks>(
 }
 
 fn compute_plookup_widget_evaluation<H: CurveHooks>(
-    proof: &Proof<H>,
+    proof: &PreparedProof<'_, H>,
     challenges: &Challenges,
     mut alpha_base: Fr,
     l_start: &Fr,
     l_end: &Fr,
     plookup_delta: &Fr,
 ) -> (Fr, Fr) {
-    let mut f = challenges.eta * proof.q3_eval.into_fr();
-    f += proof.w3_eval.into_fr() + proof.qc_eval.into_fr() * proof.w3_omega_eval.into_fr();
+    let mut f = challenges.eta * proof.q3_eval;
+    f += proof.w3_eval + proof.qc_eval * proof.w3_omega_eval;
     f *= challenges.eta;
-    f += proof.w2_eval.into_fr() + proof.qm_eval.into_fr() * proof.w2_omega_eval.into_fr();
+    f += proof.w2_eval + proof.qm_eval * proof.w2_omega_eval;
     f *= challenges.eta;
-    f += proof.w1_eval.into_fr() + proof.q2_eval.into_fr() * proof.w1_omega_eval.into_fr();
+    f += proof.w1_eval + proof.q2_eval * proof.w1_omega_eval;
 
-    let t = proof.table4_eval.into_fr() * challenges.eta_cube
-        + proof.table3_eval.into_fr() * challenges.eta_sqr
-        + proof.table2_eval.into_fr() * challenges.eta
-        + proof.table1_eval.into_fr();
+    let t = proof.table4_eval * challenges.eta_cube
+        + proof.table3_eval * challenges.eta_sqr
+        + proof.table2_eval * challenges.eta
+        + proof.table1_eval;
 
-    let t_omega = proof.table4_omega_eval.into_fr() * challenges.eta_cube
-        + proof.table3_omega_eval.into_fr() * challenges.eta_sqr
-        + proof.table2_omega_eval.into_fr() * challenges.eta
-        + proof.table1_omega_eval.into_fr();
+    let t_omega = proof.table4_omega_eval * challenges.eta_cube
+        + proof.table3_omega_eval * challenges.eta_sqr
+        + proof.table2_omega_eval * challenges.eta
+        + proof.table1_omega_eval;
 
     let gamma_beta_constant = challenges.gamma * (challenges.beta + Fr::ONE);
-    let mut numerator = f * proof.table_type_eval.into_fr() + challenges.gamma;
+    let mut numerator = f * proof.table_type_eval + challenges.gamma;
     let temp0 = t + t_omega * challenges.beta + gamma_beta_constant;
     numerator *= temp0;
     numerator *= challenges.beta + Fr::ONE;
     let temp0 = challenges.alpha * l_start;
     numerator += temp0;
-    numerator *= proof.z_lookup_eval.into_fr();
+    numerator *= proof.z_lookup_eval;
     numerator -= temp0;
 
-    let mut denominator = proof.s_eval.into_fr()
-        + proof.s_omega_eval.into_fr() * challenges.beta
-        + gamma_beta_constant;
+    let mut denominator = proof.s_eval + proof.s_omega_eval * challenges.beta + gamma_beta_constant;
     let temp1 = challenges.alpha_sqr * l_end;
     denominator -= temp1;
-    denominator *= proof.z_lookup_omega_eval.into_fr();
+    denominator *= proof.z_lookup_omega_eval;
     denominator += temp1 * plookup_delta;
 
     let plookup_identity = (numerator - denominator) * alpha_base;
@@ -659,33 +1053,32 @@ fn compute_plookup_widget_evaluation<H: CurveHooks>(
 }
 
 fn compute_arithmetic_widget_evaluation<H: CurveHooks>(
-    proof: &Proof<H>,
+    proof: &PreparedProof<'_, H>,
     challenges: &Challenges,
     mut alpha_base: Fr,
 ) -> (Fr, Fr) {
-    let w1q1 = proof.w1_eval.into_fr() * proof.q1_eval.into_fr();
-    let w2q2 = proof.w2_eval.into_fr() * proof.q2_eval.into_fr();
-    let w3q3 = proof.w3_eval.into_fr() * proof.q3_eval.into_fr();
-    let w4q4 = proof.w4_eval.into_fr() * proof.q4_eval.into_fr();
-
-    let w1w2qm = proof.w1_eval.into_fr()
-        * proof.w2_eval.into_fr()
-        * proof.qm_eval.into_fr()
-        * (proof.q_arith_eval.into_fr() - MontFp!("3"))
+    let w1q1 = proof.w1_eval * proof.q1_eval;
+    let w2q2 = proof.w2_eval * proof.q2_eval;
+    let w3q3 = proof.w3_eval * proof.q3_eval;
+    let w4q4 = proof.w4_eval * proof.q4_eval;
+
+    let w1w2qm = proof.w1_eval
+        * proof.w2_eval
+        * proof.qm_eval
+        * (proof.q_arith_eval - MontFp!("3"))
         * constants::NEGATIVE_INVERSE_OF_2;
 
-    let identity = w1w2qm + w1q1 + w2q2 + w3q3 + w4q4 + proof.qc_eval.into_fr();
+    let identity = w1w2qm + w1q1 + w2q2 + w3q3 + w4q4 + proof.qc_eval;
 
     let extra_small_addition_gate_identity = challenges.alpha
-        * (proof.q_arith_eval.into_fr() - MontFp!("2"))
-        * (proof.w1_eval.into_fr() + proof.w4_eval.into_fr() - proof.w1_omega_eval.into_fr()
-            + proof.qm_eval.into_fr());
+        * (proof.q_arith_eval - MontFp!("2"))
+        * (proof.w1_eval + proof.w4_eval - proof.w1_omega_eval + proof.qm_eval);
 
     let arithmetic_identity = alpha_base
-        * proof.q_arith_eval.into_fr()
+        * proof.q_arith_eval
         * (identity
-            + (proof.q_arith_eval.into_fr() - Fr::ONE)
-                * (proof.w4_omega_eval.into_fr() + extra_small_addition_gate_identity));
+            + (proof.q_arith_eval - Fr::ONE)
+                * (proof.w4_omega_eval + extra_small_addition_gate_identity));
 
     // update alpha
     alpha_base *= challenges.alpha_sqr;
@@ -694,14 +1087,14 @@ fn compute_arithmetic_widget_evaluation<H: CurveHooks>(
 }
 
 fn compute_genpermsort_widget_evaluation<H: CurveHooks>(
-    proof: &Proof<H>,
+    proof: &PreparedProof<'_, H>,
     challenges: &Challenges,
     mut alpha_base: Fr,
 ) -> (Fr, Fr) {
-    let d1 = proof.w2_eval.into_fr() - proof.w1_eval.into_fr();
-    let d2 = proof.w3_eval.into_fr() - proof.w2_eval.into_fr();
-    let d3 = proof.w4_eval.into_fr() - proof.w3_eval.into_fr();
-    let d4 = proof.w1_omega_eval.into_fr() - proof.w4_eval.into_fr();
+    let d1 = proof.w2_eval - proof.w1_eval;
+    let d2 = proof.w3_eval - proof.w2_eval;
+    let d3 = proof.w4_eval - proof.w3_eval;
+    let d4 = proof.w1_omega_eval - proof.w4_eval;
 
     let mut range_accumulator =
         d1 * (d1 - Fr::ONE) * (d1 - MontFp!("2")) * (d1 - MontFp!("3")) * alpha_base;
@@ -723,7 +1116,7 @@ fn compute_genpermsort_widget_evaluation<H: CurveHooks>(
         * (d4 - MontFp!("3"))
         * alpha_base
         * challenges.alpha_cube;
-    range_accumulator *= proof.q_sort_eval.into_fr();
+    range_accumulator *= proof.q_sort_eval;
 
     let sort_identity = range_accumulator;
 
@@ -734,59 +1127,55 @@ fn compute_genpermsort_widget_evaluation<H: CurveHooks>(
 }
 
 fn compute_elliptic_widget_evaluation<H: CurveHooks>(
-    proof: &Proof<H>,
+    proof: &PreparedProof<'_, H>,
     challenges: &Challenges,
     mut alpha_base: Fr,
 ) -> (Fr, Fr) {
     // Aliases:
-    let x1_eval = &proof.w2_eval;
-    let x2_eval = &proof.w1_omega_eval;
-    let x3_eval = &proof.w2_omega_eval;
-    let y1_eval = &proof.w3_eval;
-    let y2_eval = &proof.w4_omega_eval;
-    let y3_eval = &proof.w3_omega_eval;
-    let qsign = &proof.q1_eval;
-
-    let x_diff = x2_eval.into_fr() - x1_eval.into_fr();
-    let y2_sqr = y2_eval.into_fr().square();
-    let y1_sqr = y1_eval.into_fr().square();
-    let y1y2 = y1_eval.into_fr() * y2_eval.into_fr() * qsign.into_fr();
-
-    let mut x_add_identity = (x3_eval.into_fr() + x2_eval.into_fr() + x1_eval.into_fr())
-        * x_diff.square()
-        + y1y2.double()
-        - (y1_sqr + y2_sqr);
-    x_add_identity = x_add_identity * (Fr::ONE - proof.qm_eval.into_fr()) * alpha_base;
-
-    let y1_plus_y3 = y1_eval.into_fr() + y3_eval.into_fr();
-    let y_diff = y2_eval.into_fr() * qsign.into_fr() - y1_eval.into_fr();
-    let mut y_add_identity =
-        y1_plus_y3 * x_diff + ((x3_eval.into_fr() - x1_eval.into_fr()) * y_diff);
-    y_add_identity *= (Fr::ONE - proof.qm_eval.into_fr()) * alpha_base * challenges.alpha;
-
-    let mut elliptic_identity = (x_add_identity + y_add_identity) * proof.q_elliptic_eval.into_fr();
+    let x1_eval = proof.w2_eval;
+    let x2_eval = proof.w1_omega_eval;
+    let x3_eval = proof.w2_omega_eval;
+    let y1_eval = proof.w3_eval;
+    let y2_eval = proof.w4_omega_eval;
+    let y3_eval = proof.w3_omega_eval;
+    let qsign = proof.q1_eval;
+
+    let x_diff = x2_eval - x1_eval;
+    let y2_sqr = y2_eval.square();
+    let y1_sqr = y1_eval.square();
+    let y1y2 = y1_eval * y2_eval * qsign;
+
+    let mut x_add_identity =
+        (x3_eval + x2_eval + x1_eval) * x_diff.square() + y1y2.double() - (y1_sqr + y2_sqr);
+    x_add_identity = x_add_identity * (Fr::ONE - proof.qm_eval) * alpha_base;
+
+    let y1_plus_y3 = y1_eval + y3_eval;
+    let y_diff = y2_eval * qsign - y1_eval;
+    let mut y_add_identity = y1_plus_y3 * x_diff + ((x3_eval - x1_eval) * y_diff);
+    y_add_identity *= (Fr::ONE - proof.qm_eval) * alpha_base * challenges.alpha;
+
+    let mut elliptic_identity = (x_add_identity + y_add_identity) * proof.q_elliptic_eval;
 
     // y^2 = x^3 + ax + b
     // for Grumpkin, a = 0 and b = -17. We use b in a custom gate relation that evaluates elliptic curve arithmetic
     let grumpkin_curve_b_parameter_negated = MontFp!("17");
 
-    let x1_sqr = x1_eval.into_fr().square();
-    let x_pow_4 = (y1_sqr + grumpkin_curve_b_parameter_negated) * x1_eval.into_fr();
+    let x1_sqr = x1_eval.square();
+    let x_pow_4 = (y1_sqr + grumpkin_curve_b_parameter_negated) * x1_eval;
     let y1_sqr_mul_4 = y1_sqr * MontFp!("4");
     let x1_pow_4_mul_9 = x_pow_4 * MontFp!("9");
     let x1_sqr_mul_3 = x1_sqr * MontFp!("3");
-    let mut x_double_identity =
-        (x3_eval.into_fr() + x1_eval.into_fr().double()) * y1_sqr_mul_4 - x1_pow_4_mul_9;
+    let mut x_double_identity = (x3_eval + x1_eval.double()) * y1_sqr_mul_4 - x1_pow_4_mul_9;
 
-    let mut y_double_identity = x1_sqr_mul_3 * (x1_eval.into_fr() - x3_eval.into_fr())
-        - y1_eval.into_fr().double() * (y1_eval.into_fr() + y3_eval.into_fr());
+    let mut y_double_identity =
+        x1_sqr_mul_3 * (x1_eval - x3_eval) - y1_eval.double() * (y1_eval + y3_eval);
 
     x_double_identity *= alpha_base;
     y_double_identity *= alpha_base * challenges.alpha;
-    x_double_identity *= proof.qm_eval.into_fr();
-    y_double_identity *= proof.qm_eval.into_fr();
+    x_double_identity *= proof.qm_eval;
+    y_double_identity *= proof.qm_eval;
 
-    elliptic_identity += (x_double_identity + y_double_identity) * proof.q_elliptic_eval.into_fr();
+    elliptic_identity += (x_double_identity + y_double_identity) * proof.q_elliptic_eval;
 
     // update alpha
     alpha_base *= challenges.alpha_quad;
@@ -794,82 +1183,79 @@ fn compute_elliptic_widget_evaluation<H: CurveHooks>(
     (elliptic_identity, alpha_base)
 }
 
-fn compute_aux_non_native_field_evaluation<H: CurveHooks>(proof: &Proof<H>) -> Fr {
-    let mut limb_subproduct = proof.w1_eval.into_fr() * proof.w2_omega_eval.into_fr()
-        + proof.w1_omega_eval.into_fr() * proof.w2_eval.into_fr();
+fn compute_aux_non_native_field_evaluation<H: CurveHooks>(proof: &PreparedProof<'_, H>) -> Fr {
+    let mut limb_subproduct =
+        proof.w1_eval * proof.w2_omega_eval + proof.w1_omega_eval * proof.w2_eval;
 
-    let mut non_native_field_gate_2 = proof.w1_eval.into_fr() * proof.w4_eval.into_fr()
-        + proof.w2_eval.into_fr() * proof.w3_eval.into_fr()
-        - proof.w3_omega_eval.into_fr();
+    let mut non_native_field_gate_2 =
+        proof.w1_eval * proof.w4_eval + proof.w2_eval * proof.w3_eval - proof.w3_omega_eval;
 
     non_native_field_gate_2 *= LIMB_SIZE;
-    non_native_field_gate_2 -= proof.w4_omega_eval.into_fr();
+    non_native_field_gate_2 -= proof.w4_omega_eval;
     non_native_field_gate_2 += limb_subproduct;
-    non_native_field_gate_2 *= proof.q4_eval.into_fr();
+    non_native_field_gate_2 *= proof.q4_eval;
     limb_subproduct *= LIMB_SIZE;
-    limb_subproduct += proof.w1_omega_eval.into_fr() * proof.w2_omega_eval.into_fr();
+    limb_subproduct += proof.w1_omega_eval * proof.w2_omega_eval;
 
-    let non_native_field_gate_1 = (limb_subproduct
-        - (proof.w3_eval.into_fr() + proof.w4_eval.into_fr()))
-        * proof.q3_eval.into_fr();
+    let non_native_field_gate_1 =
+        (limb_subproduct - (proof.w3_eval + proof.w4_eval)) * proof.q3_eval;
 
-    let non_native_field_gate_3 = (limb_subproduct + proof.w4_eval.into_fr()
-        - (proof.w3_omega_eval.into_fr() + proof.w4_omega_eval.into_fr()))
-        * proof.qm_eval.into_fr();
+    let non_native_field_gate_3 = (limb_subproduct + proof.w4_eval
+        - (proof.w3_omega_eval + proof.w4_omega_eval))
+        * proof.qm_eval;
 
     // compute non_native_field_identity
-    (non_native_field_gate_1 + non_native_field_gate_2 + non_native_field_gate_3)
-        * proof.q2_eval.into_fr()
+    (non_native_field_gate_1 + non_native_field_gate_2 + non_native_field_gate_3) * proof.q2_eval
 }
 
-fn compute_aux_limb_accumulator_evaluation<H: CurveHooks>(proof: &Proof<H>) -> Fr {
-    let mut limb_accumulator_1 = proof.w2_omega_eval.into_fr() * SUBLIMB_SHIFT;
-    limb_accumulator_1 += proof.w1_omega_eval.into_fr();
+fn compute_aux_limb_accumulator_evaluation<H: CurveHooks>(proof: &PreparedProof<'_, H>) -> Fr {
+    let mut limb_accumulator_1 = proof.w2_omega_eval * SUBLIMB_SHIFT;
+    limb_accumulator_1 += proof.w1_omega_eval;
     limb_accumulator_1 *= SUBLIMB_SHIFT;
-    limb_accumulator_1 += proof.w3_eval.into_fr();
+    limb_accumulator_1 += proof.w3_eval;
     limb_accumulator_1 *= SUBLIMB_SHIFT;
-    limb_accumulator_1 += proof.w2_eval.into_fr();
+    limb_accumulator_1 += proof.w2_eval;
     limb_accumulator_1 *= SUBLIMB_SHIFT;
-    limb_accumulator_1 += proof.w1_eval.into_fr();
-    limb_accumulator_1 += -proof.w4_eval.into_fr();
-    limb_accumulator_1 *= proof.q4_eval.into_fr();
+    limb_accumulator_1 += proof.w1_eval;
+    limb_accumulator_1 += -proof.w4_eval;
+    limb_accumulator_1 *= proof.q4_eval;
 
-    let mut limb_accumulator_2 = proof.w3_omega_eval.into_fr() * SUBLIMB_SHIFT;
-    limb_accumulator_2 += proof.w2_omega_eval.into_fr();
+    let mut limb_accumulator_2 = proof.w3_omega_eval * SUBLIMB_SHIFT;
+    limb_accumulator_2 += proof.w2_omega_eval;
     limb_accumulator_2 *= SUBLIMB_SHIFT;
-    limb_accumulator_2 += proof.w1_omega_eval.into_fr();
+    limb_accumulator_2 += proof.w1_omega_eval;
     limb_accumulator_2 *= SUBLIMB_SHIFT;
-    limb_accumulator_2 += proof.w4_eval.into_fr();
+    limb_accumulator_2 += proof.w4_eval;
     limb_accumulator_2 *= SUBLIMB_SHIFT;
-    limb_accumulator_2 += proof.w3_eval.into_fr();
-    limb_accumulator_2 += -proof.w4_omega_eval.into_fr();
-    limb_accumulator_2 *= proof.qm_eval.into_fr();
+    limb_accumulator_2 += proof.w3_eval;
+    limb_accumulator_2 += -proof.w4_omega_eval;
+    limb_accumulator_2 *= proof.qm_eval;
 
-    (limb_accumulator_1 + limb_accumulator_2) * proof.q3_eval.into_fr()
+    (limb_accumulator_1 + limb_accumulator_2) * proof.q3_eval
 }
 
 fn compute_aux_ram_consistency_evaluation<H: CurveHooks>(
-    proof: &Proof<H>,
+    proof: &PreparedProof<'_, H>,
     challenges: &Challenges,
     index_delta: &Fr,
     partial_record_check: &Fr,
     index_is_monotonically_increasing: &Fr,
 ) -> Fr {
-    let mut next_gate_access_type = proof.w3_omega_eval.into_fr() * challenges.eta;
-    next_gate_access_type += proof.w2_omega_eval.into_fr();
+    let mut next_gate_access_type = proof.w3_omega_eval * challenges.eta;
+    next_gate_access_type += proof.w2_omega_eval;
     next_gate_access_type *= challenges.eta;
-    next_gate_access_type += proof.w1_omega_eval.into_fr();
+    next_gate_access_type += proof.w1_omega_eval;
     next_gate_access_type *= challenges.eta;
-    next_gate_access_type = proof.w4_omega_eval.into_fr() - next_gate_access_type;
+    next_gate_access_type = proof.w4_omega_eval - next_gate_access_type;
 
-    let value_delta = proof.w3_omega_eval.into_fr() - proof.w3_eval.into_fr();
+    let value_delta = proof.w3_omega_eval - proof.w3_eval;
 
     let adjacent_values_match_if_adjacent_indices_match_and_next_access_is_a_read_operation =
         (Fr::ONE - index_delta) * value_delta * (Fr::ONE - next_gate_access_type);
 
     // AUX_RAM_CONSISTENCY_EVALUATION:
 
-    let access_type = proof.w4_eval.into_fr() - partial_record_check;
+    let access_type = proof.w4_eval - partial_record_check;
 
     let access_check = access_type * (access_type - Fr::ONE);
 
@@ -890,30 +1276,27 @@ fn compute_aux_ram_consistency_evaluation<H: CurveHooks>(
 }
 
 fn compute_auxiliary_identity<H: CurveHooks>(
-    proof: &Proof<H>,
+    proof: &PreparedProof<'_, H>,
     challenges: &Challenges,
     mut alpha_base: Fr,
     index_delta: &Fr,
     aux_evaluations: &AuxiliaryEvaluations,
 ) -> (Fr, Fr) {
-    let timestamp_delta = proof.w2_omega_eval.into_fr() - proof.w2_eval.into_fr();
+    let timestamp_delta = proof.w2_omega_eval - proof.w2_eval;
 
-    let ram_timestamp_check_identity =
-        (Fr::ONE - index_delta) * timestamp_delta - proof.w3_eval.into_fr();
+    let ram_timestamp_check_identity = (Fr::ONE - index_delta) * timestamp_delta - proof.w3_eval;
 
-    let mut memory_identity =
-        aux_evaluations.aux_rom_consistency_evaluation * proof.q2_eval.into_fr();
-    memory_identity += ram_timestamp_check_identity * proof.q4_eval.into_fr();
-    memory_identity += aux_evaluations.aux_memory_evaluation * proof.qm_eval.into_fr();
-    memory_identity *= proof.q1_eval.into_fr();
-    memory_identity +=
-        aux_evaluations.aux_ram_consistency_evaluation * proof.q_arith_eval.into_fr();
+    let mut memory_identity = aux_evaluations.aux_rom_consistency_evaluation * proof.q2_eval;
+    memory_identity += ram_timestamp_check_identity * proof.q4_eval;
+    memory_identity += aux_evaluations.aux_memory_evaluation * proof.qm_eval;
+    memory_identity *= proof.q1_eval;
+    memory_identity += aux_evaluations.aux_ram_consistency_evaluation * proof.q_arith_eval;
 
     let mut auxiliary_identity = memory_identity + aux_evaluations.aux_non_native_field_evaluation;
 
     auxiliary_identity += aux_evaluations.aux_limb_accumulator_evaluation;
 
-    auxiliary_identity *= proof.q_aux_eval.into_fr();
+    auxiliary_identity *= proof.q_aux_eval;
 
     auxiliary_identity *= alpha_base;
 
@@ -924,27 +1307,27 @@ fn compute_auxiliary_identity<H: CurveHooks>(
 }
 
 fn compute_auxiliary_widget_evaluation<H: CurveHooks>(
-    proof: &Proof<H>,
+    proof: &PreparedProof<'_, H>,
     challenges: &Challenges,
     alpha_base: Fr,
 ) -> (Fr, Fr) {
     let aux_non_native_field_evaluation = compute_aux_non_native_field_evaluation::<H>(proof);
     let aux_limb_accumulator_evaluation = compute_aux_limb_accumulator_evaluation::<H>(proof);
 
-    let mut memory_record_check = proof.w3_eval.into_fr() * challenges.eta;
-    memory_record_check += proof.w2_eval.into_fr();
+    let mut memory_record_check = proof.w3_eval * challenges.eta;
+    memory_record_check += proof.w2_eval;
     memory_record_check *= challenges.eta;
-    memory_record_check += proof.w1_eval.into_fr();
+    memory_record_check += proof.w1_eval;
     memory_record_check *= challenges.eta;
-    memory_record_check += proof.qc_eval.into_fr();
+    memory_record_check += proof.qc_eval;
 
     let partial_record_check = memory_record_check;
-    memory_record_check += -proof.w4_eval.into_fr();
+    memory_record_check += -proof.w4_eval;
 
     let aux_memory_evaluation = memory_record_check;
 
-    let index_delta = proof.w1_omega_eval.into_fr() - proof.w1_eval.into_fr();
-    let record_delta = proof.w4_omega_eval.into_fr() - proof.w4_eval.into_fr();
+    let index_delta = proof.w1_omega_eval - proof.w1_eval;
+    let record_delta = proof.w4_omega_eval - proof.w4_eval;
     let index_is_monotonically_increasing = index_delta * (index_delta - Fr::ONE);
 
     let adjacent_values_match_if_adjacent_indices_match = record_delta * (Fr::ONE - index_delta);
@@ -998,13 +1381,15 @@ fn quotient_evaluation(
         * zero_poly_inverse
 }
 
-fn perform_final_checks<H: CurveHooks>(
-    proof: &Proof<H>,
+fn compute_pairing_points<H: VerifierHooks>(
+    hooks: &H,
+    proof: &PreparedProof<'_, H>,
     vk: &PreparedVerificationKey<H>,
     challenges: &Challenges,
     nu_challenges: &NuChallenges,
     quotient_eval: &Fr,
-) -> bool {
+    recursive_points: Option<(G1<H>, G1<H>)>,
+) -> Result<(G1<H>, G1<H>), VerifyError> {
     // Note: Validations already took place back when we parsed the proof.
     let u_plus_one = nu_challenges.c_u + Fr::ONE;
     let zeta_pow_2n = challenges.zeta_pow_n.square();
@@ -1012,18 +1397,18 @@ fn perform_final_checks<H: CurveHooks>(
     let batch_evaluation =
         compute_batch_evaluation_scalar_multiplier::<H>(proof, nu_challenges, quotient_eval);
 
-    let bases = [
-        proof.t1,
-        proof.t2,
-        proof.t3,
-        proof.t4,
-        proof.w1,
-        proof.w2,
-        proof.w3,
-        proof.w4,
-        proof.s,
-        proof.z,
-        proof.z_lookup,
+    let bases: [G1<H>; FINAL_MSM_SIZE] = [
+        proof.proof.t1,
+        proof.proof.t2,
+        proof.proof.t3,
+        proof.proof.t4,
+        proof.proof.w1,
+        proof.proof.w2,
+        proof.proof.w3,
+        proof.proof.w4,
+        proof.proof.s,
+        proof.proof.z,
+        proof.proof.z_lookup,
         vk.q_1,
         vk.q_2,
         vk.q_3,
@@ -1048,11 +1433,11 @@ fn perform_final_checks<H: CurveHooks>(
         vk.id_3,
         vk.id_4,
         <<Config<H> as BnConfig>::G1Config as SWCurveConfig>::GENERATOR,
-        proof.pi_z,
-        proof.pi_z_omega,
+        proof.proof.pi_z,
+        proof.proof.pi_z_omega,
     ];
 
-    let scalars = [
+    let scalars: [Fr; FINAL_MSM_SIZE] = [
         Fr::ONE,
         challenges.zeta_pow_n,
         zeta_pow_2n,
@@ -1092,88 +1477,133 @@ fn perform_final_checks<H: CurveHooks>(
         challenges.zeta * nu_challenges.c_u * vk.work_root,
     ];
 
-    let pairing_rhs = H::bn254_msm_g1(&bases, &scalars).unwrap();
+    let mut pairing_rhs = match &vk.fixed_base_tables {
+        None => hooks
+            .msm_g1(&bases, &scalars)
+            .map_err(|_| VerifyError::CurveHooksError)?,
+        // The VK commitments and the generator go through the tables, the proof points
+        // through the hooks
+        Some(tables) => {
+            let fixed = FIXED_BASES_RANGE;
+            let fixed_bases = bases[fixed.clone()].try_into().unwrap();
+            debug_assert_eq!(fixed_bases, vk.fixed_bases());
+            let variable_bases = [&bases[..fixed.start], &bases[fixed.end..]].concat();
+            let variable_scalars = [&scalars[..fixed.start], &scalars[fixed.end..]].concat();
+
+            hooks
+                .msm_g1(&variable_bases, &variable_scalars)
+                .map_err(|_| VerifyError::CurveHooksError)?
+                + tables
+                    .msm(&fixed_bases, &scalars[fixed])
+                    .ok_or(VerifyError::KeyError)?
+        }
+    };
 
     // PAIRING_LHS = [PI_Z] + [PI_Z_OMEGA] * u
-    let bases = [proof.pi_z_omega, proof.pi_z];
+    let bases = [proof.proof.pi_z_omega, proof.proof.pi_z];
     let scalars = [nu_challenges.c_u, Fr::ONE];
-    let mut pairing_lhs = H::bn254_msm_g1(&bases, &scalars).unwrap();
+    let mut pairing_lhs = hooks
+        .msm_g1(&bases, &scalars)
+        .map_err(|_| VerifyError::CurveHooksError)?;
     pairing_lhs.y = -pairing_lhs.y;
 
-    // Perform Pairing:
+    // PAIRING_RHS += [RECURSIVE_P1] * u^2, PAIRING_LHS += [RECURSIVE_P2] * u^2
+    if let Some((recursive_p1, recursive_p2)) = recursive_points {
+        let u_squared = nu_challenges.c_u.square();
+        pairing_rhs += hooks
+            .msm_g1(&[recursive_p1], &[u_squared])
+            .map_err(|_| VerifyError::CurveHooksError)?;
+        pairing_lhs += hooks
+            .msm_g1(&[recursive_p2], &[u_squared])
+            .map_err(|_| VerifyError::CurveHooksError)?;
+    }
+
+    Ok((pairing_lhs.into_affine(), pairing_rhs.into_affine()))
+}
 
+/// Checks `e(PAIRING_RHS, [1]_2) * e(PAIRING_LHS, [x]_2) == 1`.
+///
+/// The Miller loop and the final exponentiation go straight through the hooks, so that their
+/// failures are reported as [`VerifyError::CurveHooksError`].
+fn pairing_check<H: VerifierHooks>(
+    hooks: &H,
+    pairing_lhs: G1<H>,
+    pairing_rhs: G1<H>,
+    srs: &PreparedSrs<H>,
+) -> Result<bool, VerifyError> {
     // rhs paired with [1]_2
     // lhs paired with [x]_2
 
-    let g1_points = [
-        G1Prepared::from(pairing_rhs.into_affine()),
-        G1Prepared::from(pairing_lhs.into_affine()),
-    ];
+    let g1_points = [G1Prepared::from(pairing_rhs), G1Prepared::from(pairing_lhs)];
 
-    let g2_points = [
-        G2Prepared::from(G2::<H>::generator()),
-        G2Prepared::from(read_g2::<H>(&SRS_G2).unwrap()),
-    ];
+    let g2_points = [srs.g2_generator.clone(), srs.g2_x.clone()];
 
-    let product = Bn254::<H>::multi_pairing(g1_points, g2_points);
+    let miller_loop = hooks
+        .multi_miller_loop(g1_points.into_iter(), g2_points.into_iter())
+        .map_err(|_| VerifyError::CurveHooksError)?;
+    let product = hooks
+        .final_exponentiation(miller_loop)
+        .map_err(|_| VerifyError::CurveHooksError)?;
 
-    product.0.is_one()
+    Ok(product.is_one())
 }
 
 // Compute Batch Evaluation Scalar Multiplier
 fn compute_batch_evaluation_scalar_multiplier<H: CurveHooks>(
-    proof: &Proof<H>,
+    proof: &PreparedProof<'_, H>,
     nu_challenges: &NuChallenges,
     quotient_eval: &Fr,
 ) -> Fr {
-    let mut batch_evaluation = nu_challenges.c_v[0]
-        * (proof.w1_omega_eval.into_fr() * nu_challenges.c_u + proof.w1_eval.into_fr());
-    batch_evaluation += nu_challenges.c_v[1]
-        * (proof.w2_omega_eval.into_fr() * nu_challenges.c_u + proof.w2_eval.into_fr());
-    batch_evaluation += nu_challenges.c_v[2]
-        * (proof.w3_omega_eval.into_fr() * nu_challenges.c_u + proof.w3_eval.into_fr());
-    batch_evaluation += nu_challenges.c_v[3]
-        * (proof.w4_omega_eval.into_fr() * nu_challenges.c_u + proof.w4_eval.into_fr());
-    batch_evaluation += nu_challenges.c_v[4]
-        * (proof.s_omega_eval.into_fr() * nu_challenges.c_u + proof.s_eval.into_fr());
-    batch_evaluation += nu_challenges.c_v[5]
-        * (proof.z_omega_eval.into_fr() * nu_challenges.c_u + proof.z_eval.into_fr());
+    let mut batch_evaluation =
+        nu_challenges.c_v[0] * (proof.w1_omega_eval * nu_challenges.c_u + proof.w1_eval);
+    batch_evaluation +=
+        nu_challenges.c_v[1] * (proof.w2_omega_eval * nu_challenges.c_u + proof.w2_eval);
+    batch_evaluation +=
+        nu_challenges.c_v[2] * (proof.w3_omega_eval * nu_challenges.c_u + proof.w3_eval);
+    batch_evaluation +=
+        nu_challenges.c_v[3] * (proof.w4_omega_eval * nu_challenges.c_u + proof.w4_eval);
+    batch_evaluation +=
+        nu_challenges.c_v[4] * (proof.s_omega_eval * nu_challenges.c_u + proof.s_eval);
+    batch_evaluation +=
+        nu_challenges.c_v[5] * (proof.z_omega_eval * nu_challenges.c_u + proof.z_eval);
     batch_evaluation += nu_challenges.c_v[6]
-        * (proof.z_lookup_omega_eval.into_fr() * nu_challenges.c_u + proof.z_lookup_eval.into_fr());
-
-    batch_evaluation += nu_challenges.c_v[7] * proof.q1_eval.into_fr();
-    batch_evaluation += nu_challenges.c_v[8] * proof.q2_eval.into_fr();
-    batch_evaluation += nu_challenges.c_v[9] * proof.q3_eval.into_fr();
-    batch_evaluation += nu_challenges.c_v[10] * proof.q4_eval.into_fr();
-    batch_evaluation += nu_challenges.c_v[11] * proof.qm_eval.into_fr();
-    batch_evaluation += nu_challenges.c_v[12] * proof.qc_eval.into_fr();
-    batch_evaluation += nu_challenges.c_v[13] * proof.q_arith_eval.into_fr();
-    batch_evaluation += nu_challenges.c_v[14] * proof.q_sort_eval.into_fr();
-    batch_evaluation += nu_challenges.c_v[15] * proof.q_elliptic_eval.into_fr();
-    batch_evaluation += nu_challenges.c_v[16] * proof.q_aux_eval.into_fr();
-    batch_evaluation += nu_challenges.c_v[17] * proof.sigma1_eval.into_fr();
-    batch_evaluation += nu_challenges.c_v[18] * proof.sigma2_eval.into_fr();
-    batch_evaluation += nu_challenges.c_v[19] * proof.sigma3_eval.into_fr();
-    batch_evaluation += nu_challenges.c_v[20] * proof.sigma4_eval.into_fr();
-
-    batch_evaluation += nu_challenges.c_v[21]
-        * (proof.table1_omega_eval.into_fr() * nu_challenges.c_u + proof.table1_eval.into_fr());
-    batch_evaluation += nu_challenges.c_v[22]
-        * (proof.table2_omega_eval.into_fr() * nu_challenges.c_u + proof.table2_eval.into_fr());
-    batch_evaluation += nu_challenges.c_v[23]
-        * (proof.table3_omega_eval.into_fr() * nu_challenges.c_u + proof.table3_eval.into_fr());
-    batch_evaluation += nu_challenges.c_v[24]
-        * (proof.table4_omega_eval.into_fr() * nu_challenges.c_u + proof.table4_eval.into_fr());
-
-    batch_evaluation += nu_challenges.c_v[25] * proof.table_type_eval.into_fr();
-    batch_evaluation += nu_challenges.c_v[26] * proof.id1_eval.into_fr();
-    batch_evaluation += nu_challenges.c_v[27] * proof.id2_eval.into_fr();
-    batch_evaluation += nu_challenges.c_v[28] * proof.id3_eval.into_fr();
-    batch_evaluation += nu_challenges.c_v[29] * proof.id4_eval.into_fr();
+        * (proof.z_lookup_omega_eval * nu_challenges.c_u + proof.z_lookup_eval);
+
+    batch_evaluation += nu_challenges.c_v[7] * proof.q1_eval;
+    batch_evaluation += nu_challenges.c_v[8] * proof.q2_eval;
+    batch_evaluation += nu_challenges.c_v[9] * proof.q3_eval;
+    batch_evaluation += nu_challenges.c_v[10] * proof.q4_eval;
+    batch_evaluation += nu_challenges.c_v[11] * proof.qm_eval;
+    batch_evaluation += nu_challenges.c_v[12] * proof.qc_eval;
+    batch_evaluation += nu_challenges.c_v[13] * proof.q_arith_eval;
+    batch_evaluation += nu_challenges.c_v[14] * proof.q_sort_eval;
+    batch_evaluation += nu_challenges.c_v[15] * proof.q_elliptic_eval;
+    batch_evaluation += nu_challenges.c_v[16] * proof.q_aux_eval;
+    batch_evaluation += nu_challenges.c_v[17] * proof.sigma1_eval;
+    batch_evaluation += nu_challenges.c_v[18] * proof.sigma2_eval;
+    batch_evaluation += nu_challenges.c_v[19] * proof.sigma3_eval;
+    batch_evaluation += nu_challenges.c_v[20] * proof.sigma4_eval;
+
+    batch_evaluation +=
+        nu_challenges.c_v[21] * (proof.table1_omega_eval * nu_challenges.c_u + proof.table1_eval);
+    batch_evaluation +=
+        nu_challenges.c_v[22] * (proof.table2_omega_eval * nu_challenges.c_u + proof.table2_eval);
+    batch_evaluation +=
+        nu_challenges.c_v[23] * (proof.table3_omega_eval * nu_challenges.c_u + proof.table3_eval);
+    batch_evaluation +=
+        nu_challenges.c_v[24] * (proof.table4_omega_eval * nu_challenges.c_u + proof.table4_eval);
+
+    batch_evaluation += nu_challenges.c_v[25] * proof.table_type_eval;
+    batch_evaluation += nu_challenges.c_v[26] * proof.id1_eval;
+    batch_evaluation += nu_challenges.c_v[27] * proof.id2_eval;
+    batch_evaluation += nu_challenges.c_v[28] * proof.id3_eval;
+    batch_evaluation += nu_challenges.c_v[29] * proof.id4_eval;
     batch_evaluation += quotient_eval;
 
     batch_evaluation
 }
 
+#[cfg(test)]
+mod fixtures;
 #[cfg(test)]
 mod should;
@@ -0,0 +1,219 @@
+// Copyright 2024 Horizen Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0 or MIT
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Verification spread over the current rayon pool.
+//!
+//! The hooks are shared by reference between the workers, hence the `Sync` bound: hooks
+//! that keep a mutable state (a gas meter, call counters) need to synchronize it.
+
+use alloc::vec::Vec;
+
+use ark_std::{rand::RngCore, UniformRand};
+use rayon::prelude::*;
+
+use crate::{
+    batch::{check_entries, prepare_item, BatchEntry, G1Projective},
+    errors::VerifyError,
+    hooks::VerifierHooks,
+    pairing_check, BatchItem, Fr, Keccak256Transcript, PreparedSrs, G1,
+};
+
+/// The smallest MSM that is split between the workers. Below it, the MSM goes through a
+/// single [`VerifierHooks::msm_g1`] call.
+pub const PARALLEL_MSM_THRESHOLD: usize = 64;
+
+/// Verifies independent proofs in parallel, returning the result of each of them in the
+/// order of `items`.
+///
+/// Unlike [`crate::verify_batch`], every proof gets its own pairing check, so that a bad
+/// proof can't slow down the others.
+pub fn verify_many<H: VerifierHooks + Sync>(
+    hooks: &H,
+    items: &[BatchItem<'_, H>],
+) -> Vec<Result<(), VerifyError>> {
+    verify_many_with_srs(hooks, items, &PreparedSrs::default())
+}
+
+/// Same as [`verify_many`], but the pairings are checked against `srs`.
+pub fn verify_many_with_srs<H: VerifierHooks + Sync>(
+    hooks: &H,
+    items: &[BatchItem<'_, H>],
+    srs: &PreparedSrs<H>,
+) -> Vec<Result<(), VerifyError>> {
+    items
+        .par_iter()
+        .map(|(vk, proof, pubs)| {
+            let (pairing_lhs, pairing_rhs) =
+                prepare_item::<H, Keccak256Transcript<H>>(hooks, vk, proof, pubs)?;
+            if pairing_check(hooks, pairing_lhs, pairing_rhs, srs)? {
+                Ok(())
+            } else {
+                Err(VerifyError::VerificationError)
+            }
+        })
+        .collect()
+}
+
+/// Same as [`crate::verify_batch`], but the proofs are reduced to their pairing points in
+/// parallel, and the MSMs of the folded pairing check are split between the workers once
+/// they reach [`PARALLEL_MSM_THRESHOLD`] points.
+pub fn verify_batch_parallel<H: VerifierHooks + Sync, R: RngCore>(
+    hooks: &H,
+    items: &[BatchItem<'_, H>],
+    rng: &mut R,
+) -> Result<(), VerifyError> {
+    verify_batch_parallel_with_srs(hooks, items, rng, &PreparedSrs::default())
+}
+
+/// Same as [`verify_batch_parallel`], but the pairings are checked against `srs`.
+pub fn verify_batch_parallel_with_srs<H: VerifierHooks + Sync, R: RngCore>(
+    hooks: &H,
+    items: &[BatchItem<'_, H>],
+    rng: &mut R,
+    srs: &PreparedSrs<H>,
+) -> Result<(), VerifyError> {
+    // The rng can't be shared between the workers: draw the scalars upfront
+    let scalars = (0..items.len()).map(|_| Fr::rand(rng)).collect::<Vec<Fr>>();
+    let points = items
+        .par_iter()
+        .map(|(vk, proof, pubs)| prepare_item::<H, Keccak256Transcript<H>>(hooks, vk, proof, pubs))
+        .collect::<Vec<_>>();
+
+    let mut invalid = Vec::new();
+    let mut entries = Vec::with_capacity(items.len());
+    for (index, (points, r)) in points.into_iter().zip(scalars).enumerate() {
+        match points {
+            Ok((pairing_lhs, pairing_rhs)) => entries.push(BatchEntry {
+                index,
+                pairing_lhs,
+                pairing_rhs,
+                r,
+            }),
+            Err(_) => invalid.push(index),
+        }
+    }
+
+    check_entries(hooks, &entries, invalid, srs, par_msm_g1::<H>)
+}
+
+// Splits the MSM in one chunk per worker and sums the partial results.
+fn par_msm_g1<H: VerifierHooks + Sync>(
+    hooks: &H,
+    bases: &[G1<H>],
+    scalars: &[Fr],
+) -> Result<G1Projective<H>, VerifyError> {
+    let msm = |bases: &[G1<H>], scalars: &[Fr]| {
+        hooks
+            .msm_g1(bases, scalars)
+            .map_err(|_| VerifyError::CurveHooksError)
+    };
+    if bases.len() < PARALLEL_MSM_THRESHOLD {
+        return msm(bases, scalars);
+    }
+
+    let chunk_size = bases.len().div_ceil(rayon::current_num_threads());
+    bases
+        .par_chunks(chunk_size)
+        .zip(scalars.par_chunks(chunk_size))
+        .map(|(bases, scalars)| msm(bases, scalars))
+        .try_reduce(G1Projective::<H>::default, |a, b| Ok(a + b))
+}
+
+#[cfg(test)]
+mod should {
+    use super::*;
+    use crate::{
+        fixtures::{bb_output, bb_prepared_vk, bb_vk},
+        key::VerificationKey,
+        proof::Proof,
+        PreparedVerificationKey, PublicInput,
+    };
+    use ark_ec::{AffineRepr, CurveGroup, VariableBaseMSM};
+    use rstest::{fixture, rstest};
+
+    #[fixture]
+    fn bb_proof(bb_vk: VerificationKey<()>, bb_output: &[u8]) -> (Vec<PublicInput>, Proof<()>) {
+        Proof::<()>::from_bb_output(bb_output, &bb_vk).unwrap()
+    }
+
+    fn wrong_pubs(pubs: &[PublicInput]) -> Vec<PublicInput> {
+        let mut pubs = pubs.to_vec();
+        pubs[0][31] ^= 1;
+        pubs
+    }
+
+    #[rstest]
+    fn verify_many_proofs_independently(
+        bb_prepared_vk: PreparedVerificationKey<()>,
+        bb_proof: (Vec<PublicInput>, Proof<()>),
+    ) {
+        let (pubs, proof) = bb_proof;
+        let wrong_pubs = wrong_pubs(&pubs);
+        let items = [
+            (&bb_prepared_vk, &proof, &pubs[..]),
+            (&bb_prepared_vk, &proof, &wrong_pubs[..]),
+            (&bb_prepared_vk, &proof, &pubs[..0]),
+        ];
+
+        let results = verify_many(&(), &items);
+
+        assert_eq!(results[0], Ok(()));
+        assert_eq!(results[1], Err(VerifyError::VerificationError));
+        assert!(matches!(
+            results[2],
+            Err(VerifyError::PublicInputError { .. })
+        ));
+    }
+
+    #[rstest]
+    fn report_every_invalid_proof_of_a_parallel_batch(
+        bb_prepared_vk: PreparedVerificationKey<()>,
+        bb_proof: (Vec<PublicInput>, Proof<()>),
+    ) {
+        let (pubs, proof) = bb_proof;
+        let wrong_pubs = wrong_pubs(&pubs);
+        let mut items = alloc::vec![(&bb_prepared_vk, &proof, &pubs[..]); 5];
+        items[1].2 = &wrong_pubs[..];
+        items[4].2 = &wrong_pubs[..];
+
+        assert_eq!(
+            verify_batch_parallel(&(), &items, &mut ark_std::test_rng()),
+            Err(VerifyError::BatchVerificationError {
+                invalid: alloc::vec![1, 4]
+            })
+        );
+        assert_eq!(
+            verify_batch_parallel(&(), &items[..1], &mut ark_std::test_rng()),
+            Ok(())
+        );
+    }
+
+    #[rstest]
+    fn split_a_large_msm_between_the_workers() {
+        let rng = &mut ark_std::test_rng();
+        let size = 4 * PARALLEL_MSM_THRESHOLD + 1;
+        let bases = (0..size)
+            .map(|_| (G1::<()>::generator() * Fr::rand(rng)).into_affine())
+            .collect::<Vec<_>>();
+        let scalars = (0..size).map(|_| Fr::rand(rng)).collect::<Vec<_>>();
+
+        assert_eq!(
+            par_msm_g1(&(), &bases, &scalars).unwrap().into_affine(),
+            G1Projective::<()>::msm(&bases, &scalars)
+                .unwrap()
+                .into_affine()
+        );
+    }
+}
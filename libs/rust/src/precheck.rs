@@ -0,0 +1,244 @@
+// Copyright 2024 Horizen Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0 or MIT
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use alloc::vec::Vec;
+use core::{fmt, marker::PhantomData};
+
+use crate::{
+    check_public_input_number, check_public_inputs_in_fr, compute_challenge_round,
+    compute_nu_round, compute_pairing_points, compute_widget_round,
+    errors::VerifyError,
+    hooks::VerifierHooks,
+    key::{PreparedVerificationKey, VerificationKey},
+    pairing_check,
+    proof::{PreparedProof, Proof},
+    utils::IntoU256,
+    ChallengeRound, Keccak256Transcript, PreparedSrs, Public, Transcript, U256,
+};
+
+/// A submission that went through [`precheck`], ready for the expensive part of the
+/// verification. The remaining challenges are derived with the `T` transcript, the one the
+/// first ones were derived with.
+///
+/// The handle owns everything the checks produced: the prepared key, the proof with its
+/// evaluations already converted to [`crate::Fr`], the public inputs and the challenges up
+/// to `zeta`. Only [`precheck`] builds it, so [`Self::verify`] neither parses nor converts
+/// anything again: it can only fail on the verification itself or on the curve hooks.
+pub struct Prechecked<H: VerifierHooks, T: Transcript<H> = Keccak256Transcript<H>> {
+    vk: PreparedVerificationKey<H>,
+    proof: PreparedProof<'static, H>,
+    public_inputs: Vec<U256>,
+    challenge_round: ChallengeRound<H>,
+    transcript: PhantomData<fn() -> T>,
+}
+
+// Not derived, to not require `T` to implement them.
+impl<H: VerifierHooks, T: Transcript<H>> fmt::Debug for Prechecked<H, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Prechecked")
+            .field("vk", &self.vk)
+            .field("proof", &self.proof)
+            .field("public_inputs", &self.public_inputs)
+            .field("challenge_round", &self.challenge_round)
+            .finish()
+    }
+}
+
+impl<H: VerifierHooks, T: Transcript<H>> PartialEq for Prechecked<H, T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.vk == other.vk
+            && self.proof == other.proof
+            && self.public_inputs == other.public_inputs
+            && self.challenge_round == other.challenge_round
+    }
+}
+
+impl<H: VerifierHooks, T: Transcript<H>> Eq for Prechecked<H, T> {}
+
+/// Does the cheap part of [`crate::verify`], to reject malformed submissions before
+/// spending any MSM or pairing on them (the challenges are hashed through `hooks`):
+/// - strict parsing of the verification key, the proof and its evaluations;
+/// - the number of public inputs, and their range;
+/// - the Fiat-Shamir challenges up to `zeta`, and the aggregation object if any.
+///
+/// The returned handle finishes the verification without doing any of this again. Only
+/// UltraPlonk keys are accepted: the legacy circuits go through [`crate::legacy`].
+pub fn precheck<H: VerifierHooks>(
+    hooks: &H,
+    raw_vk: &[u8],
+    raw_proof: &[u8],
+    pubs: &Public,
+) -> Result<Prechecked<H>, VerifyError> {
+    precheck_with_transcript::<H, Keccak256Transcript<H>>(hooks, raw_vk, raw_proof, pubs)
+}
+
+/// Same as [`precheck`], but the Fiat-Shamir challenges are derived with the `T`
+/// transcript: it must be the one the proof was generated with.
+pub fn precheck_with_transcript<H: VerifierHooks, T: Transcript<H>>(
+    hooks: &H,
+    raw_vk: &[u8],
+    raw_proof: &[u8],
+    pubs: &Public,
+) -> Result<Prechecked<H, T>, VerifyError> {
+    let vk =
+        VerificationKey::<H>::try_from_solidity_bytes(raw_vk).map_err(|_| VerifyError::KeyError)?;
+    let vk = PreparedVerificationKey::<H>::try_from(&vk).map_err(|_| VerifyError::KeyError)?;
+
+    let proof = Proof::<H>::try_from(raw_proof).map_err(|_| VerifyError::InvalidProofError)?;
+    let proof = PreparedProof::try_from(proof).map_err(|_| VerifyError::InvalidProofError)?;
+
+    check_public_input_number(vk.num_public_inputs, pubs)?;
+    let public_inputs = pubs
+        .iter()
+        .map(|pi_bytes| pi_bytes.into_u256())
+        .collect::<Vec<U256>>();
+    check_public_inputs_in_fr(&public_inputs)?;
+
+    let challenge_round =
+        compute_challenge_round::<H, T>(hooks, &vk, &proof.proof, &public_inputs)?;
+
+    Ok(Prechecked {
+        vk,
+        proof,
+        public_inputs,
+        challenge_round,
+        transcript: PhantomData,
+    })
+}
+
+impl<H: VerifierHooks, T: Transcript<H>> Prechecked<H, T> {
+    /// Completes the verification against the Ignition `[x]_2` point.
+    pub fn verify(&self, hooks: &H) -> Result<(), VerifyError> {
+        self.verify_with_srs(hooks, &PreparedSrs::default())
+    }
+
+    /// Completes the verification against the prepared `[x]_2` point of `srs`.
+    pub fn verify_with_srs(&self, hooks: &H, srs: &PreparedSrs<H>) -> Result<(), VerifyError> {
+        let challenges = self.challenge_round.challenges(&self.vk);
+
+        let widget_round =
+            compute_widget_round::<H>(&self.vk, &self.proof, &challenges, &self.public_inputs)?;
+        let (quotient_eval, nu_challenges) = compute_nu_round::<H, T>(
+            hooks,
+            &self.proof.proof,
+            &self.challenge_round.c_current,
+            &widget_round,
+        )?;
+        let (pairing_lhs, pairing_rhs) = compute_pairing_points(
+            hooks,
+            &self.proof,
+            &self.vk,
+            &challenges,
+            &nu_challenges,
+            &quotient_eval,
+            self.challenge_round.recursive_points,
+        )?;
+
+        if pairing_check(hooks, pairing_lhs, pairing_rhs, srs)? {
+            Ok(())
+        } else {
+            Err(VerifyError::VerificationError)
+        }
+    }
+}
+
+#[cfg(test)]
+mod should {
+    use super::*;
+    use crate::{
+        fixtures::{bb_output, bb_vk},
+        PublicInput, PUBS_SIZE,
+    };
+    use rstest::{fixture, rstest};
+
+    #[fixture]
+    fn vk(bb_vk: VerificationKey<()>) -> Vec<u8> {
+        bb_vk.as_solidity_bytes()
+    }
+
+    fn split(bb_output: &[u8]) -> ([PublicInput; 1], &[u8]) {
+        (
+            bb_output[..PUBS_SIZE].try_into().unwrap(),
+            &bb_output[PUBS_SIZE..],
+        )
+    }
+
+    #[rstest]
+    fn precheck_and_verify_a_valid_proof(vk: Vec<u8>, bb_output: &[u8]) {
+        let (pubs, proof) = split(bb_output);
+
+        let prechecked = precheck::<()>(&(), &vk, proof, &pubs).unwrap();
+
+        assert_eq!(prechecked.verify(&()), Ok(()));
+        assert_eq!(
+            prechecked.verify(&()),
+            crate::verify::<()>(&vk, proof, &pubs)
+        );
+    }
+
+    mod reject {
+        use super::*;
+
+        #[rstest]
+        fn a_wrong_number_of_public_inputs(vk: Vec<u8>, bb_output: &[u8]) {
+            let (pubs, proof) = split(bb_output);
+
+            assert!(matches!(
+                precheck::<()>(&(), &vk, proof, &[pubs[0], pubs[0]]),
+                Err(VerifyError::PublicInputError { .. })
+            ));
+        }
+
+        #[rstest]
+        fn a_public_input_out_of_fr(vk: Vec<u8>, bb_output: &[u8]) {
+            let (_, proof) = split(bb_output);
+
+            assert!(matches!(
+                precheck::<()>(&(), &vk, proof, &[[0xff; PUBS_SIZE]]),
+                Err(VerifyError::PublicInputError { .. })
+            ));
+        }
+
+        #[rstest]
+        fn a_malformed_proof(vk: Vec<u8>, bb_output: &[u8]) {
+            let (pubs, proof) = split(bb_output);
+
+            assert_eq!(
+                precheck::<()>(&(), &vk, &proof[1..], &pubs),
+                Err(VerifyError::InvalidProofError)
+            );
+        }
+
+        #[rstest]
+        fn a_malformed_vk(vk: Vec<u8>, bb_output: &[u8]) {
+            let (pubs, proof) = split(bb_output);
+
+            assert_eq!(
+                precheck::<()>(&(), &vk[1..], proof, &pubs),
+                Err(VerifyError::KeyError)
+            );
+        }
+
+        #[rstest]
+        fn a_wrong_public_input_only_at_verification(vk: Vec<u8>, bb_output: &[u8]) {
+            let (mut pubs, proof) = split(bb_output);
+            pubs[0][31] ^= 1;
+
+            let prechecked = precheck::<()>(&(), &vk, proof, &pubs).unwrap();
+
+            assert_eq!(prechecked.verify(&()), Err(VerifyError::VerificationError));
+        }
+    }
+}
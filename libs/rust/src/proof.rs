@@ -16,10 +16,13 @@
 
 use crate::{
     errors::{FieldError, GroupError},
-    utils::{read_fq_util, read_g1_util},
-    Fq, G1, PROOF_SIZE,
+    key::VerificationKey,
+    utils::{read_fq_util, read_g1_util, IntoBytes, IntoU256},
+    DecodingMode, Fq, Fr, PublicInput, G1, PROOF_SIZE, PUBS_SIZE,
 };
+use alloc::{borrow::Cow, vec::Vec};
 use ark_bn254_ext::CurveHooks;
+use ark_ff::PrimeField;
 use snafu::Snafu;
 
 #[derive(Debug, PartialEq, Snafu)]
@@ -52,11 +55,26 @@ pub enum ProofError {
     #[snafu(display("Value is not a member of Fq"))]
     NotMember,
 
+    #[snafu(display("Point at infinity is not a valid commitment"))]
+    PointAtInfinity,
+
+    #[snafu(display("Buffer too short. Expected: {}; Got: {}", expected_size, actual_size))]
+    BufferTooShort {
+        expected_size: usize,
+        actual_size: usize,
+    },
+
+    #[snafu(display("Public input {} is not a member of Fr", index))]
+    PublicInputNotMember { index: usize },
+
+    #[snafu(display("Evaluation {} is not a member of Fr", field))]
+    EvaluationNotMember { field: &'static str },
+
     #[snafu(display("Other error"))]
     OtherError,
 }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Eq)]
 pub struct Proof<H: CurveHooks> {
     pub w1: G1<H>,
     pub w2: G1<H>,
@@ -114,10 +132,29 @@ pub struct Proof<H: CurveHooks> {
     pub pi_z_omega: G1<H>,
 }
 
-fn read_proof_g1<H: CurveHooks>(data: &[u8], offset: &mut usize) -> Result<G1<H>, ProofError> {
-    read_g1_util::<H>(&data[*offset..*offset + 64], true)
+// Not derived, to not require `H: Clone`: every field is `Copy`.
+impl<H: CurveHooks> Clone for Proof<H> {
+    fn clone(&self) -> Self {
+        Self { ..*self }
+    }
+}
+
+pub(crate) fn read_proof_g1<H: CurveHooks>(
+    data: &[u8],
+    offset: &mut usize,
+    mode: DecodingMode,
+) -> Result<G1<H>, ProofError> {
+    let bytes = data
+        .get(*offset..*offset + 64)
+        .ok_or(ProofError::BufferTooShort {
+            expected_size: *offset + 64,
+            actual_size: data.len(),
+        })?;
+    read_g1_util::<H>(bytes, true, mode)
         .map_err(|e| match e {
             GroupError::NotOnCurve => ProofError::PointNotOnCurve,
+            GroupError::NotMember => ProofError::NotMember,
+            GroupError::PointAtInfinity => ProofError::PointAtInfinity,
             GroupError::InvalidSliceLength {
                 expected_length,
                 actual_length,
@@ -131,8 +168,18 @@ fn read_proof_g1<H: CurveHooks>(data: &[u8], offset: &mut usize) -> Result<G1<H>
         })
 }
 
-fn read_proof_fq(data: &[u8], offset: &mut usize) -> Result<Fq, ProofError> {
-    read_fq_util(&data[*offset..*offset + 32])
+pub(crate) fn read_proof_fq(
+    data: &[u8],
+    offset: &mut usize,
+    mode: DecodingMode,
+) -> Result<Fq, ProofError> {
+    let bytes = data
+        .get(*offset..*offset + 32)
+        .ok_or(ProofError::BufferTooShort {
+            expected_size: *offset + 32,
+            actual_size: data.len(),
+        })?;
+    read_fq_util(bytes, mode)
         .map_err(|e| match e {
             FieldError::NotMember => ProofError::NotMember,
             FieldError::InvalidSliceLength {
@@ -148,10 +195,74 @@ fn read_proof_fq(data: &[u8], offset: &mut usize) -> Result<Fq, ProofError> {
         })
 }
 
+// Evaluations are stored as `Fq` values, but are `Fr` ones: in strict mode, they must be
+// lower than the `Fr` modulus too.
+fn read_proof_eval(
+    data: &[u8],
+    offset: &mut usize,
+    field: &'static str,
+    mode: DecodingMode,
+) -> Result<Fq, ProofError> {
+    let eval = read_proof_fq(data, offset, mode)?;
+    match mode {
+        DecodingMode::Strict if Fr::from_bigint(eval.into_bigint()).is_none() => {
+            Err(ProofError::EvaluationNotMember { field })
+        }
+        _ => Ok(eval),
+    }
+}
+
+impl<H: CurveHooks> Proof<H> {
+    /// Parses the output of `bb prove`: the public inputs of `vk`, followed by the proof.
+    pub fn from_bb_output(
+        data: &[u8],
+        vk: &VerificationKey<H>,
+    ) -> Result<(Vec<PublicInput>, Self), ProofError> {
+        let pubs_size = (vk.num_public_inputs as usize).saturating_mul(PUBS_SIZE);
+        let expected_size = pubs_size.saturating_add(PROOF_SIZE);
+        if data.len() < expected_size {
+            return Err(ProofError::BufferTooShort {
+                expected_size,
+                actual_size: data.len(),
+            });
+        }
+        if data.len() != expected_size {
+            return Err(ProofError::IncorrectBufferSize {
+                expected_size,
+                actual_size: data.len(),
+            });
+        }
+
+        let (pubs, proof) = data.split_at(pubs_size);
+        let pubs = pubs
+            .chunks_exact(PUBS_SIZE)
+            .enumerate()
+            .map(|(index, pi)| {
+                let pi: PublicInput = pi.try_into().unwrap();
+                match Fr::from_bigint(pi.into_u256()) {
+                    Some(_) => Ok(pi),
+                    None => Err(ProofError::PublicInputNotMember { index }),
+                }
+            })
+            .collect::<Result<Vec<PublicInput>, _>>()?;
+
+        Ok((pubs, Self::try_from(proof)?))
+    }
+}
+
 impl<H: CurveHooks> TryFrom<&[u8]> for Proof<H> {
     type Error = ProofError;
 
     fn try_from(proof: &[u8]) -> Result<Self, ProofError> {
+        Self::try_from_bytes_with_mode(proof, DecodingMode::Strict)
+    }
+}
+
+impl<H: CurveHooks> Proof<H> {
+    /// Parses `proof`, rejecting non canonical encodings unless `mode` is
+    /// [`DecodingMode::Lenient`]. In strict mode, evaluations must also be lower than the
+    /// `Fr` modulus.
+    pub fn try_from_bytes_with_mode(proof: &[u8], mode: DecodingMode) -> Result<Self, ProofError> {
         if proof.len() != PROOF_SIZE {
             return Err(ProofError::IncorrectBufferSize {
                 expected_size: PROOF_SIZE,
@@ -161,72 +272,72 @@ impl<H: CurveHooks> TryFrom<&[u8]> for Proof<H> {
 
         let mut offset = 0;
 
-        let w1 = read_proof_g1::<H>(proof, &mut offset)?;
-        let w2 = read_proof_g1::<H>(proof, &mut offset)?;
-        let w3 = read_proof_g1::<H>(proof, &mut offset)?;
-        let w4 = read_proof_g1::<H>(proof, &mut offset)?;
-
-        let s = read_proof_g1::<H>(proof, &mut offset)?;
-        let z = read_proof_g1::<H>(proof, &mut offset)?;
-        let z_lookup = read_proof_g1::<H>(proof, &mut offset)?;
-
-        let t1 = read_proof_g1::<H>(proof, &mut offset)?;
-        let t2 = read_proof_g1::<H>(proof, &mut offset)?;
-        let t3 = read_proof_g1::<H>(proof, &mut offset)?;
-        let t4 = read_proof_g1::<H>(proof, &mut offset)?;
-
-        let w1_eval = read_proof_fq(proof, &mut offset)?;
-        let w2_eval = read_proof_fq(proof, &mut offset)?;
-        let w3_eval = read_proof_fq(proof, &mut offset)?;
-        let w4_eval = read_proof_fq(proof, &mut offset)?;
-
-        let s_eval = read_proof_fq(proof, &mut offset)?;
-        let z_eval = read_proof_fq(proof, &mut offset)?;
-        let z_lookup_eval = read_proof_fq(proof, &mut offset)?;
-
-        let q1_eval = read_proof_fq(proof, &mut offset)?;
-        let q2_eval = read_proof_fq(proof, &mut offset)?;
-        let q3_eval = read_proof_fq(proof, &mut offset)?;
-        let q4_eval = read_proof_fq(proof, &mut offset)?;
-        let qm_eval = read_proof_fq(proof, &mut offset)?;
-        let qc_eval = read_proof_fq(proof, &mut offset)?;
-        let q_arith_eval = read_proof_fq(proof, &mut offset)?;
-        let q_sort_eval = read_proof_fq(proof, &mut offset)?;
-        let q_elliptic_eval = read_proof_fq(proof, &mut offset)?;
-        let q_aux_eval = read_proof_fq(proof, &mut offset)?;
-
-        let sigma1_eval = read_proof_fq(proof, &mut offset)?;
-        let sigma2_eval = read_proof_fq(proof, &mut offset)?;
-        let sigma3_eval = read_proof_fq(proof, &mut offset)?;
-        let sigma4_eval = read_proof_fq(proof, &mut offset)?;
-
-        let table1_eval = read_proof_fq(proof, &mut offset)?;
-        let table2_eval = read_proof_fq(proof, &mut offset)?;
-        let table3_eval = read_proof_fq(proof, &mut offset)?;
-        let table4_eval = read_proof_fq(proof, &mut offset)?;
-        let table_type_eval = read_proof_fq(proof, &mut offset)?;
-
-        let id1_eval = read_proof_fq(proof, &mut offset)?;
-        let id2_eval = read_proof_fq(proof, &mut offset)?;
-        let id3_eval = read_proof_fq(proof, &mut offset)?;
-        let id4_eval = read_proof_fq(proof, &mut offset)?;
-
-        let w1_omega_eval = read_proof_fq(proof, &mut offset)?;
-        let w2_omega_eval = read_proof_fq(proof, &mut offset)?;
-        let w3_omega_eval = read_proof_fq(proof, &mut offset)?;
-        let w4_omega_eval = read_proof_fq(proof, &mut offset)?;
-
-        let s_omega_eval = read_proof_fq(proof, &mut offset)?;
-        let z_omega_eval = read_proof_fq(proof, &mut offset)?;
-        let z_lookup_omega_eval = read_proof_fq(proof, &mut offset)?;
-
-        let table1_omega_eval = read_proof_fq(proof, &mut offset)?;
-        let table2_omega_eval = read_proof_fq(proof, &mut offset)?;
-        let table3_omega_eval = read_proof_fq(proof, &mut offset)?;
-        let table4_omega_eval = read_proof_fq(proof, &mut offset)?;
-
-        let pi_z = read_proof_g1::<H>(proof, &mut offset)?;
-        let pi_z_omega = read_proof_g1::<H>(proof, &mut offset)?;
+        let w1 = read_proof_g1::<H>(proof, &mut offset, mode)?;
+        let w2 = read_proof_g1::<H>(proof, &mut offset, mode)?;
+        let w3 = read_proof_g1::<H>(proof, &mut offset, mode)?;
+        let w4 = read_proof_g1::<H>(proof, &mut offset, mode)?;
+
+        let s = read_proof_g1::<H>(proof, &mut offset, mode)?;
+        let z = read_proof_g1::<H>(proof, &mut offset, mode)?;
+        let z_lookup = read_proof_g1::<H>(proof, &mut offset, mode)?;
+
+        let t1 = read_proof_g1::<H>(proof, &mut offset, mode)?;
+        let t2 = read_proof_g1::<H>(proof, &mut offset, mode)?;
+        let t3 = read_proof_g1::<H>(proof, &mut offset, mode)?;
+        let t4 = read_proof_g1::<H>(proof, &mut offset, mode)?;
+
+        let w1_eval = read_proof_eval(proof, &mut offset, "w1_eval", mode)?;
+        let w2_eval = read_proof_eval(proof, &mut offset, "w2_eval", mode)?;
+        let w3_eval = read_proof_eval(proof, &mut offset, "w3_eval", mode)?;
+        let w4_eval = read_proof_eval(proof, &mut offset, "w4_eval", mode)?;
+
+        let s_eval = read_proof_eval(proof, &mut offset, "s_eval", mode)?;
+        let z_eval = read_proof_eval(proof, &mut offset, "z_eval", mode)?;
+        let z_lookup_eval = read_proof_eval(proof, &mut offset, "z_lookup_eval", mode)?;
+
+        let q1_eval = read_proof_eval(proof, &mut offset, "q1_eval", mode)?;
+        let q2_eval = read_proof_eval(proof, &mut offset, "q2_eval", mode)?;
+        let q3_eval = read_proof_eval(proof, &mut offset, "q3_eval", mode)?;
+        let q4_eval = read_proof_eval(proof, &mut offset, "q4_eval", mode)?;
+        let qm_eval = read_proof_eval(proof, &mut offset, "qm_eval", mode)?;
+        let qc_eval = read_proof_eval(proof, &mut offset, "qc_eval", mode)?;
+        let q_arith_eval = read_proof_eval(proof, &mut offset, "q_arith_eval", mode)?;
+        let q_sort_eval = read_proof_eval(proof, &mut offset, "q_sort_eval", mode)?;
+        let q_elliptic_eval = read_proof_eval(proof, &mut offset, "q_elliptic_eval", mode)?;
+        let q_aux_eval = read_proof_eval(proof, &mut offset, "q_aux_eval", mode)?;
+
+        let sigma1_eval = read_proof_eval(proof, &mut offset, "sigma1_eval", mode)?;
+        let sigma2_eval = read_proof_eval(proof, &mut offset, "sigma2_eval", mode)?;
+        let sigma3_eval = read_proof_eval(proof, &mut offset, "sigma3_eval", mode)?;
+        let sigma4_eval = read_proof_eval(proof, &mut offset, "sigma4_eval", mode)?;
+
+        let table1_eval = read_proof_eval(proof, &mut offset, "table1_eval", mode)?;
+        let table2_eval = read_proof_eval(proof, &mut offset, "table2_eval", mode)?;
+        let table3_eval = read_proof_eval(proof, &mut offset, "table3_eval", mode)?;
+        let table4_eval = read_proof_eval(proof, &mut offset, "table4_eval", mode)?;
+        let table_type_eval = read_proof_eval(proof, &mut offset, "table_type_eval", mode)?;
+
+        let id1_eval = read_proof_eval(proof, &mut offset, "id1_eval", mode)?;
+        let id2_eval = read_proof_eval(proof, &mut offset, "id2_eval", mode)?;
+        let id3_eval = read_proof_eval(proof, &mut offset, "id3_eval", mode)?;
+        let id4_eval = read_proof_eval(proof, &mut offset, "id4_eval", mode)?;
+
+        let w1_omega_eval = read_proof_eval(proof, &mut offset, "w1_omega_eval", mode)?;
+        let w2_omega_eval = read_proof_eval(proof, &mut offset, "w2_omega_eval", mode)?;
+        let w3_omega_eval = read_proof_eval(proof, &mut offset, "w3_omega_eval", mode)?;
+        let w4_omega_eval = read_proof_eval(proof, &mut offset, "w4_omega_eval", mode)?;
+
+        let s_omega_eval = read_proof_eval(proof, &mut offset, "s_omega_eval", mode)?;
+        let z_omega_eval = read_proof_eval(proof, &mut offset, "z_omega_eval", mode)?;
+        let z_lookup_omega_eval = read_proof_eval(proof, &mut offset, "z_lookup_omega_eval", mode)?;
+
+        let table1_omega_eval = read_proof_eval(proof, &mut offset, "table1_omega_eval", mode)?;
+        let table2_omega_eval = read_proof_eval(proof, &mut offset, "table2_omega_eval", mode)?;
+        let table3_omega_eval = read_proof_eval(proof, &mut offset, "table3_omega_eval", mode)?;
+        let table4_omega_eval = read_proof_eval(proof, &mut offset, "table4_omega_eval", mode)?;
+
+        let pi_z = read_proof_g1::<H>(proof, &mut offset, mode)?;
+        let pi_z_omega = read_proof_g1::<H>(proof, &mut offset, mode)?;
 
         Ok(Proof::<H> {
             w1,
@@ -287,9 +398,152 @@ impl<H: CurveHooks> TryFrom<&[u8]> for Proof<H> {
     }
 }
 
+/// A [`Proof`] with its 41 evaluations converted to [`Fr`] once, before they feed the
+/// widgets. The proof itself is kept, borrowed or owned, for the transcript, that hashes
+/// the evaluations as they were sent.
+#[derive(Debug, PartialEq, Eq)]
+pub struct PreparedProof<'a, H: CurveHooks> {
+    pub proof: Cow<'a, Proof<H>>,
+    pub w1_eval: Fr,
+    pub w2_eval: Fr,
+    pub w3_eval: Fr,
+    pub w4_eval: Fr,
+    pub s_eval: Fr,
+    pub z_eval: Fr,
+    pub z_lookup_eval: Fr,
+    pub q1_eval: Fr,
+    pub q2_eval: Fr,
+    pub q3_eval: Fr,
+    pub q4_eval: Fr,
+    pub qm_eval: Fr,
+    pub qc_eval: Fr,
+    pub q_arith_eval: Fr,
+    pub q_sort_eval: Fr,
+    pub q_elliptic_eval: Fr,
+    pub q_aux_eval: Fr,
+    pub sigma1_eval: Fr,
+    pub sigma2_eval: Fr,
+    pub sigma3_eval: Fr,
+    pub sigma4_eval: Fr,
+    pub table1_eval: Fr,
+    pub table2_eval: Fr,
+    pub table3_eval: Fr,
+    pub table4_eval: Fr,
+    pub table_type_eval: Fr,
+    pub id1_eval: Fr,
+    pub id2_eval: Fr,
+    pub id3_eval: Fr,
+    pub id4_eval: Fr,
+    pub w1_omega_eval: Fr,
+    pub w2_omega_eval: Fr,
+    pub w3_omega_eval: Fr,
+    pub w4_omega_eval: Fr,
+    pub s_omega_eval: Fr,
+    pub z_omega_eval: Fr,
+    pub z_lookup_omega_eval: Fr,
+    pub table1_omega_eval: Fr,
+    pub table2_omega_eval: Fr,
+    pub table3_omega_eval: Fr,
+    pub table4_omega_eval: Fr,
+}
+
+impl<'a, H: CurveHooks> TryFrom<&'a Proof<H>> for PreparedProof<'a, H> {
+    type Error = ProofError;
+
+    fn try_from(proof: &'a Proof<H>) -> Result<Self, ProofError> {
+        Self::try_from_proof_with_mode(proof, DecodingMode::Strict)
+    }
+}
+
+/// Same as the borrowing conversion, for a prepared proof that outlives its source.
+impl<H: CurveHooks> TryFrom<Proof<H>> for PreparedProof<'static, H> {
+    type Error = ProofError;
+
+    fn try_from(proof: Proof<H>) -> Result<Self, ProofError> {
+        Self::prepare(Cow::Owned(proof), DecodingMode::Strict)
+    }
+}
+
+impl<'a, H: CurveHooks> PreparedProof<'a, H> {
+    /// Converts the evaluations of `proof`. An evaluation that isn't lower than the `Fr`
+    /// modulus is rejected, unless `mode` is [`DecodingMode::Lenient`]: it is then reduced,
+    /// as the Solidity verifier does.
+    pub fn try_from_proof_with_mode(
+        proof: &'a Proof<H>,
+        mode: DecodingMode,
+    ) -> Result<Self, ProofError> {
+        Self::prepare(Cow::Borrowed(proof), mode)
+    }
+
+    fn prepare(proof: Cow<'a, Proof<H>>, mode: DecodingMode) -> Result<Self, ProofError> {
+        Ok(Self {
+            w1_eval: prepare_eval("w1_eval", &proof.w1_eval, mode)?,
+            w2_eval: prepare_eval("w2_eval", &proof.w2_eval, mode)?,
+            w3_eval: prepare_eval("w3_eval", &proof.w3_eval, mode)?,
+            w4_eval: prepare_eval("w4_eval", &proof.w4_eval, mode)?,
+            s_eval: prepare_eval("s_eval", &proof.s_eval, mode)?,
+            z_eval: prepare_eval("z_eval", &proof.z_eval, mode)?,
+            z_lookup_eval: prepare_eval("z_lookup_eval", &proof.z_lookup_eval, mode)?,
+            q1_eval: prepare_eval("q1_eval", &proof.q1_eval, mode)?,
+            q2_eval: prepare_eval("q2_eval", &proof.q2_eval, mode)?,
+            q3_eval: prepare_eval("q3_eval", &proof.q3_eval, mode)?,
+            q4_eval: prepare_eval("q4_eval", &proof.q4_eval, mode)?,
+            qm_eval: prepare_eval("qm_eval", &proof.qm_eval, mode)?,
+            qc_eval: prepare_eval("qc_eval", &proof.qc_eval, mode)?,
+            q_arith_eval: prepare_eval("q_arith_eval", &proof.q_arith_eval, mode)?,
+            q_sort_eval: prepare_eval("q_sort_eval", &proof.q_sort_eval, mode)?,
+            q_elliptic_eval: prepare_eval("q_elliptic_eval", &proof.q_elliptic_eval, mode)?,
+            q_aux_eval: prepare_eval("q_aux_eval", &proof.q_aux_eval, mode)?,
+            sigma1_eval: prepare_eval("sigma1_eval", &proof.sigma1_eval, mode)?,
+            sigma2_eval: prepare_eval("sigma2_eval", &proof.sigma2_eval, mode)?,
+            sigma3_eval: prepare_eval("sigma3_eval", &proof.sigma3_eval, mode)?,
+            sigma4_eval: prepare_eval("sigma4_eval", &proof.sigma4_eval, mode)?,
+            table1_eval: prepare_eval("table1_eval", &proof.table1_eval, mode)?,
+            table2_eval: prepare_eval("table2_eval", &proof.table2_eval, mode)?,
+            table3_eval: prepare_eval("table3_eval", &proof.table3_eval, mode)?,
+            table4_eval: prepare_eval("table4_eval", &proof.table4_eval, mode)?,
+            table_type_eval: prepare_eval("table_type_eval", &proof.table_type_eval, mode)?,
+            id1_eval: prepare_eval("id1_eval", &proof.id1_eval, mode)?,
+            id2_eval: prepare_eval("id2_eval", &proof.id2_eval, mode)?,
+            id3_eval: prepare_eval("id3_eval", &proof.id3_eval, mode)?,
+            id4_eval: prepare_eval("id4_eval", &proof.id4_eval, mode)?,
+            w1_omega_eval: prepare_eval("w1_omega_eval", &proof.w1_omega_eval, mode)?,
+            w2_omega_eval: prepare_eval("w2_omega_eval", &proof.w2_omega_eval, mode)?,
+            w3_omega_eval: prepare_eval("w3_omega_eval", &proof.w3_omega_eval, mode)?,
+            w4_omega_eval: prepare_eval("w4_omega_eval", &proof.w4_omega_eval, mode)?,
+            s_omega_eval: prepare_eval("s_omega_eval", &proof.s_omega_eval, mode)?,
+            z_omega_eval: prepare_eval("z_omega_eval", &proof.z_omega_eval, mode)?,
+            z_lookup_omega_eval: prepare_eval(
+                "z_lookup_omega_eval",
+                &proof.z_lookup_omega_eval,
+                mode,
+            )?,
+            table1_omega_eval: prepare_eval("table1_omega_eval", &proof.table1_omega_eval, mode)?,
+            table2_omega_eval: prepare_eval("table2_omega_eval", &proof.table2_omega_eval, mode)?,
+            table3_omega_eval: prepare_eval("table3_omega_eval", &proof.table3_omega_eval, mode)?,
+            table4_omega_eval: prepare_eval("table4_omega_eval", &proof.table4_omega_eval, mode)?,
+            // Last, once every evaluation has been read from it
+            proof,
+        })
+    }
+}
+
+fn prepare_eval(field: &'static str, eval: &Fq, mode: DecodingMode) -> Result<Fr, ProofError> {
+    match (Fr::from_bigint(eval.into_bigint()), mode) {
+        (Some(value), _) => Ok(value),
+        (None, DecodingMode::Lenient) => Ok(Fr::from_be_bytes_mod_order(&eval.into_bytes())),
+        (None, DecodingMode::Strict) => Err(ProofError::EvaluationNotMember { field }),
+    }
+}
+
 #[cfg(test)]
 mod should {
     use super::*;
+    use crate::{
+        fixtures::{bb_output, bb_vk},
+        utils::{IntoBytes, IntoFr},
+    };
+    use ark_ff::{AdditiveGroup, BigInteger};
     use rstest::{fixture, rstest};
 
     #[fixture]
@@ -372,6 +626,73 @@ mod should {
         assert!(Proof::<()>::try_from(&valid_proof[..]).is_ok());
     }
 
+    #[rstest]
+    fn successfully_parse_a_bb_output(bb_vk: VerificationKey<()>, bb_output: &[u8]) {
+        let (pubs, _proof) = Proof::<()>::from_bb_output(bb_output, &bb_vk).unwrap();
+
+        let mut expected_pub = [0u8; PUBS_SIZE];
+        expected_pub[31] = 2;
+        assert_eq!(pubs, [expected_pub]);
+    }
+
+    // Adds the Fq modulus to the 32 bytes big-endian value in `data`.
+    fn add_fq_modulus(data: &mut [u8]) {
+        let mut value = <[u8; 32]>::try_from(&*data).unwrap().into_u256();
+        assert!(!value.add_with_carry(&Fq::MODULUS));
+        data.copy_from_slice(&value.into_bytes());
+    }
+
+    #[rstest]
+    fn parse_a_non_canonical_proof_in_lenient_mode(valid_proof: [u8; PROOF_SIZE]) {
+        let mut non_canonical_proof = valid_proof;
+        add_fq_modulus(&mut non_canonical_proof[..32]);
+        add_fq_modulus(&mut non_canonical_proof[11 * 64..11 * 64 + 32]);
+
+        assert_eq!(
+            Proof::<()>::try_from_bytes_with_mode(&non_canonical_proof, DecodingMode::Lenient),
+            Proof::<()>::try_from(&valid_proof[..])
+        );
+    }
+
+    #[rstest]
+    fn prepare_the_evaluations_of_a_proof(valid_proof: [u8; PROOF_SIZE]) {
+        let proof = Proof::<()>::try_from(&valid_proof[..]).unwrap();
+        let prepared = PreparedProof::try_from(&proof).unwrap();
+
+        assert_eq!(prepared.w1_eval, proof.w1_eval.into_fr());
+        assert_eq!(
+            prepared.table4_omega_eval,
+            proof.table4_omega_eval.into_fr()
+        );
+    }
+
+    #[rstest]
+    fn prepare_an_owned_proof_as_a_borrowed_one(valid_proof: [u8; PROOF_SIZE]) {
+        let proof = Proof::<()>::try_from(&valid_proof[..]).unwrap();
+
+        assert_eq!(
+            PreparedProof::try_from(proof.clone()).unwrap(),
+            PreparedProof::try_from(&proof).unwrap()
+        );
+    }
+
+    // Overwrites the first evaluation of `proof` with the Fr modulus, which is still
+    // lower than the Fq one.
+    fn with_an_evaluation_out_of_fr(proof: [u8; PROOF_SIZE]) -> Proof<()> {
+        let mut proof = proof;
+        proof[11 * 64..11 * 64 + 32].copy_from_slice(&Fr::MODULUS.into_bytes());
+        Proof::<()>::try_from_bytes_with_mode(&proof, DecodingMode::Lenient).unwrap()
+    }
+
+    #[rstest]
+    fn reduce_an_evaluation_out_of_fr_in_lenient_mode(valid_proof: [u8; PROOF_SIZE]) {
+        let proof = with_an_evaluation_out_of_fr(valid_proof);
+        let prepared =
+            PreparedProof::try_from_proof_with_mode(&proof, DecodingMode::Lenient).unwrap();
+
+        assert_eq!(prepared.w1_eval, Fr::ZERO);
+    }
+
     mod reject {
         use super::*;
 
@@ -387,6 +708,45 @@ mod should {
             );
         }
 
+        #[rstest]
+        fn a_bb_output_from_a_short_buffer(bb_vk: VerificationKey<()>, bb_output: &[u8]) {
+            assert_eq!(
+                Proof::<()>::from_bb_output(&bb_output[..PROOF_SIZE], &bb_vk).unwrap_err(),
+                ProofError::BufferTooShort {
+                    expected_size: PUBS_SIZE + PROOF_SIZE,
+                    actual_size: PROOF_SIZE
+                }
+            );
+        }
+
+        #[rstest]
+        fn a_bb_output_with_trailing_bytes(bb_vk: VerificationKey<()>, bb_output: &[u8]) {
+            let mut invalid_output = bb_output.to_vec();
+            invalid_output.push(0);
+
+            assert_eq!(
+                Proof::<()>::from_bb_output(&invalid_output, &bb_vk).unwrap_err(),
+                ProofError::IncorrectBufferSize {
+                    expected_size: PUBS_SIZE + PROOF_SIZE,
+                    actual_size: PUBS_SIZE + PROOF_SIZE + 1
+                }
+            );
+        }
+
+        #[rstest]
+        fn a_bb_output_with_a_public_input_out_of_range(
+            bb_vk: VerificationKey<()>,
+            bb_output: &[u8],
+        ) {
+            let mut invalid_output = bb_output.to_vec();
+            invalid_output[..PUBS_SIZE].fill(0xff);
+
+            assert_eq!(
+                Proof::<()>::from_bb_output(&invalid_output, &bb_vk).unwrap_err(),
+                ProofError::PublicInputNotMember { index: 0 }
+            );
+        }
+
         #[rstest]
         fn a_proof_with_a_point_not_on_curve(valid_proof: [u8; PROOF_SIZE]) {
             let mut invalid_proof = [0u8; PROOF_SIZE];
@@ -398,5 +758,65 @@ mod should {
                 ProofError::PointNotOnCurve
             );
         }
+
+        #[rstest]
+        fn a_proof_with_a_non_canonical_coordinate(valid_proof: [u8; PROOF_SIZE]) {
+            let mut invalid_proof = valid_proof;
+            add_fq_modulus(&mut invalid_proof[..32]);
+
+            assert_eq!(
+                Proof::<()>::try_from(&invalid_proof[..]).unwrap_err(),
+                ProofError::NotMember
+            );
+        }
+
+        #[rstest]
+        fn a_proof_with_a_non_canonical_evaluation(valid_proof: [u8; PROOF_SIZE]) {
+            let mut invalid_proof = valid_proof;
+            add_fq_modulus(&mut invalid_proof[11 * 64..11 * 64 + 32]);
+
+            assert_eq!(
+                Proof::<()>::try_from(&invalid_proof[..]).unwrap_err(),
+                ProofError::NotMember
+            );
+        }
+
+        #[rstest]
+        fn a_proof_with_an_evaluation_out_of_fr(valid_proof: [u8; PROOF_SIZE]) {
+            let mut invalid_proof = valid_proof;
+            invalid_proof[12 * 64..12 * 64 + 32].copy_from_slice(&Fr::MODULUS.into_bytes());
+
+            assert_eq!(
+                Proof::<()>::try_from(&invalid_proof[..]).unwrap_err(),
+                ProofError::EvaluationNotMember { field: "w3_eval" }
+            );
+        }
+
+        #[rstest]
+        fn a_hand_built_proof_with_an_evaluation_out_of_fr(valid_proof: [u8; PROOF_SIZE]) {
+            let proof = with_an_evaluation_out_of_fr(valid_proof);
+
+            assert_eq!(
+                PreparedProof::try_from(&proof).unwrap_err(),
+                ProofError::EvaluationNotMember { field: "w1_eval" }
+            );
+        }
+
+        #[rstest]
+        #[case::strict(DecodingMode::Strict, ProofError::PointAtInfinity)]
+        #[case::lenient(DecodingMode::Lenient, ProofError::PointNotOnCurve)]
+        fn a_proof_with_a_point_at_infinity(
+            valid_proof: [u8; PROOF_SIZE],
+            #[case] mode: DecodingMode,
+            #[case] expected: ProofError,
+        ) {
+            let mut invalid_proof = valid_proof;
+            invalid_proof[..64].fill(0);
+
+            assert_eq!(
+                Proof::<()>::try_from_bytes_with_mode(&invalid_proof, mode).unwrap_err(),
+                expected
+            );
+        }
     }
 }
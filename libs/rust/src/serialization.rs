@@ -0,0 +1,595 @@
+// Copyright 2024 Horizen Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0 or MIT
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Serde helpers. Human readable formats get field elements and points as 0x-prefixed
+//! big-endian hex strings, binary formats get the same bytes as they are.
+
+use alloc::{format, vec::Vec};
+use core::{fmt, marker::PhantomData, ops::Deref};
+
+use serde::{
+    de::{self, SeqAccess, Visitor},
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+
+use ark_bn254_ext::CurveHooks;
+
+use crate::{
+    key::{PreparedVerificationKey, VerificationKey},
+    proof::Proof,
+    FixedBaseTables, Fq, Public, PublicInput, G1, PUBS_SIZE,
+};
+
+/// A list of public inputs that serializes as an array of 0x-hex strings.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PublicInputs(pub Vec<PublicInput>);
+
+impl Deref for PublicInputs {
+    type Target = Public;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl Serialize for PublicInputs {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.0.iter().map(|pi| Bytes(*pi)))
+    }
+}
+
+impl<'de> Deserialize<'de> for PublicInputs {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let pubs = Vec::<Bytes<PUBS_SIZE>>::deserialize(deserializer)?;
+        Ok(PublicInputs(pubs.into_iter().map(|pi| pi.0).collect()))
+    }
+}
+
+struct Bytes<const N: usize>([u8; N]);
+
+impl<const N: usize> Serialize for Bytes<N> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serializer.serialize_str(&format!("0x{}", hex::encode(self.0)))
+        } else {
+            serializer.serialize_bytes(&self.0)
+        }
+    }
+}
+
+impl<'de, const N: usize> Deserialize<'de> for Bytes<N> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_str(BytesVisitor(PhantomData))
+        } else {
+            deserializer.deserialize_bytes(BytesVisitor(PhantomData))
+        }
+    }
+}
+
+struct BytesVisitor<const N: usize>(PhantomData<[u8; N]>);
+
+impl<'de, const N: usize> Visitor<'de> for BytesVisitor<N> {
+    type Value = Bytes<N>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            formatter,
+            "{N} bytes or a 0x-prefixed hex string of {N} bytes"
+        )
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<Self::Value, E> {
+        let value = value
+            .strip_prefix("0x")
+            .ok_or_else(|| E::invalid_value(de::Unexpected::Str(value), &self))?;
+        let mut out = [0u8; N];
+        hex::decode_to_slice(value, &mut out).map_err(E::custom)?;
+        Ok(Bytes(out))
+    }
+
+    fn visit_bytes<E: de::Error>(self, value: &[u8]) -> Result<Self::Value, E> {
+        value
+            .try_into()
+            .map(Bytes)
+            .map_err(|_| E::invalid_length(value.len(), &self))
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut out = [0u8; N];
+        for (i, byte) in out.iter_mut().enumerate() {
+            *byte = seq
+                .next_element()?
+                .ok_or_else(|| de::Error::invalid_length(i, &self))?;
+        }
+        if seq.next_element::<u8>()?.is_some() {
+            return Err(de::Error::invalid_length(N + 1, &self));
+        }
+        Ok(Bytes(out))
+    }
+}
+
+/// `#[serde(with = "...")]` helpers for `Fq` elements. Non canonical values are rejected.
+pub mod fq {
+    use super::*;
+    use crate::{
+        utils::{IntoBytes, IntoU256},
+        Fq,
+    };
+    use ark_ff::PrimeField;
+
+    pub fn serialize<S: Serializer>(value: &Fq, serializer: S) -> Result<S::Ok, S::Error> {
+        Bytes(value.into_bytes()).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Fq, D::Error> {
+        let Bytes(bytes) = Bytes::<32>::deserialize(deserializer)?;
+        Fq::from_bigint(bytes.into_u256())
+            .ok_or_else(|| de::Error::custom("value is not a member of Fq"))
+    }
+}
+
+/// `#[serde(with = "...")]` helpers for `Fr` elements. Non canonical values are rejected.
+pub mod fr {
+    use super::*;
+    use crate::{
+        utils::{IntoBytes, IntoU256},
+        Fr,
+    };
+    use ark_ff::PrimeField;
+
+    pub fn serialize<S: Serializer>(value: &Fr, serializer: S) -> Result<S::Ok, S::Error> {
+        Bytes(value.into_bytes()).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Fr, D::Error> {
+        let Bytes(bytes) = Bytes::<32>::deserialize(deserializer)?;
+        Fr::from_bigint(bytes.into_u256())
+            .ok_or_else(|| de::Error::custom("value is not a member of Fr"))
+    }
+}
+
+/// `#[serde(with = "...")]` helpers for G1 points, encoded as `x || y`. Points not on the
+/// curve are rejected.
+pub mod g1 {
+    use super::*;
+    use crate::{
+        utils::{IntoBytes, IntoU256},
+        Fq, G1,
+    };
+    use ark_bn254_ext::CurveHooks;
+    use ark_ff::PrimeField;
+
+    pub fn serialize<S: Serializer, H: CurveHooks>(
+        point: &G1<H>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        let mut bytes = [0u8; 64];
+        bytes[..32].copy_from_slice(&point.x.into_bytes());
+        bytes[32..].copy_from_slice(&point.y.into_bytes());
+        Bytes(bytes).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>, H: CurveHooks>(
+        deserializer: D,
+    ) -> Result<G1<H>, D::Error> {
+        let Bytes(bytes) = Bytes::<64>::deserialize(deserializer)?;
+        let coordinate = |data: &[u8]| {
+            let data: [u8; 32] = data.try_into().unwrap();
+            Fq::from_bigint(data.into_u256())
+                .ok_or_else(|| <D::Error as de::Error>::custom("coordinate is not a member of Fq"))
+        };
+
+        let point = G1::<H>::new_unchecked(coordinate(&bytes[..32])?, coordinate(&bytes[32..])?);
+        if !point.is_on_curve() {
+            return Err(de::Error::custom("point is not on curve"));
+        }
+
+        Ok(point)
+    }
+}
+
+/// Values that go through a wrapper type: points and field elements are foreign types.
+trait SerdeAs: Sized {
+    type Repr: Serialize + for<'de> Deserialize<'de>;
+
+    fn to_repr(&self) -> Self::Repr;
+    fn from_repr(repr: Self::Repr) -> Self;
+}
+
+macro_rules! serde_as_itself {
+    ($($ty:ty),*) => {$(
+        impl SerdeAs for $ty {
+            type Repr = $ty;
+
+            fn to_repr(&self) -> Self::Repr {
+                *self
+            }
+
+            fn from_repr(repr: Self::Repr) -> Self {
+                repr
+            }
+        }
+    )*};
+}
+
+serde_as_itself!(u32, bool);
+
+#[derive(Serialize, Deserialize)]
+#[serde(transparent)]
+struct FqRepr(#[serde(with = "fq")] Fq);
+
+impl SerdeAs for Fq {
+    type Repr = FqRepr;
+
+    fn to_repr(&self) -> Self::Repr {
+        FqRepr(*self)
+    }
+
+    fn from_repr(repr: Self::Repr) -> Self {
+        repr.0
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(transparent, bound = "")]
+struct G1Repr<H: CurveHooks>(#[serde(with = "g1")] G1<H>);
+
+impl<H: CurveHooks> SerdeAs for G1<H> {
+    type Repr = G1Repr<H>;
+
+    fn to_repr(&self) -> Self::Repr {
+        G1Repr(*self)
+    }
+
+    fn from_repr(repr: Self::Repr) -> Self {
+        repr.0
+    }
+}
+
+// Declares `$mirror`, a copy of the struct `$ty` whose fields go through their `SerdeAs`
+// representation, and the conversions between them.
+macro_rules! serde_mirror {
+    ($ty:ident => $mirror:ident { $($(#[$attr:meta])* $field:ident: $field_ty:ty),* $(,)? }) => {
+        #[derive(Serialize, Deserialize)]
+        #[serde(bound = "")]
+        struct $mirror<H: CurveHooks> {
+            $($(#[$attr])* $field: <$field_ty as SerdeAs>::Repr,)*
+        }
+
+        impl<H: CurveHooks> From<&$ty<H>> for $mirror<H> {
+            fn from(value: &$ty<H>) -> Self {
+                $mirror {
+                    $($field: value.$field.to_repr(),)*
+                }
+            }
+        }
+
+        impl<H: CurveHooks> From<$mirror<H>> for $ty<H> {
+            fn from(mirror: $mirror<H>) -> Self {
+                $ty {
+                    $($field: SerdeAs::from_repr(mirror.$field),)*
+                }
+            }
+        }
+    };
+}
+
+serde_mirror!(VerificationKey => VerificationKeyMirror {
+    circuit_type: u32,
+    circuit_size: u32,
+    num_public_inputs: u32,
+    q_1: G1<H>,
+    q_2: G1<H>,
+    q_3: G1<H>,
+    q_4: G1<H>,
+    q_m: G1<H>,
+    q_c: G1<H>,
+    q_arithmetic: G1<H>,
+    q_aux: G1<H>,
+    q_elliptic: G1<H>,
+    q_sort: G1<H>,
+    sigma_1: G1<H>,
+    sigma_2: G1<H>,
+    sigma_3: G1<H>,
+    sigma_4: G1<H>,
+    table_1: G1<H>,
+    table_2: G1<H>,
+    table_3: G1<H>,
+    table_4: G1<H>,
+    table_type: G1<H>,
+    id_1: G1<H>,
+    id_2: G1<H>,
+    id_3: G1<H>,
+    id_4: G1<H>,
+    contains_recursive_proof: bool,
+    recursive_proof_indices: u32,
+    #[serde(default)]
+    is_recursive_circuit: bool,
+});
+
+serde_mirror!(Proof => ProofMirror {
+    w1: G1<H>,
+    w2: G1<H>,
+    w3: G1<H>,
+    w4: G1<H>,
+    s: G1<H>,
+    z: G1<H>,
+    z_lookup: G1<H>,
+    t1: G1<H>,
+    t2: G1<H>,
+    t3: G1<H>,
+    t4: G1<H>,
+    w1_eval: Fq,
+    w2_eval: Fq,
+    w3_eval: Fq,
+    w4_eval: Fq,
+    s_eval: Fq,
+    z_eval: Fq,
+    z_lookup_eval: Fq,
+    q1_eval: Fq,
+    q2_eval: Fq,
+    q3_eval: Fq,
+    q4_eval: Fq,
+    qm_eval: Fq,
+    qc_eval: Fq,
+    q_arith_eval: Fq,
+    q_sort_eval: Fq,
+    q_elliptic_eval: Fq,
+    q_aux_eval: Fq,
+    sigma1_eval: Fq,
+    sigma2_eval: Fq,
+    sigma3_eval: Fq,
+    sigma4_eval: Fq,
+    table1_eval: Fq,
+    table2_eval: Fq,
+    table3_eval: Fq,
+    table4_eval: Fq,
+    table_type_eval: Fq,
+    id1_eval: Fq,
+    id2_eval: Fq,
+    id3_eval: Fq,
+    id4_eval: Fq,
+    w1_omega_eval: Fq,
+    w2_omega_eval: Fq,
+    w3_omega_eval: Fq,
+    w4_omega_eval: Fq,
+    s_omega_eval: Fq,
+    z_omega_eval: Fq,
+    z_lookup_omega_eval: Fq,
+    table1_omega_eval: Fq,
+    table2_omega_eval: Fq,
+    table3_omega_eval: Fq,
+    table4_omega_eval: Fq,
+    pi_z: G1<H>,
+    pi_z_omega: G1<H>,
+});
+
+/// Points must be on the curve and coordinates canonical, and the key must pass
+/// [`VerificationKey::check`].
+impl<H: CurveHooks> Serialize for VerificationKey<H> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        VerificationKeyMirror::from(self).serialize(serializer)
+    }
+}
+
+impl<'de, H: CurveHooks> Deserialize<'de> for VerificationKey<H> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let vk = VerificationKey::from(VerificationKeyMirror::deserialize(deserializer)?);
+        vk.check().map_err(de::Error::custom)?;
+        Ok(vk)
+    }
+}
+
+/// Points must be on the curve and coordinates canonical.
+impl<H: CurveHooks> Serialize for Proof<H> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        ProofMirror::from(self).serialize(serializer)
+    }
+}
+
+impl<'de, H: CurveHooks> Deserialize<'de> for Proof<H> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        ProofMirror::deserialize(deserializer).map(Proof::from)
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(bound = "")]
+struct PreparedVerificationKeyMirror<H: CurveHooks> {
+    vk: VerificationKey<H>,
+    #[serde(default)]
+    fixed_base_window_bits: Option<u8>,
+}
+
+/// A prepared key goes as its [`VerificationKey`] and the window size of its fixed-base
+/// tables, if any: the domain values and the tables are computed again on deserialization.
+impl<H: CurveHooks> Serialize for PreparedVerificationKey<H> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        PreparedVerificationKeyMirror {
+            vk: VerificationKey::from(self),
+            fixed_base_window_bits: self.fixed_base_tables().map(FixedBaseTables::window_bits),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de, H: CurveHooks> Deserialize<'de> for PreparedVerificationKey<H> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let PreparedVerificationKeyMirror {
+            vk,
+            fixed_base_window_bits,
+        } = PreparedVerificationKeyMirror::deserialize(deserializer)?;
+        let vk = PreparedVerificationKey::try_from(&vk).map_err(de::Error::custom)?;
+        match fixed_base_window_bits {
+            Some(window_bits) => vk
+                .with_fixed_base_tables(window_bits)
+                .map_err(de::Error::custom),
+            None => Ok(vk),
+        }
+    }
+}
+
+#[cfg(test)]
+mod should {
+    use super::*;
+    use crate::fixtures::{bb_vk, BB_OUTPUT};
+    use rstest::{fixture, rstest};
+
+    #[fixture]
+    fn raw_proof() -> &'static [u8] {
+        &BB_OUTPUT[PUBS_SIZE..]
+    }
+
+    #[rstest]
+    fn serialize_deserialize_a_vk_as_json(bb_vk: VerificationKey<()>) {
+        let json = serde_json::to_value(&bb_vk).unwrap();
+
+        assert_eq!(json["circuit_size"], 16);
+        assert_eq!(json["id_1"].as_str().unwrap().len(), 2 + 128);
+        assert_eq!(
+            serde_json::from_value::<VerificationKey<()>>(json).unwrap(),
+            bb_vk
+        );
+    }
+
+    #[rstest]
+    fn serialize_deserialize_a_prepared_vk_as_json(bb_vk: VerificationKey<()>) {
+        let prepared_vk = PreparedVerificationKey::try_from(&bb_vk).unwrap();
+        let json = serde_json::to_string(&prepared_vk).unwrap();
+
+        assert_eq!(
+            serde_json::from_str::<PreparedVerificationKey<()>>(&json).unwrap(),
+            prepared_vk
+        );
+    }
+
+    #[rstest]
+    fn serialize_deserialize_a_prepared_vk_with_fixed_base_tables_as_json(
+        bb_vk: VerificationKey<()>,
+    ) {
+        let prepared_vk = PreparedVerificationKey::try_from(&bb_vk)
+            .unwrap()
+            .with_fixed_base_tables(2)
+            .unwrap();
+        let json = serde_json::to_value(&prepared_vk).unwrap();
+
+        assert_eq!(json["fixed_base_window_bits"], 2);
+        assert_eq!(
+            serde_json::from_value::<PreparedVerificationKey<()>>(json).unwrap(),
+            prepared_vk
+        );
+    }
+
+    #[rstest]
+    fn compute_the_domain_of_a_deserialized_prepared_vk(mut bb_vk: VerificationKey<()>) {
+        let prepared_vk = PreparedVerificationKey::try_from(&bb_vk).unwrap();
+        let mut json = serde_json::to_value(&prepared_vk).unwrap();
+        assert!(json["vk"].get("work_root").is_none());
+
+        json["vk"]["circuit_size"] = serde_json::Value::from(32);
+        bb_vk.circuit_size = 32;
+
+        assert_eq!(
+            serde_json::from_value::<PreparedVerificationKey<()>>(json).unwrap(),
+            PreparedVerificationKey::try_from(&bb_vk).unwrap()
+        );
+    }
+
+    #[rstest]
+    fn serialize_deserialize_a_proof_as_json(raw_proof: &[u8]) {
+        let proof = Proof::<()>::try_from(raw_proof).unwrap();
+        let json = serde_json::to_value(&proof).unwrap();
+
+        assert_eq!(json["w1_eval"].as_str().unwrap().len(), 2 + 64);
+        assert_eq!(serde_json::from_value::<Proof<()>>(json).unwrap(), proof);
+    }
+
+    #[rstest]
+    fn serialize_deserialize_public_inputs_as_json() {
+        let mut pi = [0u8; PUBS_SIZE];
+        pi[31] = 2;
+        let pubs = PublicInputs(alloc::vec![pi]);
+        let json = serde_json::to_string(&pubs).unwrap();
+
+        assert_eq!(
+            json,
+            r#"["0x0000000000000000000000000000000000000000000000000000000000000002"]"#
+        );
+        assert_eq!(serde_json::from_str::<PublicInputs>(&json).unwrap(), pubs);
+    }
+
+    mod reject {
+        use super::*;
+
+        #[rstest]
+        fn a_vk_with_a_point_not_on_curve(bb_vk: VerificationKey<()>) {
+            let mut json = serde_json::to_value(&bb_vk).unwrap();
+            json["q_1"] = serde_json::Value::from(format!("0x{}", "00".repeat(63) + "01"));
+
+            assert!(serde_json::from_value::<VerificationKey<()>>(json).is_err());
+        }
+
+        #[rstest]
+        #[case::circuit_type("circuit_type", 1)]
+        #[case::circuit_size_not_a_power_of_two("circuit_size", 24)]
+        #[case::circuit_size_too_large("circuit_size", 1 << 29)]
+        #[case::recursive_proof_indices("recursive_proof_indices", 1)]
+        fn a_vk_failing_its_checks(
+            bb_vk: VerificationKey<()>,
+            #[case] field: &str,
+            #[case] value: u32,
+        ) {
+            let mut json = serde_json::to_value(&bb_vk).unwrap();
+            json[field] = serde_json::Value::from(value);
+
+            assert!(serde_json::from_value::<VerificationKey<()>>(json).is_err());
+        }
+
+        #[rstest]
+        fn a_prepared_vk_with_an_invalid_circuit_size(bb_vk: VerificationKey<()>) {
+            let prepared_vk = PreparedVerificationKey::try_from(&bb_vk).unwrap();
+            let mut json = serde_json::to_value(&prepared_vk).unwrap();
+            json["vk"]["circuit_size"] = serde_json::Value::from(24);
+
+            assert!(serde_json::from_value::<PreparedVerificationKey<()>>(json).is_err());
+        }
+
+        #[rstest]
+        fn a_prepared_vk_with_a_window_out_of_range(bb_vk: VerificationKey<()>) {
+            let prepared_vk = PreparedVerificationKey::try_from(&bb_vk).unwrap();
+            let mut json = serde_json::to_value(&prepared_vk).unwrap();
+            json["fixed_base_window_bits"] = serde_json::Value::from(0);
+
+            assert!(serde_json::from_value::<PreparedVerificationKey<()>>(json).is_err());
+        }
+
+        #[rstest]
+        fn a_proof_with_a_non_canonical_evaluation(raw_proof: &[u8]) {
+            let proof = Proof::<()>::try_from(raw_proof).unwrap();
+            let mut json = serde_json::to_value(&proof).unwrap();
+            json["w1_eval"] = serde_json::Value::from(format!("0x{}", "ff".repeat(32)));
+
+            assert!(serde_json::from_value::<Proof<()>>(json).is_err());
+        }
+
+        #[rstest]
+        fn a_public_input_without_the_0x_prefix() {
+            let json = format!(r#"["{}"]"#, "00".repeat(32));
+
+            assert!(serde_json::from_str::<PublicInputs>(&json).is_err());
+        }
+    }
+}
@@ -14,7 +14,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::*;
+use crate::{transcript::testing::OtherTranscript, *};
 use rstest::{fixture, rstest};
 
 #[fixture]
@@ -167,10 +167,904 @@ fn verify_valid_proof(
     assert!(verify::<()>(&valid_vk, &valid_proof, &valid_pub).is_ok());
 }
 
+#[rstest]
+fn verify_valid_proof_with_a_hooks_instance(
+    valid_vk: [u8; VK_SIZE],
+    valid_proof: [u8; PROOF_SIZE],
+    valid_pub: [PublicInput; 1],
+) {
+    let hooks = ();
+
+    assert!(verify_with_hooks(&hooks, &valid_vk, &valid_proof, &valid_pub).is_ok());
+    assert_eq!(
+        verify_with_hooks(&hooks, &valid_vk, &valid_proof, &[[0u8; 32]]),
+        Err(VerifyError::VerificationError)
+    );
+}
+
+#[rstest]
+fn call_the_curve_operations_on_the_hooks_instance(
+    valid_vk: [u8; VK_SIZE],
+    valid_proof: [u8; PROOF_SIZE],
+    valid_pub: [PublicInput; 1],
+) {
+    use crate::hooks::testing::TestHooks;
+    use core::sync::atomic::Ordering;
+
+    let hooks = TestHooks::default();
+
+    assert_eq!(
+        verify_with_hooks(&hooks, &valid_vk, &valid_proof, &valid_pub),
+        Ok(())
+    );
+    assert!(hooks.msm_calls.load(Ordering::Relaxed) > 0);
+    assert_eq!(hooks.miller_loop_calls.load(Ordering::Relaxed), 1);
+    assert_eq!(hooks.final_exponentiation_calls.load(Ordering::Relaxed), 1);
+}
+
+#[rstest]
+fn verify_only_with_the_transcript_of_the_proof(
+    valid_vk: [u8; VK_SIZE],
+    valid_proof: [u8; PROOF_SIZE],
+    valid_pub: [PublicInput; 1],
+) {
+    assert!(
+        verify_with_transcript::<(), Keccak256Transcript>(&valid_vk, &valid_proof, &valid_pub)
+            .is_ok()
+    );
+    assert_eq!(
+        verify_with_transcript::<(), OtherTranscript>(&valid_vk, &valid_proof, &valid_pub),
+        Err(VerifyError::VerificationError)
+    );
+}
+
+#[rstest]
+fn derive_the_challenges_of_every_entry_point_with_the_given_transcript(
+    valid_vk: [u8; VK_SIZE],
+    valid_proof: [u8; PROOF_SIZE],
+    valid_pub: [PublicInput; 1],
+) {
+    let vk = VerificationKey::<()>::try_from_solidity_bytes(&valid_vk).unwrap();
+    let vk = PreparedVerificationKey::try_from(&vk).unwrap();
+    let proof = Proof::<()>::try_from(&valid_proof[..]).unwrap();
+    let srs = PreparedSrs::default();
+
+    assert_eq!(
+        verify_deferred_with_transcript::<(), OtherTranscript>(
+            &(),
+            &vk,
+            &proof,
+            &[Fr::from(10u64)]
+        )
+        .unwrap()
+        .finalize(&()),
+        Err(VerifyError::VerificationError)
+    );
+    assert_eq!(
+        verify_batch_with_transcript::<(), OtherTranscript, _>(
+            &(),
+            &[(&vk, &proof, &valid_pub[..])],
+            &mut ark_std::test_rng(),
+            &srs
+        ),
+        Err(VerifyError::BatchVerificationError {
+            invalid: alloc::vec![0]
+        })
+    );
+    assert!(
+        !verify_with_trace_and_transcript::<(), OtherTranscript>(
+            &valid_vk,
+            &valid_proof,
+            &valid_pub
+        )
+        .unwrap()
+        .verified
+    );
+    assert_eq!(
+        precheck_with_transcript::<(), OtherTranscript>(&(), &valid_vk, &valid_proof, &valid_pub)
+            .unwrap()
+            .verify(&()),
+        Err(VerifyError::VerificationError)
+    );
+    assert_eq!(
+        stepwise::VerificationState::<(), OtherTranscript>::with_transcript(
+            &(),
+            &vk,
+            &valid_proof,
+            &valid_pub
+        )
+        .unwrap()
+        .run(&(), &vk, &srs),
+        Err(VerifyError::VerificationError)
+    );
+}
+
+#[rstest]
+fn hash_the_challenges_of_every_entry_point_through_the_hooks(
+    valid_vk: [u8; VK_SIZE],
+    valid_proof: [u8; PROOF_SIZE],
+    valid_pub: [PublicInput; 1],
+) {
+    use crate::hooks::testing::TestHooks;
+    use core::sync::atomic::Ordering;
+
+    let vk = VerificationKey::<TestHooks>::try_from_solidity_bytes(&valid_vk).unwrap();
+    let vk = PreparedVerificationKey::try_from(&vk).unwrap();
+    let proof = Proof::<TestHooks>::try_from(&valid_proof[..]).unwrap();
+    let pubs = [Fr::from(10u64)];
+    let srs = PreparedSrs::default();
+    let keccak_calls = |verify: &dyn Fn(&TestHooks) -> Result<(), VerifyError>| {
+        let hooks = TestHooks::default();
+        assert_eq!(verify(&hooks), Ok(()));
+        hooks.keccak_calls.load(Ordering::Relaxed)
+    };
+
+    let expected =
+        keccak_calls(&|hooks| verify_with_hooks(hooks, &valid_vk, &valid_proof, &valid_pub));
+    assert!(expected > 0);
+    assert_eq!(
+        keccak_calls(&|hooks| verify_prepared(hooks, &vk, &proof, &pubs)),
+        expected
+    );
+    assert_eq!(
+        keccak_calls(&|hooks| verify_deferred(hooks, &vk, &proof, &pubs)?.finalize(hooks)),
+        expected
+    );
+    assert_eq!(
+        keccak_calls(&|hooks| verify_batch(
+            hooks,
+            &[(&vk, &proof, &valid_pub[..])],
+            &mut ark_std::test_rng()
+        )),
+        expected
+    );
+    assert_eq!(
+        keccak_calls(&|hooks| precheck(hooks, &valid_vk, &valid_proof, &valid_pub)?.verify(hooks)),
+        expected
+    );
+    // The stepwise verifier also hashes the key once on creation and once per stage
+    assert_eq!(
+        keccak_calls(&|hooks| stepwise::VerificationState::new(
+            hooks,
+            &vk,
+            &valid_proof,
+            &valid_pub
+        )?
+        .run(hooks, &vk, &srs)),
+        expected + 6
+    );
+}
+
+#[rstest]
+fn verify_against_a_custom_srs(
+    valid_vk: [u8; VK_SIZE],
+    valid_proof: [u8; PROOF_SIZE],
+    valid_pub: [PublicInput; 1],
+) {
+    let ignition = PreparedSrs::from(&Srs::<()>::try_from_bytes(&srs::SRS_G2).unwrap());
+    let other_setup = PreparedSrs::from(&Srs::<()>::new(G2::<()>::generator()).unwrap());
+
+    // The same prepared setup serves any number of verifications
+    for _ in 0..2 {
+        assert!(verify_with_srs::<()>(&valid_vk, &valid_proof, &valid_pub, &ignition).is_ok());
+    }
+    assert_eq!(
+        verify_with_srs::<()>(&valid_vk, &valid_proof, &valid_pub, &other_setup),
+        Err(VerifyError::VerificationError)
+    );
+}
+
+#[rstest]
+fn verify_a_non_canonical_proof_only_in_lenient_mode(
+    valid_vk: [u8; VK_SIZE],
+    valid_proof: [u8; PROOF_SIZE],
+    valid_pub: [PublicInput; 1],
+) {
+    use ark_ff::BigInteger;
+
+    // Encode w1_eval as w1_eval + p
+    let mut non_canonical_proof = valid_proof;
+    let w1_eval = &mut non_canonical_proof[11 * 64..11 * 64 + 32];
+    let mut value = <[u8; 32]>::try_from(&*w1_eval).unwrap().into_u256();
+    value.add_with_carry(&Fq::MODULUS);
+    w1_eval.copy_from_slice(&value.into_bytes());
+
+    assert_eq!(
+        verify::<()>(&valid_vk, &non_canonical_proof, &valid_pub),
+        Err(VerifyError::InvalidProofError)
+    );
+    assert!(verify_with_mode::<()>(
+        &valid_vk,
+        &non_canonical_proof,
+        &valid_pub,
+        DecodingMode::Lenient
+    )
+    .is_ok());
+}
+
+mod bb_fixtures {
+    use super::*;
+    use crate::key::RawVkFormat;
+
+    // Each proof.bin is prefixed by its public input.
+    #[rstest]
+    #[case::v0_31_0(
+        include_bytes!("../tests/resources/v0.31.0/vk.bin"),
+        include_bytes!("../tests/resources/v0.31.0/proof.bin"),
+        RawVkFormat::V0_31
+    )]
+    #[case::v0_32_0(
+        include_bytes!("../tests/resources/v0.32.0/vk.bin"),
+        include_bytes!("../tests/resources/v0.32.0/proof.bin"),
+        RawVkFormat::V0_31
+    )]
+    #[case::v0_33_0(
+        include_bytes!("../tests/resources/v0.33.0/vk.bin"),
+        include_bytes!("../tests/resources/v0.33.0/proof.bin"),
+        RawVkFormat::V0_33
+    )]
+    #[case::v0_34_0(
+        include_bytes!("../tests/resources/v0.34.0/vk.bin"),
+        include_bytes!("../tests/resources/v0.34.0/proof.bin"),
+        RawVkFormat::V0_33
+    )]
+    #[case::v0_35_0(
+        include_bytes!("../tests/resources/v0.35.0/vk.bin"),
+        include_bytes!("../tests/resources/v0.35.0/proof.bin"),
+        RawVkFormat::V0_33
+    )]
+    #[case::v0_36_0(
+        include_bytes!("../tests/resources/v0.36.0/vk.bin"),
+        include_bytes!("../tests/resources/v0.36.0/proof.bin"),
+        RawVkFormat::V0_33
+    )]
+    #[case::v0_37_0(
+        include_bytes!("../tests/resources/v0.37.0/vk.bin"),
+        include_bytes!("../tests/resources/v0.37.0/proof.bin"),
+        RawVkFormat::V0_33
+    )]
+    #[case::v0_38_0(
+        include_bytes!("../tests/resources/v0.38.0/vk.bin"),
+        include_bytes!("../tests/resources/v0.38.0/proof.bin"),
+        RawVkFormat::V0_33
+    )]
+    #[case::v0_39_0(
+        include_bytes!("../tests/resources/v0.39.0/vk.bin"),
+        include_bytes!("../tests/resources/v0.39.0/proof.bin"),
+        RawVkFormat::V0_33
+    )]
+    #[case::v1_0_0_beta_0(
+        include_bytes!("../tests/resources/v1.0.0-beta.0/vk.bin"),
+        include_bytes!("../tests/resources/v1.0.0-beta.0/proof.bin"),
+        RawVkFormat::V0_33
+    )]
+    #[case::v1_0_0_beta_1(
+        include_bytes!("../tests/resources/v1.0.0-beta.1/vk.bin"),
+        include_bytes!("../tests/resources/v1.0.0-beta.1/proof.bin"),
+        RawVkFormat::V0_33
+    )]
+    fn verify_a_bb_proof(
+        #[case] raw_vk: &[u8],
+        #[case] raw_proof: &[u8],
+        #[case] expected_format: RawVkFormat,
+    ) {
+        let (vk, format) = VerificationKey::<()>::try_from_raw_bytes(raw_vk).unwrap();
+        let (pubs, proof) = raw_proof.split_at(PUBS_SIZE);
+
+        assert_eq!(format, expected_format);
+        assert_eq!(
+            verify::<()>(&vk.as_solidity_bytes(), proof, &[pubs.try_into().unwrap()]),
+            Ok(())
+        );
+    }
+}
+
+mod prepared {
+    use super::*;
+
+    #[rstest]
+    fn verify_a_valid_proof_with_a_prepared_vk(
+        valid_vk: [u8; VK_SIZE],
+        valid_proof: [u8; PROOF_SIZE],
+    ) {
+        let vk = VerificationKey::<()>::try_from_solidity_bytes(&valid_vk).unwrap();
+        let prepared_vk = PreparedVerificationKey::try_from(&vk).unwrap();
+        let proof = Proof::<()>::try_from(&valid_proof[..]).unwrap();
+
+        for _ in 0..2 {
+            assert_eq!(
+                verify_prepared(&(), &prepared_vk, &proof, &[Fr::from(10u64)]),
+                Ok(())
+            );
+        }
+    }
+
+    #[rstest]
+    fn verify_a_valid_proof_with_fixed_base_tables(
+        valid_vk: [u8; VK_SIZE],
+        valid_proof: [u8; PROOF_SIZE],
+    ) {
+        let vk = VerificationKey::<()>::try_from_solidity_bytes(&valid_vk).unwrap();
+        let prepared_vk = PreparedVerificationKey::try_from(&vk)
+            .unwrap()
+            .with_fixed_base_tables(4)
+            .unwrap();
+        let proof = Proof::<()>::try_from(&valid_proof[..]).unwrap();
+
+        assert_eq!(
+            verify_prepared(&(), &prepared_vk, &proof, &[Fr::from(10u64)]),
+            Ok(())
+        );
+        assert_eq!(
+            verify_prepared(&(), &prepared_vk, &proof, &[Fr::from(9u64)]),
+            Err(VerifyError::VerificationError)
+        );
+    }
+
+    #[rstest]
+    fn reject_fixed_base_tables_of_another_vk(
+        valid_vk: [u8; VK_SIZE],
+        valid_proof: [u8; PROOF_SIZE],
+    ) {
+        let vk = VerificationKey::<()>::try_from_solidity_bytes(&valid_vk).unwrap();
+        let mut prepared_vk = PreparedVerificationKey::try_from(&vk)
+            .unwrap()
+            .with_fixed_base_tables(2)
+            .unwrap();
+        // The commitments change after the tables are built
+        prepared_vk.q_1 = prepared_vk.q_2;
+        let proof = Proof::<()>::try_from(&valid_proof[..]).unwrap();
+
+        assert_eq!(
+            verify_prepared(&(), &prepared_vk, &proof, &[Fr::from(10u64)]),
+            Err(VerifyError::KeyError)
+        );
+    }
+
+    #[rstest]
+    fn reject_a_wrong_public_input(valid_vk: [u8; VK_SIZE], valid_proof: [u8; PROOF_SIZE]) {
+        let vk = VerificationKey::<()>::try_from_solidity_bytes(&valid_vk).unwrap();
+        let prepared_vk = PreparedVerificationKey::try_from(&vk).unwrap();
+        let proof = Proof::<()>::try_from(&valid_proof[..]).unwrap();
+
+        assert_eq!(
+            verify_prepared(&(), &prepared_vk, &proof, &[Fr::from(9u64)]),
+            Err(VerifyError::VerificationError)
+        );
+    }
+
+    #[rstest]
+    fn reject_a_wrong_public_input_number(valid_vk: [u8; VK_SIZE], valid_proof: [u8; PROOF_SIZE]) {
+        let vk = VerificationKey::<()>::try_from_solidity_bytes(&valid_vk).unwrap();
+        let prepared_vk = PreparedVerificationKey::try_from(&vk).unwrap();
+        let proof = Proof::<()>::try_from(&valid_proof[..]).unwrap();
+
+        assert!(matches!(
+            verify_prepared(
+                &(),
+                &prepared_vk,
+                &proof,
+                &[Fr::from(10u64), Fr::from(10u64)]
+            ),
+            Err(VerifyError::PublicInputError { .. })
+        ));
+    }
+}
+
+mod deferred {
+    use super::*;
+
+    fn accumulator(
+        raw_vk: &[u8; VK_SIZE],
+        raw_proof: &[u8; PROOF_SIZE],
+        pub_input: u64,
+    ) -> PairingAccumulator<()> {
+        let vk = VerificationKey::<()>::try_from_solidity_bytes(raw_vk).unwrap();
+        let proof = Proof::<()>::try_from(&raw_proof[..]).unwrap();
+        verify_deferred(
+            &(),
+            &PreparedVerificationKey::try_from(&vk).unwrap(),
+            &proof,
+            &[Fr::from(pub_input)],
+        )
+        .unwrap()
+    }
+
+    #[rstest]
+    fn finalize_the_accumulator_of_a_valid_proof(
+        valid_vk: [u8; VK_SIZE],
+        valid_proof: [u8; PROOF_SIZE],
+    ) {
+        assert_eq!(
+            accumulator(&valid_vk, &valid_proof, 10).finalize(&()),
+            Ok(())
+        );
+    }
+
+    #[rstest]
+    fn finalize_merged_accumulators(valid_vk: [u8; VK_SIZE], valid_proof: [u8; PROOF_SIZE]) {
+        let rng = &mut ark_std::test_rng();
+        let acc = accumulator(&valid_vk, &valid_proof, 10);
+
+        assert_eq!(
+            acc.merge(&(), &acc, rng)
+                .unwrap()
+                .merge(&(), &acc, rng)
+                .unwrap()
+                .finalize(&()),
+            Ok(())
+        );
+    }
+
+    #[rstest]
+    fn serialize_deserialize_an_accumulator(
+        valid_vk: [u8; VK_SIZE],
+        valid_proof: [u8; PROOF_SIZE],
+    ) {
+        let acc = accumulator(&valid_vk, &valid_proof, 10);
+
+        assert_eq!(
+            PairingAccumulator::<()>::try_from(&acc.to_bytes()[..]),
+            Ok(acc)
+        );
+    }
+
+    #[rstest]
+    fn encode_an_accumulator_as_evm_pairing_input(
+        valid_vk: [u8; VK_SIZE],
+        valid_proof: [u8; PROOF_SIZE],
+    ) {
+        let acc = accumulator(&valid_vk, &valid_proof, 10);
+        let bytes = acc.to_bytes();
+        let input = acc.to_evm_pairing_input();
+
+        assert_eq!(input[..64], bytes[64..]);
+        assert_eq!(
+            input[64..192],
+            hex_literal::hex!(
+                "
+                198e9393920d483a7260bfb731fb5d25f1aa493335a9e71297e485b7aef312c2
+                1800deef121f1e76426a00665e5c4479674322d4f75edadd46debd5cd992f6ed
+                090689d0585ff075ec9e99ad690c3395bc4b313370b38ef355acdadcd122975b
+                12c85ea5db8c6deb4aab71808dcb408fe3d1e7690c43d37b4ce6cc0166fa7daa
+                "
+            )
+        );
+        assert_eq!(input[192..256], bytes[..64]);
+        assert_eq!(
+            input[256..],
+            hex_literal::hex!(
+                "
+                260e01b251f6f1c7e7ff4e580791dee8ea51d87a358e038b4efe30fac09383c1
+                0118c4d5b837bcc2bc89b5b398b5974e9f5944073b32078b7e231fec938883b0
+                04fc6369f7110fe3d25156c1bb9a72859cf2a04641f99ba4ee413c80da6a5fe4
+                22febda3c0c0632a56475b4214e5615e11e6dd3f96e6cea2854a87d4dacc5e55
+                "
+            )
+        );
+    }
+
+    #[rstest]
+    fn encode_an_accumulator_as_evm_pairing_input_with_a_custom_srs(
+        valid_vk: [u8; VK_SIZE],
+        valid_proof: [u8; PROOF_SIZE],
+    ) {
+        let acc = accumulator(&valid_vk, &valid_proof, 10);
+        let g2 = (*Srs::<()>::ignition().g2() * Fr::from(2u64)).into_affine();
+        let srs = Srs::new(g2).unwrap();
+
+        let input = acc.to_evm_pairing_input_with_srs(&srs);
+        let ignition_input = acc.to_evm_pairing_input();
+
+        assert_eq!(input[..256], ignition_input[..256]);
+        assert_ne!(input[256..], ignition_input[256..]);
+        assert_eq!(
+            acc.to_evm_pairing_input_with_srs(&Srs::ignition()),
+            ignition_input
+        );
+    }
+
+    mod reject {
+        use super::*;
+
+        #[rstest]
+        fn the_accumulator_of_an_invalid_proof(
+            valid_vk: [u8; VK_SIZE],
+            valid_proof: [u8; PROOF_SIZE],
+        ) {
+            assert_eq!(
+                accumulator(&valid_vk, &valid_proof, 9).finalize(&()),
+                Err(VerifyError::VerificationError)
+            );
+        }
+
+        #[rstest]
+        fn an_accumulator_merged_with_an_invalid_one(
+            valid_vk: [u8; VK_SIZE],
+            valid_proof: [u8; PROOF_SIZE],
+        ) {
+            let valid = accumulator(&valid_vk, &valid_proof, 10);
+            let invalid = accumulator(&valid_vk, &valid_proof, 9);
+
+            assert_eq!(
+                valid
+                    .merge(&(), &invalid, &mut ark_std::test_rng())
+                    .unwrap()
+                    .finalize(&()),
+                Err(VerifyError::VerificationError)
+            );
+        }
+
+        #[rstest]
+        fn an_accumulator_with_a_point_not_on_curve(
+            valid_vk: [u8; VK_SIZE],
+            valid_proof: [u8; PROOF_SIZE],
+        ) {
+            let mut bytes = accumulator(&valid_vk, &valid_proof, 10).to_bytes();
+            bytes[127] ^= 1;
+
+            assert_eq!(
+                PairingAccumulator::<()>::try_from(&bytes[..]),
+                Err(errors::GroupError::NotOnCurve)
+            );
+        }
+
+        #[rstest]
+        fn an_accumulator_from_a_short_buffer() {
+            assert_eq!(
+                PairingAccumulator::<()>::try_from(&[0u8; 64][..]),
+                Err(errors::GroupError::InvalidSliceLength {
+                    actual_length: 64,
+                    expected_length: 128
+                })
+            );
+        }
+    }
+}
+
+mod untrusted_input {
+    use super::*;
+    use crate::{
+        accumulator::ACCUMULATOR_SIZE,
+        fixtures::{BB_OUTPUT, BB_VK},
+        key::RawVkFormat,
+    };
+    use ark_std::rand::RngCore;
+
+    // Feeds `data` to every parser: any error is fine, a panic is not.
+    fn parse_everything(data: &[u8]) {
+        let bb_vk = VerificationKey::<()>::try_from(BB_VK).unwrap();
+
+        if let Ok(vk) = VerificationKey::<()>::try_from_solidity_bytes(data) {
+            let _ = PreparedVerificationKey::try_from(&vk);
+        }
+        if let Ok((vk, _)) = VerificationKey::<()>::try_from_raw_bytes(data) {
+            let _ = PreparedVerificationKey::try_from(&vk);
+        }
+        let _ = VerificationKey::<()>::try_from_raw_bytes_with_mode(data, DecodingMode::Lenient);
+        for format in [RawVkFormat::Legacy, RawVkFormat::V0_31, RawVkFormat::V0_33] {
+            let _ = VerificationKey::<()>::try_from_raw_bytes_with_format(data, format);
+        }
+        let _ = Proof::<()>::try_from(data);
+        let _ = Proof::<()>::try_from_bytes_with_mode(data, DecodingMode::Lenient);
+        let _ = Proof::<()>::from_bb_output(data, &bb_vk);
+        let _ = PairingAccumulator::<()>::try_from(data);
+    }
+
+    fn inputs(valid_vk: [u8; VK_SIZE], valid_proof: [u8; PROOF_SIZE]) -> [Vec<u8>; 4] {
+        [
+            valid_vk.to_vec(),
+            valid_proof.to_vec(),
+            BB_VK.to_vec(),
+            BB_OUTPUT.to_vec(),
+        ]
+    }
+
+    #[rstest]
+    fn truncated_inputs(valid_vk: [u8; VK_SIZE], valid_proof: [u8; PROOF_SIZE]) {
+        for input in inputs(valid_vk, valid_proof) {
+            for len in 0..input.len() {
+                parse_everything(&input[..len]);
+            }
+        }
+    }
+
+    #[rstest]
+    fn corrupted_inputs(valid_vk: [u8; VK_SIZE], valid_proof: [u8; PROOF_SIZE]) {
+        for mut input in inputs(valid_vk, valid_proof) {
+            for i in 0..input.len() {
+                input[i] ^= 0xff;
+                parse_everything(&input);
+                input[i] ^= 0xff;
+            }
+        }
+    }
+
+    #[rstest]
+    fn garbage_inputs(valid_vk: [u8; VK_SIZE], valid_pub: [PublicInput; 1]) {
+        let rng = &mut ark_std::test_rng();
+        let sizes = [
+            0,
+            1,
+            4,
+            PUBS_SIZE,
+            64,
+            ACCUMULATOR_SIZE,
+            VK_SIZE,
+            1715,
+            1719,
+            1779,
+            PROOF_SIZE,
+            PUBS_SIZE + PROOF_SIZE,
+        ];
+
+        for size in sizes {
+            for _ in 0..16 {
+                let mut data = alloc::vec![0u8; size];
+                rng.fill_bytes(&mut data);
+
+                parse_everything(&data);
+                let _ = verify::<()>(&data, &data, &valid_pub);
+                let _ = verify::<()>(&valid_vk, &data, &valid_pub);
+            }
+        }
+    }
+}
+
+mod trace {
+    use super::*;
+
+    #[rstest]
+    fn trace_a_valid_proof(
+        valid_vk: [u8; VK_SIZE],
+        valid_proof: [u8; PROOF_SIZE],
+        valid_pub: [PublicInput; 1],
+    ) {
+        let trace = verify_with_trace::<()>(&valid_vk, &valid_proof, &valid_pub).unwrap();
+
+        let vk = VerificationKey::<()>::try_from_solidity_bytes(&valid_vk).unwrap();
+        let proof = Proof::<()>::try_from(&valid_proof[..]).unwrap();
+        let acc = verify_deferred(
+            &(),
+            &PreparedVerificationKey::try_from(&vk).unwrap(),
+            &proof,
+            &[Fr::from(10u64)],
+        )
+        .unwrap();
+
+        assert!(trace.verified);
+        assert_eq!((trace.pairing_lhs, trace.pairing_rhs), (acc.lhs, acc.rhs));
+    }
+
+    #[rstest]
+    fn trace_an_invalid_proof(
+        valid_vk: [u8; VK_SIZE],
+        valid_proof: [u8; PROOF_SIZE],
+        valid_pub: [PublicInput; 1],
+    ) {
+        let mut invalid_pub = valid_pub;
+        invalid_pub[0][31] -= 1;
+
+        let valid = verify_with_trace::<()>(&valid_vk, &valid_proof, &valid_pub).unwrap();
+        let invalid = verify_with_trace::<()>(&valid_vk, &valid_proof, &invalid_pub).unwrap();
+
+        assert!(!invalid.verified);
+        // The public inputs enter the transcript since the very first challenge
+        assert_ne!(invalid.eta, valid.eta);
+    }
+
+    #[rstest]
+    fn reject_a_wrong_public_input_number(valid_vk: [u8; VK_SIZE], valid_proof: [u8; PROOF_SIZE]) {
+        assert!(matches!(
+            verify_with_trace::<()>(&valid_vk, &valid_proof, &[]),
+            Err(VerifyError::PublicInputError { .. })
+        ));
+    }
+}
+
+mod batch {
+    use super::*;
+
+    fn prepare(
+        raw_vk: &[u8; VK_SIZE],
+        raw_proof: &[u8; PROOF_SIZE],
+    ) -> (PreparedVerificationKey<()>, Proof<()>) {
+        let vk = VerificationKey::<()>::try_from_solidity_bytes(raw_vk).unwrap();
+        let proof = Proof::<()>::try_from(&raw_proof[..]).unwrap();
+        (PreparedVerificationKey::try_from(&vk).unwrap(), proof)
+    }
+
+    #[rstest]
+    fn verify_a_batch_of_valid_proofs(
+        valid_vk: [u8; VK_SIZE],
+        valid_proof: [u8; PROOF_SIZE],
+        valid_pub: [PublicInput; 1],
+    ) {
+        let (vk, proof) = prepare(&valid_vk, &valid_proof);
+        let items = [(&vk, &proof, &valid_pub[..]); 3];
+
+        assert_eq!(verify_batch(&(), &items, &mut ark_std::test_rng()), Ok(()));
+    }
+
+    #[rstest]
+    fn verify_an_empty_batch() {
+        assert_eq!(
+            verify_batch::<(), _>(&(), &[], &mut ark_std::test_rng()),
+            Ok(())
+        );
+    }
+
+    #[rstest]
+    fn report_every_invalid_proof_of_a_batch(
+        valid_vk: [u8; VK_SIZE],
+        valid_proof: [u8; PROOF_SIZE],
+        valid_pub: [PublicInput; 1],
+    ) {
+        let (vk, proof) = prepare(&valid_vk, &valid_proof);
+        let mut invalid_pub = valid_pub;
+        invalid_pub[0][31] -= 1;
+        let items = [
+            (&vk, &proof, &valid_pub[..]),
+            (&vk, &proof, &invalid_pub[..]),
+            (&vk, &proof, &valid_pub[..]),
+            (&vk, &proof, &valid_pub[..]),
+            (&vk, &proof, &invalid_pub[..]),
+        ];
+
+        assert_eq!(
+            verify_batch(&(), &items, &mut ark_std::test_rng()),
+            Err(VerifyError::BatchVerificationError {
+                invalid: alloc::vec![1, 4]
+            })
+        );
+    }
+
+    #[rstest]
+    fn report_proofs_with_a_wrong_public_input_number(
+        valid_vk: [u8; VK_SIZE],
+        valid_proof: [u8; PROOF_SIZE],
+        valid_pub: [PublicInput; 1],
+    ) {
+        let (vk, proof) = prepare(&valid_vk, &valid_proof);
+        let items = [(&vk, &proof, &valid_pub[..]), (&vk, &proof, &[][..])];
+
+        assert_eq!(
+            verify_batch(&(), &items, &mut ark_std::test_rng()),
+            Err(VerifyError::BatchVerificationError {
+                invalid: alloc::vec![1]
+            })
+        );
+    }
+}
+
+mod recursion {
+    use super::*;
+    use ark_ff::BigInt;
+
+    fn limb(value: u128) -> U256 {
+        BigInt::new([value as u64, (value >> 64) as u64, 0, 0])
+    }
+
+    // P1 = G and P2 = -G, preceded by an unrelated public input.
+    #[fixture]
+    fn aggregation_object() -> [U256; 17] {
+        [
+            limb(42),
+            limb(1),
+            limb(0),
+            limb(0),
+            limb(0),
+            limb(2),
+            limb(0),
+            limb(0),
+            limb(0),
+            limb(1),
+            limb(0),
+            limb(0),
+            limb(0),
+            limb(0xd3c208c16d87cfd45),
+            limb(0x5d97816a916871ca8),
+            limb(0x29b85045b6818158),
+            limb(0x30644e72e131a),
+        ]
+    }
+
+    #[rstest]
+    fn read_the_aggregation_object(aggregation_object: [U256; 17]) {
+        let generator = G1::<()>::generator();
+
+        assert_eq!(
+            read_aggregation_object::<()>(&aggregation_object, 1),
+            Ok((generator, -generator))
+        );
+    }
+
+    mod reject {
+        use super::*;
+
+        #[rstest]
+        fn an_aggregation_object_out_of_the_public_inputs(aggregation_object: [U256; 17]) {
+            assert!(matches!(
+                read_aggregation_object::<()>(&aggregation_object, 2),
+                Err(VerifyError::PublicInputError { .. })
+            ));
+        }
+
+        #[rstest]
+        fn a_coordinate_not_in_the_base_field(mut aggregation_object: [U256; 17]) {
+            aggregation_object[13] = limb(0xd3c208c16d87cfd47);
+
+            assert!(matches!(
+                read_aggregation_object::<()>(&aggregation_object, 1),
+                Err(VerifyError::PublicInputError { .. })
+            ));
+        }
+
+        #[rstest]
+        fn an_oversized_limb(mut aggregation_object: [U256; 17]) {
+            aggregation_object[2] = limb(1 << 68);
+
+            assert!(matches!(
+                read_aggregation_object::<()>(&aggregation_object, 1),
+                Err(VerifyError::PublicInputError { .. })
+            ));
+        }
+
+        #[rstest]
+        fn a_point_not_on_curve(mut aggregation_object: [U256; 17]) {
+            aggregation_object[5] = limb(3);
+
+            assert!(matches!(
+                read_aggregation_object::<()>(&aggregation_object, 1),
+                Err(VerifyError::PublicInputError { .. })
+            ));
+        }
+
+        #[rstest]
+        fn a_recursive_proof_with_an_invalid_aggregation_object(
+            valid_vk: [u8; VK_SIZE],
+            valid_proof: [u8; PROOF_SIZE],
+        ) {
+            let vk = VerificationKey::<()>::try_from_solidity_bytes(&valid_vk).unwrap();
+            let mut prepared_vk = PreparedVerificationKey::try_from(&vk).unwrap();
+            prepared_vk.num_public_inputs = 16;
+            prepared_vk.contains_recursive_proof = true;
+            let proof = Proof::<()>::try_from(&valid_proof[..]).unwrap();
+
+            assert!(matches!(
+                verify_prepared(&(), &prepared_vk, &proof, &[Fr::from(0u64); 16]),
+                Err(VerifyError::PublicInputError { .. })
+            ));
+        }
+    }
+}
+
 mod reject {
     use alloc::string::ToString;
 
     use super::*;
+    use crate::hooks::testing::{Fault, TestHooks};
+
+    #[rstest]
+    #[case::msm(Fault::FailingMsm)]
+    #[case::miller_loop(Fault::FailingMillerLoop)]
+    #[case::final_exponentiation(Fault::FailingFinalExponentiation)]
+    fn a_proof_whose_curve_hooks_fail(
+        valid_vk: [u8; VK_SIZE],
+        valid_proof: [u8; PROOF_SIZE],
+        valid_pub: [PublicInput; 1],
+        #[case] fault: Fault,
+    ) {
+        assert_eq!(
+            verify_with_hooks(
+                &TestHooks::with_fault(fault),
+                &valid_vk,
+                &valid_proof,
+                &valid_pub
+            ),
+            Err(VerifyError::CurveHooksError)
+        );
+    }
 
     #[rstest]
     fn an_invalid_vk(valid_proof: [u8; PROOF_SIZE], valid_pub: [PublicInput; 1]) {
@@ -14,6 +14,14 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use ark_bn254_ext::{Config, CurveHooks};
+use ark_ec::AffineRepr;
+use ark_ff::MontFp;
+use ark_models_ext::bn::G2Prepared;
+use snafu::Snafu;
+
+use crate::{utils::read_fq_util, DecodingMode, Fq, Fq2, G2};
+
 /// A constant byte slice representing BN254 G2 point. `noir-compiler` when installed will
 /// downloads this data and stores it in ~/.nargo/backends/acvm-backend-barretenberg/crs/bn254_g2.dat
 pub static SRS_G2: [u8; 128] = hex_literal::hex!(
@@ -24,3 +32,206 @@ pub static SRS_G2: [u8; 128] = hex_literal::hex!(
         04fc6369f7110fe3d25156c1bb9a72859cf2a04641f99ba4ee413c80da6a5fe4
     "
 );
+
+/// Size of a G2 point in the [`SRS_G2`] layout.
+pub const SRS_G2_SIZE: usize = 128;
+
+#[derive(Debug, PartialEq, Snafu)]
+pub enum SrsError {
+    #[snafu(display(
+        "Invalid slice size. Expected: {}; Got: {}",
+        expected_length,
+        actual_length
+    ))]
+    InvalidSliceLength {
+        expected_length: usize,
+        actual_length: usize,
+    },
+
+    #[snafu(display("Value is not a member of Fq"))]
+    NotMember,
+
+    #[snafu(display("Point is the point at infinity"))]
+    PointAtInfinity,
+
+    #[snafu(display("Point is not on curve"))]
+    PointNotOnCurve,
+
+    #[snafu(display("Point is not in the G2 subgroup"))]
+    PointNotInCorrectSubgroup,
+}
+
+/// The `[x]_2` point of the trusted setup that proofs are checked against.
+///
+/// [`Srs::default`] is the Aztec Ignition one, which is what bb uses. Any other point goes
+/// through [`Srs::new`], so that only points of the G2 subgroup are accepted, the point at
+/// infinity excepted: with it, the pairing check holds for any proof.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Srs<H: CurveHooks> {
+    g2: G2<H>,
+}
+
+impl<H: CurveHooks> Srs<H> {
+    /// Validates `g2` and wraps it.
+    pub fn new(g2: G2<H>) -> Result<Self, SrsError> {
+        if g2.is_zero() {
+            return Err(SrsError::PointAtInfinity);
+        }
+        if !g2.is_on_curve() {
+            return Err(SrsError::PointNotOnCurve);
+        }
+        if !g2.is_in_correct_subgroup_assuming_on_curve() {
+            return Err(SrsError::PointNotInCorrectSubgroup);
+        }
+
+        Ok(Self { g2 })
+    }
+
+    /// The Aztec Ignition `[x]_2` point, i.e. [`SRS_G2`].
+    pub fn ignition() -> Self {
+        let x = Fq2::new(
+            MontFp!("496075682290949347282619629729389528669750910289829251317610107342504362928"),
+            MontFp!(
+                "17212635814319756364507010169094758005397460366678210664966334781961899574209"
+            ),
+        );
+        let y = Fq2::new(
+            MontFp!(
+                "15828724851114720558251891430452666121603726704878231219287131634746610441813"
+            ),
+            MontFp!("2255182984359105691812395885056400739448730162863181907784180250290003009508"),
+        );
+
+        Self {
+            g2: G2::<H>::new_unchecked(x, y),
+        }
+    }
+
+    /// Parses `x.c0 || x.c1 || y.c0 || y.c1`, each coordinate being 32 big-endian bytes as
+    /// in [`SRS_G2`].
+    pub fn try_from_bytes(data: &[u8]) -> Result<Self, SrsError> {
+        check_size(data)?;
+
+        let read = |i: usize| {
+            read_fq_util(&data[i * 32..(i + 1) * 32], DecodingMode::Strict)
+                .map_err(|_| SrsError::NotMember)
+        };
+        Self::from_coordinates([read(0)?, read(1)?, read(2)?, read(3)?])
+    }
+
+    /// The `[x]_2` point.
+    pub fn g2(&self) -> &G2<H> {
+        &self.g2
+    }
+
+    fn from_coordinates([x_c0, x_c1, y_c0, y_c1]: [Fq; 4]) -> Result<Self, SrsError> {
+        Self::new(G2::<H>::new_unchecked(
+            Fq2::new(x_c0, x_c1),
+            Fq2::new(y_c0, y_c1),
+        ))
+    }
+}
+
+impl<H: CurveHooks> Default for Srs<H> {
+    fn default() -> Self {
+        Self::ignition()
+    }
+}
+
+/// An [`Srs`] together with the Miller loop line coefficients of `[1]_2` and `[x]_2`.
+///
+/// Preparing the G2 points is a sizeable part of a verification: build this once and pass it
+/// by reference to every verification instead.
+#[derive(Debug, Clone)]
+pub struct PreparedSrs<H: CurveHooks> {
+    pub(crate) g2_generator: G2Prepared<Config<H>>,
+    pub(crate) g2_x: G2Prepared<Config<H>>,
+}
+
+impl<H: CurveHooks> From<&Srs<H>> for PreparedSrs<H> {
+    fn from(srs: &Srs<H>) -> Self {
+        Self {
+            g2_generator: G2Prepared::from(G2::<H>::generator()),
+            g2_x: G2Prepared::from(srs.g2),
+        }
+    }
+}
+
+impl<H: CurveHooks> Default for PreparedSrs<H> {
+    fn default() -> Self {
+        Self::from(&Srs::default())
+    }
+}
+
+fn check_size(data: &[u8]) -> Result<(), SrsError> {
+    if data.len() != SRS_G2_SIZE {
+        return Err(SrsError::InvalidSliceLength {
+            expected_length: SRS_G2_SIZE,
+            actual_length: data.len(),
+        });
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod should {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    fn parse_the_ignition_point() {
+        assert_eq!(Srs::<()>::try_from_bytes(&SRS_G2), Ok(Srs::ignition()));
+        assert_eq!(Srs::new(*Srs::<()>::ignition().g2()), Ok(Srs::ignition()));
+    }
+
+    mod reject {
+        use super::*;
+
+        #[rstest]
+        fn a_point_with_a_wrong_size() {
+            assert_eq!(
+                Srs::<()>::try_from_bytes(&SRS_G2[1..]),
+                Err(SrsError::InvalidSliceLength {
+                    expected_length: SRS_G2_SIZE,
+                    actual_length: SRS_G2_SIZE - 1
+                })
+            );
+        }
+
+        #[rstest]
+        fn a_non_canonical_coordinate() {
+            let mut invalid_g2 = SRS_G2;
+            invalid_g2[..32].fill(0xff);
+
+            assert_eq!(
+                Srs::<()>::try_from_bytes(&invalid_g2),
+                Err(SrsError::NotMember)
+            );
+        }
+
+        #[rstest]
+        fn a_point_not_on_curve() {
+            let mut invalid_g2 = SRS_G2;
+            invalid_g2[127] ^= 1;
+
+            assert_eq!(
+                Srs::<()>::try_from_bytes(&invalid_g2),
+                Err(SrsError::PointNotOnCurve)
+            );
+        }
+
+        #[rstest]
+        fn a_point_out_of_the_g2_subgroup() {
+            let point = (1u64..)
+                .find_map(|x| G2::<()>::get_point_from_x_unchecked(Fq2::from(x), false))
+                .unwrap();
+
+            assert_eq!(Srs::new(point), Err(SrsError::PointNotInCorrectSubgroup));
+        }
+
+        #[rstest]
+        fn the_point_at_infinity() {
+            assert_eq!(Srs::new(G2::<()>::zero()), Err(SrsError::PointAtInfinity));
+        }
+    }
+}
@@ -0,0 +1,801 @@
+// Copyright 2024 Horizen Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0 or MIT
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A verification that runs one bounded [`Stage`] per call, for runtimes that cap the work
+//! of a single call.
+//!
+//! A [`VerificationState`] owns the proof and the public inputs, and can be stored between
+//! two steps with [`VerificationState::to_bytes`]. The verification key is passed again to
+//! every step: the state only keeps a digest of it, and rejects any other key. The stages
+//! are the ones [`crate::verify`] goes through, so both reach the same result.
+//!
+//! Only UltraPlonk proofs can be verified stepwise: Standard and Turbo ones go through
+//! [`crate::legacy`].
+//!
+//! A serialized state carries values that the next steps trust without recomputing them,
+//! so states must only be read back from storage that the verifier alone can write. On top
+//! of that, [`VerificationState::to_bytes`] seals the state with a Keccak256 MAC under a
+//! secret key of the verifier, over the transcript [`Transcript::ID`], the key digest, the
+//! proof, the public inputs and the progress, and [`VerificationState::try_from_bytes`]
+//! rejects any state whose MAC doesn't match. A verified state is never decoded: the proof
+//! has to be stepped through again. The key digest and the MAC are hashed through the
+//! Keccak-256 implementation of the hooks the state is handled with.
+
+use alloc::vec::Vec;
+use core::{fmt, marker::PhantomData};
+
+use ark_bn254_ext::CurveHooks;
+use ark_ff::{AdditiveGroup, PrimeField};
+use snafu::Snafu;
+
+use crate::{
+    accumulator::ACCUMULATOR_SIZE,
+    check_public_input_number, compute_challenge_round, compute_nu_round, compute_pairing_points,
+    compute_widget_round,
+    errors::VerifyError,
+    hooks::VerifierHooks,
+    key::PreparedVerificationKey,
+    pairing_check,
+    proof::{PreparedProof, Proof},
+    utils::{read_g1_util, IntoBytes, IntoU256},
+    ChallengeRound, DecodingMode, Fr, Keccak256Transcript, NuChallenges, PairingAccumulator,
+    PreparedSrs, Public, Transcript, WidgetRound, G1, PROOF_SIZE, U256,
+};
+
+/// The stage a [`VerificationState`] runs on its next step.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum Stage {
+    /// Fiat-Shamir challenges up to `zeta`, and the aggregation object if any.
+    Challenges,
+    /// Public input and plookup deltas, Lagrange terms and widget identities.
+    Widgets,
+    /// Quotient evaluation and nu challenges.
+    Quotient,
+    /// The MSMs of the pairing points.
+    Msm,
+    /// The final pairing check.
+    Pairing,
+    /// Nothing left: the proof is valid.
+    Verified,
+}
+
+/// Size of the secret key sealing the serialized states.
+pub const MAC_KEY_SIZE: usize = 32;
+
+// The stages, indexed by their serialization tag.
+const STAGES: [Stage; 6] = [
+    Stage::Challenges,
+    Stage::Widgets,
+    Stage::Quotient,
+    Stage::Msm,
+    Stage::Pairing,
+    Stage::Verified,
+];
+
+#[derive(Debug, PartialEq, Snafu)]
+pub enum StateError {
+    #[snafu(display("Buffer too short"))]
+    BufferTooShort,
+
+    #[snafu(display("Trailing bytes after the state"))]
+    TrailingBytes,
+
+    #[snafu(display("Unknown stage {}", tag))]
+    UnknownStage { tag: u8 },
+
+    #[snafu(display("Invalid proof"))]
+    InvalidProof,
+
+    #[snafu(display("Value is not a member of Fr"))]
+    NotMember,
+
+    #[snafu(display("Invalid G1 point"))]
+    InvalidPoint,
+
+    #[snafu(display("The state MAC doesn't match"))]
+    InvalidMac,
+
+    #[snafu(display("A verified state can't be resumed"))]
+    VerifiedState,
+}
+
+/// The values computed by the stages run so far.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Progress<H: CurveHooks> {
+    Challenges,
+    Widgets(ChallengeRound<H>),
+    Quotient(ChallengeRound<H>, WidgetRound),
+    Msm(ChallengeRound<H>, Fr, NuChallenges),
+    Pairing(PairingAccumulator<H>),
+    Verified,
+}
+
+/// An UltraPlonk verification in progress, whose Fiat-Shamir challenges are derived with
+/// the `T` transcript.
+///
+/// The transcript is not part of the serialized state, but its [`Transcript::ID`] is sealed
+/// with it: a state can only be resumed with the transcript it was created with.
+pub struct VerificationState<H: VerifierHooks, T: Transcript<H> = Keccak256Transcript<H>> {
+    vk_digest: [u8; 32],
+    raw_proof: [u8; PROOF_SIZE],
+    public_inputs: Vec<U256>,
+    progress: Progress<H>,
+    transcript: PhantomData<fn() -> T>,
+}
+
+// Not derived, to not require `T` to implement them.
+impl<H: VerifierHooks, T: Transcript<H>> fmt::Debug for VerificationState<H, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("VerificationState")
+            .field("vk_digest", &self.vk_digest)
+            .field("raw_proof", &self.raw_proof)
+            .field("public_inputs", &self.public_inputs)
+            .field("progress", &self.progress)
+            .finish()
+    }
+}
+
+impl<H: VerifierHooks, T: Transcript<H>> Clone for VerificationState<H, T> {
+    fn clone(&self) -> Self {
+        Self {
+            vk_digest: self.vk_digest,
+            raw_proof: self.raw_proof,
+            public_inputs: self.public_inputs.clone(),
+            progress: self.progress.clone(),
+            transcript: PhantomData,
+        }
+    }
+}
+
+impl<H: VerifierHooks, T: Transcript<H>> PartialEq for VerificationState<H, T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.vk_digest == other.vk_digest
+            && self.raw_proof == other.raw_proof
+            && self.public_inputs == other.public_inputs
+            && self.progress == other.progress
+    }
+}
+
+impl<H: VerifierHooks, T: Transcript<H>> Eq for VerificationState<H, T> {}
+
+impl<H: VerifierHooks> VerificationState<H> {
+    /// Starts the verification of `raw_proof` against `vk` and `pubs`. The proof and the
+    /// number of public inputs are checked here, and `vk` is hashed through `hooks`.
+    pub fn new(
+        hooks: &H,
+        vk: &PreparedVerificationKey<H>,
+        raw_proof: &[u8],
+        pubs: &Public,
+    ) -> Result<Self, VerifyError> {
+        Self::with_transcript(hooks, vk, raw_proof, pubs)
+    }
+}
+
+impl<H: VerifierHooks, T: Transcript<H>> VerificationState<H, T> {
+    /// Same as [`VerificationState::new`], but the Fiat-Shamir challenges are derived with
+    /// the `T` transcript: it must be the one the proof was generated with.
+    pub fn with_transcript(
+        hooks: &H,
+        vk: &PreparedVerificationKey<H>,
+        raw_proof: &[u8],
+        pubs: &Public,
+    ) -> Result<Self, VerifyError> {
+        Proof::<H>::try_from(raw_proof).map_err(|_| VerifyError::InvalidProofError)?;
+        check_public_input_number(vk.num_public_inputs, pubs)?;
+
+        Ok(Self {
+            vk_digest: vk_digest(hooks, vk),
+            raw_proof: raw_proof.try_into().unwrap(),
+            public_inputs: pubs.iter().map(|pi| pi.into_u256()).collect(),
+            progress: Progress::Challenges,
+            transcript: PhantomData,
+        })
+    }
+
+    /// The stage the next [`Self::step`] runs.
+    pub fn stage(&self) -> Stage {
+        match self.progress {
+            Progress::Challenges => Stage::Challenges,
+            Progress::Widgets(..) => Stage::Widgets,
+            Progress::Quotient(..) => Stage::Quotient,
+            Progress::Msm(..) => Stage::Msm,
+            Progress::Pairing(..) => Stage::Pairing,
+            Progress::Verified => Stage::Verified,
+        }
+    }
+
+    /// Runs the current stage and returns the next one. `vk` must be the key the state was
+    /// created with, or [`VerifyError::KeyError`] is returned.
+    ///
+    /// A failing pairing check is reported as [`VerifyError::VerificationError`]. On error
+    /// the state is left as it was.
+    pub fn step(
+        &mut self,
+        hooks: &H,
+        vk: &PreparedVerificationKey<H>,
+        srs: &PreparedSrs<H>,
+    ) -> Result<Stage, VerifyError> {
+        if vk_digest(hooks, vk) != self.vk_digest {
+            return Err(VerifyError::KeyError);
+        }
+        let proof = Proof::<H>::try_from(&self.raw_proof[..])
+            .map_err(|_| VerifyError::InvalidProofError)?;
+        let prepared_proof =
+            || PreparedProof::try_from(&proof).map_err(|_| VerifyError::InvalidProofError);
+
+        self.progress = match &self.progress {
+            Progress::Challenges => {
+                let challenge_round =
+                    compute_challenge_round::<H, T>(hooks, vk, &proof, &self.public_inputs)?;
+                Progress::Widgets(challenge_round)
+            }
+            Progress::Widgets(challenge_round) => {
+                let widget_round = compute_widget_round::<H>(
+                    vk,
+                    &prepared_proof()?,
+                    &challenge_round.challenges(vk),
+                    &self.public_inputs,
+                )?;
+                Progress::Quotient(challenge_round.clone(), widget_round)
+            }
+            Progress::Quotient(challenge_round, widget_round) => {
+                let (quotient_eval, nu_challenges) = compute_nu_round::<H, T>(
+                    hooks,
+                    &proof,
+                    &challenge_round.c_current,
+                    widget_round,
+                )?;
+                Progress::Msm(challenge_round.clone(), quotient_eval, nu_challenges)
+            }
+            Progress::Msm(challenge_round, quotient_eval, nu_challenges) => {
+                let (lhs, rhs) = compute_pairing_points(
+                    hooks,
+                    &prepared_proof()?,
+                    vk,
+                    &challenge_round.challenges(vk),
+                    nu_challenges,
+                    quotient_eval,
+                    challenge_round.recursive_points,
+                )?;
+                Progress::Pairing(PairingAccumulator { lhs, rhs })
+            }
+            Progress::Pairing(accumulator) => {
+                if !pairing_check(hooks, accumulator.lhs, accumulator.rhs, srs)? {
+                    return Err(VerifyError::VerificationError);
+                }
+                Progress::Verified
+            }
+            Progress::Verified => Progress::Verified,
+        };
+
+        Ok(self.stage())
+    }
+
+    /// Runs every remaining stage.
+    pub fn run(
+        &mut self,
+        hooks: &H,
+        vk: &PreparedVerificationKey<H>,
+        srs: &PreparedSrs<H>,
+    ) -> Result<(), VerifyError> {
+        while self.step(hooks, vk, srs)? != Stage::Verified {}
+        Ok(())
+    }
+
+    /// Serializes the state: the stage tag, the key digest, the proof, the number of public
+    /// inputs as a big-endian `u32` followed by the inputs, the values computed so far, field
+    /// elements and points being 32 bytes big-endian words, and finally the MAC of all of
+    /// them and of the transcript identifier under `mac_key`, computed through `hooks`.
+    pub fn to_bytes(&self, hooks: &H, mac_key: &[u8; MAC_KEY_SIZE]) -> Vec<u8> {
+        let mut out = Vec::new();
+        out.push(self.stage() as u8);
+        out.extend_from_slice(&self.vk_digest);
+        out.extend_from_slice(&self.raw_proof);
+        out.extend_from_slice(&(self.public_inputs.len() as u32).to_be_bytes());
+        for pi in &self.public_inputs {
+            out.extend_from_slice(&pi.into_bytes());
+        }
+
+        match &self.progress {
+            Progress::Challenges | Progress::Verified => {}
+            Progress::Widgets(challenge_round) => write_challenge_round(&mut out, challenge_round),
+            Progress::Quotient(challenge_round, widget_round) => {
+                write_challenge_round(&mut out, challenge_round);
+                write_widget_round(&mut out, widget_round);
+            }
+            Progress::Msm(challenge_round, quotient_eval, nu_challenges) => {
+                write_challenge_round(&mut out, challenge_round);
+                out.extend_from_slice(&quotient_eval.into_bytes());
+                for c_v in &nu_challenges.c_v {
+                    out.extend_from_slice(&c_v.into_bytes());
+                }
+                out.extend_from_slice(&nu_challenges.c_u.into_bytes());
+            }
+            Progress::Pairing(accumulator) => out.extend_from_slice(&accumulator.to_bytes()),
+        }
+        let mac = mac::<H, T>(hooks, mac_key, &out);
+        out.extend_from_slice(&mac);
+        out
+    }
+
+    /// Parses a state serialized by [`Self::to_bytes`] with the same `mac_key`.
+    ///
+    /// Fails with [`StateError::InvalidMac`] when the state was not sealed under `mac_key`
+    /// and for the `T` transcript, or was altered since, and with
+    /// [`StateError::VerifiedState`] for a verified state.
+    pub fn try_from_bytes(
+        hooks: &H,
+        data: &[u8],
+        mac_key: &[u8; MAC_KEY_SIZE],
+    ) -> Result<Self, StateError> {
+        let body_size = data
+            .len()
+            .checked_sub(32)
+            .ok_or(StateError::BufferTooShort)?;
+        let (data, expected_mac) = data.split_at(body_size);
+        let diff = mac::<H, T>(hooks, mac_key, data)
+            .iter()
+            .zip(expected_mac)
+            .fold(0, |diff, (a, b)| diff | (a ^ b));
+        if diff != 0 {
+            return Err(StateError::InvalidMac);
+        }
+
+        let mut reader = Reader(data);
+        let tag = reader.take(1)?[0];
+        let vk_digest = reader.take(32)?.try_into().unwrap();
+        let raw_proof: [u8; PROOF_SIZE] = reader.take(PROOF_SIZE)?.try_into().unwrap();
+        Proof::<H>::try_from(&raw_proof[..]).map_err(|_| StateError::InvalidProof)?;
+        let num_public_inputs = u32::from_be_bytes(reader.take(4)?.try_into().unwrap());
+        let public_inputs = (0..num_public_inputs)
+            .map(|_| Ok(reader.array()?.into_u256()))
+            .collect::<Result<Vec<U256>, StateError>>()?;
+
+        let stage = STAGES
+            .get(tag as usize)
+            .ok_or(StateError::UnknownStage { tag })?;
+        let progress = match stage {
+            Stage::Challenges => Progress::Challenges,
+            Stage::Widgets => Progress::Widgets(reader.challenge_round()?),
+            Stage::Quotient => {
+                Progress::Quotient(reader.challenge_round()?, reader.widget_round()?)
+            }
+            Stage::Msm => Progress::Msm(
+                reader.challenge_round()?,
+                reader.fr()?,
+                reader.nu_challenges()?,
+            ),
+            Stage::Pairing => Progress::Pairing(
+                PairingAccumulator::try_from(reader.take(ACCUMULATOR_SIZE)?)
+                    .map_err(|_| StateError::InvalidPoint)?,
+            ),
+            Stage::Verified => return Err(StateError::VerifiedState),
+        };
+
+        if !reader.0.is_empty() {
+            return Err(StateError::TrailingBytes);
+        }
+
+        Ok(Self {
+            vk_digest,
+            raw_proof,
+            public_inputs,
+            progress,
+            transcript: PhantomData,
+        })
+    }
+}
+
+// Binds a state to the key it was created with.
+fn vk_digest<H: VerifierHooks>(hooks: &H, vk: &PreparedVerificationKey<H>) -> [u8; 32] {
+    let mut transcript = Keccak256Transcript::<H>::new()
+        .chain_update(vk.circuit_type.to_be_bytes())
+        .chain_update(vk.circuit_size.to_be_bytes())
+        .chain_update(vk.num_public_inputs.to_be_bytes())
+        .chain_update([vk.contains_recursive_proof as u8])
+        .chain_update(vk.recursive_proof_indices.to_be_bytes())
+        .chain_update(vk.work_root.into_bytes())
+        .chain_update(vk.work_root_inverse.into_bytes())
+        .chain_update(vk.domain_inverse.into_bytes());
+    for base in vk.fixed_bases() {
+        transcript.update(base.x.into_bytes());
+        transcript.update(base.y.into_bytes());
+    }
+    transcript.finalize(hooks)
+}
+
+// Keccak256 isn't subject to length extension, so prefixing the key is a sound MAC. The
+// transcript identifier is length prefixed, for it not to run into the state bytes.
+fn mac<H: VerifierHooks, T: Transcript<H>>(
+    hooks: &H,
+    key: &[u8; MAC_KEY_SIZE],
+    data: &[u8],
+) -> [u8; 32] {
+    Keccak256Transcript::<H>::new()
+        .chain_update(key)
+        .chain_update((T::ID.len() as u32).to_be_bytes())
+        .chain_update(T::ID)
+        .chain_update(data)
+        .finalize(hooks)
+}
+
+fn write_challenge_round<H: CurveHooks>(out: &mut Vec<u8>, round: &ChallengeRound<H>) {
+    match round.recursive_points {
+        None => out.push(0),
+        Some((p1, p2)) => {
+            out.push(1);
+            for point in [p1, p2] {
+                out.extend_from_slice(&point.x.into_bytes());
+                out.extend_from_slice(&point.y.into_bytes());
+            }
+        }
+    }
+    for value in [round.eta, round.beta, round.gamma, round.alpha, round.zeta] {
+        out.extend_from_slice(&value.into_bytes());
+    }
+    out.extend_from_slice(&round.c_current);
+}
+
+fn write_widget_round(out: &mut Vec<u8>, round: &WidgetRound) {
+    for value in [
+        round.public_input_delta,
+        round.plookup_delta,
+        round.zero_poly,
+        round.zero_poly_inverse,
+        round.l_start,
+        round.l_end,
+        round.permutation_identity,
+        round.plookup_identity,
+        round.arithmetic_identity,
+        round.sort_identity,
+        round.elliptic_identity,
+        round.aux_identity,
+    ] {
+        out.extend_from_slice(&value.into_bytes());
+    }
+}
+
+struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, size: usize) -> Result<&'a [u8], StateError> {
+        if self.0.len() < size {
+            return Err(StateError::BufferTooShort);
+        }
+        let (head, tail) = self.0.split_at(size);
+        self.0 = tail;
+        Ok(head)
+    }
+
+    fn array(&mut self) -> Result<[u8; 32], StateError> {
+        Ok(self.take(32)?.try_into().unwrap())
+    }
+
+    fn fr(&mut self) -> Result<Fr, StateError> {
+        Fr::from_bigint(self.array()?.into_u256()).ok_or(StateError::NotMember)
+    }
+
+    fn g1<H: CurveHooks>(&mut self) -> Result<G1<H>, StateError> {
+        read_g1_util::<H>(self.take(64)?, false, DecodingMode::Strict)
+            .map_err(|_| StateError::InvalidPoint)
+    }
+
+    fn challenge_round<H: CurveHooks>(&mut self) -> Result<ChallengeRound<H>, StateError> {
+        let recursive_points = match self.take(1)?[0] {
+            0 => None,
+            1 => Some((self.g1::<H>()?, self.g1::<H>()?)),
+            _ => return Err(StateError::InvalidPoint),
+        };
+
+        Ok(ChallengeRound {
+            recursive_points,
+            eta: self.fr()?,
+            beta: self.fr()?,
+            gamma: self.fr()?,
+            alpha: self.fr()?,
+            zeta: self.fr()?,
+            c_current: self.array()?,
+        })
+    }
+
+    fn nu_challenges(&mut self) -> Result<NuChallenges, StateError> {
+        let mut c_v = [Fr::ZERO; 30];
+        for c in c_v.iter_mut() {
+            *c = self.fr()?;
+        }
+        Ok(NuChallenges::new(c_v, self.fr()?))
+    }
+
+    fn widget_round(&mut self) -> Result<WidgetRound, StateError> {
+        Ok(WidgetRound {
+            public_input_delta: self.fr()?,
+            plookup_delta: self.fr()?,
+            zero_poly: self.fr()?,
+            zero_poly_inverse: self.fr()?,
+            l_start: self.fr()?,
+            l_end: self.fr()?,
+            permutation_identity: self.fr()?,
+            plookup_identity: self.fr()?,
+            arithmetic_identity: self.fr()?,
+            sort_identity: self.fr()?,
+            elliptic_identity: self.fr()?,
+            aux_identity: self.fr()?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod should {
+    use super::*;
+    use crate::{
+        fixtures::{bb_output, bb_prepared_vk},
+        transcript::testing::OtherTranscript,
+        PublicInput, PUBS_SIZE,
+    };
+    use ark_ff::Field;
+    use rstest::rstest;
+
+    const MAC_KEY: [u8; MAC_KEY_SIZE] = [7; MAC_KEY_SIZE];
+
+    // Seals a hand-edited serialized state again.
+    fn reseal(mut bytes: Vec<u8>) -> Vec<u8> {
+        bytes.truncate(bytes.len() - 32);
+        let mac = mac::<(), Keccak256Transcript>(&(), &MAC_KEY, &bytes);
+        bytes.extend_from_slice(&mac);
+        bytes
+    }
+
+    fn state(bb_output: &[u8], vk: &PreparedVerificationKey<()>) -> VerificationState<()> {
+        let pubs: [PublicInput; 1] = bb_output[..PUBS_SIZE].try_into().unwrap();
+        VerificationState::new(&(), vk, &bb_output[PUBS_SIZE..], &pubs).unwrap()
+    }
+
+    #[rstest]
+    fn verify_a_valid_proof_one_stage_per_step(
+        bb_prepared_vk: PreparedVerificationKey<()>,
+        bb_output: &[u8],
+    ) {
+        let srs = PreparedSrs::default();
+        let mut state = state(bb_output, &bb_prepared_vk);
+
+        let mut stages = alloc::vec![state.stage()];
+        while state.stage() != Stage::Verified {
+            stages.push(state.step(&(), &bb_prepared_vk, &srs).unwrap());
+        }
+
+        assert_eq!(stages, STAGES);
+        assert_eq!(state.step(&(), &bb_prepared_vk, &srs), Ok(Stage::Verified));
+    }
+
+    #[rstest]
+    fn resume_a_serialized_state_at_every_stage(
+        bb_prepared_vk: PreparedVerificationKey<()>,
+        bb_output: &[u8],
+    ) {
+        let srs = PreparedSrs::default();
+        let mut state = state(bb_output, &bb_prepared_vk);
+
+        while state.stage() != Stage::Verified {
+            let resumed =
+                VerificationState::try_from_bytes(&(), &state.to_bytes(&(), &MAC_KEY), &MAC_KEY)
+                    .unwrap();
+            assert_eq!(resumed, state);
+
+            state = resumed;
+            state.step(&(), &bb_prepared_vk, &srs).unwrap();
+        }
+    }
+
+    #[rstest]
+    fn reach_the_pairing_points_of_a_single_run(
+        bb_prepared_vk: PreparedVerificationKey<()>,
+        bb_output: &[u8],
+    ) {
+        let proof = Proof::<()>::try_from(&bb_output[PUBS_SIZE..]).unwrap();
+        let pubs = [Fr::from_be_bytes_mod_order(&bb_output[..PUBS_SIZE])];
+        let expected = crate::verify_deferred(&(), &bb_prepared_vk, &proof, &pubs).unwrap();
+
+        let mut state = state(bb_output, &bb_prepared_vk);
+        while state.stage() != Stage::Pairing {
+            state
+                .step(&(), &bb_prepared_vk, &PreparedSrs::default())
+                .unwrap();
+        }
+
+        assert_eq!(state.progress, Progress::Pairing(expected));
+    }
+
+    mod reject {
+        use super::*;
+
+        #[rstest]
+        fn a_proof_with_a_wrong_public_input(
+            bb_prepared_vk: PreparedVerificationKey<()>,
+            bb_output: &[u8],
+        ) {
+            let srs = PreparedSrs::default();
+            let mut output = bb_output.to_vec();
+            output[PUBS_SIZE - 1] ^= 1;
+            let mut state = state(&output, &bb_prepared_vk);
+
+            while state.stage() != Stage::Pairing {
+                state.step(&(), &bb_prepared_vk, &srs).unwrap();
+            }
+
+            assert_eq!(
+                state.step(&(), &bb_prepared_vk, &srs),
+                Err(VerifyError::VerificationError)
+            );
+            assert_eq!(state.stage(), Stage::Pairing);
+        }
+
+        #[rstest]
+        fn a_step_with_another_vk(bb_prepared_vk: PreparedVerificationKey<()>, bb_output: &[u8]) {
+            let mut state = state(bb_output, &bb_prepared_vk);
+            let mut other_vk = bb_prepared_vk;
+            other_vk.q_1 = other_vk.q_2;
+
+            assert_eq!(
+                state.step(&(), &other_vk, &PreparedSrs::default()),
+                Err(VerifyError::KeyError)
+            );
+        }
+
+        #[rstest]
+        fn a_step_with_another_domain(
+            bb_prepared_vk: PreparedVerificationKey<()>,
+            bb_output: &[u8],
+        ) {
+            let mut state = state(bb_output, &bb_prepared_vk);
+            let mut other_vk = bb_prepared_vk;
+            other_vk.work_root = other_vk.work_root.square();
+
+            assert_eq!(
+                state.step(&(), &other_vk, &PreparedSrs::default()),
+                Err(VerifyError::KeyError)
+            );
+        }
+
+        #[rstest]
+        fn a_state_with_an_unknown_stage(
+            bb_prepared_vk: PreparedVerificationKey<()>,
+            bb_output: &[u8],
+        ) {
+            let mut bytes = state(bb_output, &bb_prepared_vk).to_bytes(&(), &MAC_KEY);
+            bytes[0] = STAGES.len() as u8;
+
+            assert_eq!(
+                VerificationState::<()>::try_from_bytes(&(), &reseal(bytes), &MAC_KEY),
+                Err(StateError::UnknownStage {
+                    tag: STAGES.len() as u8
+                })
+            );
+        }
+
+        #[rstest]
+        fn a_truncated_state(bb_prepared_vk: PreparedVerificationKey<()>, bb_output: &[u8]) {
+            let mut state = state(bb_output, &bb_prepared_vk);
+            state
+                .step(&(), &bb_prepared_vk, &PreparedSrs::default())
+                .unwrap();
+            let bytes = state.to_bytes(&(), &MAC_KEY);
+            let (body, mac) = bytes.split_at(bytes.len() - 32);
+
+            assert_eq!(
+                VerificationState::<()>::try_from_bytes(&(), &bytes[..31], &MAC_KEY),
+                Err(StateError::BufferTooShort)
+            );
+            assert_eq!(
+                VerificationState::<()>::try_from_bytes(
+                    &(),
+                    &reseal([&body[..body.len() - 1], mac].concat()),
+                    &MAC_KEY
+                ),
+                Err(StateError::BufferTooShort)
+            );
+            assert_eq!(
+                VerificationState::<()>::try_from_bytes(
+                    &(),
+                    &reseal([body, &[0u8][..], mac].concat()),
+                    &MAC_KEY
+                ),
+                Err(StateError::TrailingBytes)
+            );
+        }
+
+        #[rstest]
+        fn a_verified_state(bb_prepared_vk: PreparedVerificationKey<()>, bb_output: &[u8]) {
+            let mut state = state(bb_output, &bb_prepared_vk);
+            state
+                .run(&(), &bb_prepared_vk, &PreparedSrs::default())
+                .unwrap();
+
+            assert_eq!(
+                VerificationState::<()>::try_from_bytes(
+                    &(),
+                    &state.to_bytes(&(), &MAC_KEY),
+                    &MAC_KEY
+                ),
+                Err(StateError::VerifiedState)
+            );
+        }
+
+        #[rstest]
+        fn a_state_skipping_to_the_verified_stage(
+            bb_prepared_vk: PreparedVerificationKey<()>,
+            bb_output: &[u8],
+        ) {
+            let mut bytes = state(bb_output, &bb_prepared_vk).to_bytes(&(), &MAC_KEY);
+            bytes[0] = Stage::Verified as u8;
+
+            assert_eq!(
+                VerificationState::<()>::try_from_bytes(&(), &bytes, &MAC_KEY),
+                Err(StateError::InvalidMac)
+            );
+            assert_eq!(
+                VerificationState::<()>::try_from_bytes(&(), &reseal(bytes), &MAC_KEY),
+                Err(StateError::VerifiedState)
+            );
+        }
+
+        #[rstest]
+        fn a_state_with_tampered_progress(
+            bb_prepared_vk: PreparedVerificationKey<()>,
+            bb_output: &[u8],
+        ) {
+            let mut state = state(bb_output, &bb_prepared_vk);
+            while state.stage() != Stage::Pairing {
+                state
+                    .step(&(), &bb_prepared_vk, &PreparedSrs::default())
+                    .unwrap();
+            }
+            let mut bytes = state.to_bytes(&(), &MAC_KEY);
+            let accumulator_offset = bytes.len() - 32 - ACCUMULATOR_SIZE;
+            bytes[accumulator_offset + 31] ^= 1;
+
+            assert_eq!(
+                VerificationState::<()>::try_from_bytes(&(), &bytes, &MAC_KEY),
+                Err(StateError::InvalidMac)
+            );
+        }
+
+        #[rstest]
+        fn a_state_resumed_with_another_transcript(
+            bb_prepared_vk: PreparedVerificationKey<()>,
+            bb_output: &[u8],
+        ) {
+            let bytes = state(bb_output, &bb_prepared_vk).to_bytes(&(), &MAC_KEY);
+
+            assert_eq!(
+                VerificationState::<(), OtherTranscript>::try_from_bytes(&(), &bytes, &MAC_KEY),
+                Err(StateError::InvalidMac)
+            );
+        }
+
+        #[rstest]
+        fn a_state_sealed_with_another_key(
+            bb_prepared_vk: PreparedVerificationKey<()>,
+            bb_output: &[u8],
+        ) {
+            let bytes = state(bb_output, &bb_prepared_vk).to_bytes(&(), &[8; MAC_KEY_SIZE]);
+
+            assert_eq!(
+                VerificationState::<()>::try_from_bytes(&(), &bytes, &MAC_KEY),
+                Err(StateError::InvalidMac)
+            );
+        }
+    }
+}
@@ -0,0 +1,119 @@
+// Copyright 2024 Horizen Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0 or MIT
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use alloc::vec::Vec;
+
+use ark_bn254_ext::CurveHooks;
+
+use crate::{
+    check_public_input_number, compute_verification_trace,
+    errors::VerifyError,
+    hooks::VerifierHooks,
+    key::{PreparedVerificationKey, VerificationKey},
+    pairing_check,
+    proof::{PreparedProof, Proof},
+    utils::IntoU256,
+    Fr, Keccak256Transcript, PreparedSrs, Public, Transcript, G1, U256,
+};
+
+/// Every intermediate value computed while verifying a proof.
+///
+/// Values are named after the Solidity verifier memory locations, so that a trace can be
+/// diffed against a run of the contract to spot transcript or encoding mismatches.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VerificationTrace<H: CurveHooks> {
+    // Fiat-Shamir challenges
+    pub eta: Fr,
+    pub beta: Fr,
+    pub gamma: Fr,
+    pub alpha: Fr,
+    pub zeta: Fr,
+    pub c_v: [Fr; 30],
+    pub c_u: Fr,
+
+    // Permutation and plookup deltas
+    pub public_input_delta: Fr,
+    pub plookup_delta: Fr,
+
+    // Lagrange and vanishing polynomial terms
+    pub zero_poly: Fr,
+    pub zero_poly_inverse: Fr,
+    pub l_start: Fr,
+    pub l_end: Fr,
+
+    // Widget identities
+    pub permutation_identity: Fr,
+    pub plookup_identity: Fr,
+    pub arithmetic_identity: Fr,
+    pub sort_identity: Fr,
+    pub elliptic_identity: Fr,
+    pub aux_identity: Fr,
+
+    pub quotient_eval: Fr,
+
+    // Pairing points, with `pairing_lhs` already negated
+    pub pairing_lhs: G1<H>,
+    pub pairing_rhs: G1<H>,
+
+    /// Outcome of the final pairing check.
+    pub verified: bool,
+}
+
+/// Same as [`crate::verify`], but returns the [`VerificationTrace`] of the run instead of
+/// just failing with [`VerifyError::VerificationError`] when the pairing check doesn't hold.
+///
+/// Errors are still returned when the inputs can't be decoded. Only UltraPlonk keys are
+/// traced: Standard and Turbo ones are rejected with [`VerifyError::KeyError`].
+pub fn verify_with_trace<H: VerifierHooks + Default>(
+    raw_vk: &[u8],
+    raw_proof: &[u8],
+    pubs: &Public,
+) -> Result<VerificationTrace<H>, VerifyError> {
+    verify_with_trace_and_transcript::<H, Keccak256Transcript<H>>(raw_vk, raw_proof, pubs)
+}
+
+/// Same as [`verify_with_trace`], but the Fiat-Shamir challenges are derived with the `T`
+/// transcript: it must be the one the proof was generated with.
+pub fn verify_with_trace_and_transcript<H: VerifierHooks + Default, T: Transcript<H>>(
+    raw_vk: &[u8],
+    raw_proof: &[u8],
+    pubs: &Public,
+) -> Result<VerificationTrace<H>, VerifyError> {
+    let vk =
+        VerificationKey::<H>::try_from_solidity_bytes(raw_vk).map_err(|_| VerifyError::KeyError)?;
+    let prepared_vk =
+        PreparedVerificationKey::<H>::try_from(&vk).map_err(|_| VerifyError::KeyError)?;
+
+    let proof = Proof::<H>::try_from(raw_proof).map_err(|_| VerifyError::InvalidProofError)?;
+    let proof = PreparedProof::try_from(&proof).map_err(|_| VerifyError::InvalidProofError)?;
+
+    check_public_input_number(vk.num_public_inputs, pubs)?;
+    let public_inputs = &pubs
+        .iter()
+        .map(|pi_bytes| pi_bytes.into_u256())
+        .collect::<Vec<U256>>();
+
+    let hooks = H::default();
+    let mut trace =
+        compute_verification_trace::<H, T>(&hooks, &prepared_vk, &proof, public_inputs)?;
+    trace.verified = pairing_check(
+        &hooks,
+        trace.pairing_lhs,
+        trace.pairing_rhs,
+        &PreparedSrs::default(),
+    )?;
+
+    Ok(trace)
+}
@@ -0,0 +1,200 @@
+// Copyright 2024 Horizen Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0 or MIT
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Hashes that the Fiat-Shamir challenges can be derived with.
+//!
+//! Every challenge is the hash of the previous one followed by the round data, serialized
+//! as 32 bytes big-endian words exactly as the Solidity verifier does. Only the hash
+//! changes between transcripts: [`Keccak256Transcript`] is the one of the proofs that bb
+//! verifies on-chain.
+//!
+//! bb hashes the transcript of its recursive UltraPlonk proofs with Pedersen and Blake3s,
+//! which this crate doesn't implement yet: those proofs can't be verified, but the
+//! [`Transcript`] trait is where such a transcript would plug in.
+//!
+//! The Keccak-256 implementation can be supplied by the embedder through [`KeccakHooks`],
+//! in the same way [`crate::VerifierHooks`] supplies the curve operations: a Substrate
+//! runtime can forward it to a host function and a zkVM guest to its Keccak precompile.
+//! The hooks instance the verification is called with is the one challenges are hashed
+//! with, and each transcript receives it when a challenge is finalized.
+
+use alloc::vec::Vec;
+
+use sha3::{Digest, Keccak256};
+
+/// A Fiat-Shamir challenge hash, computed through the `K` hooks instance when it relies on
+/// one.
+pub trait Transcript<K: ?Sized = ()>: Sized {
+    /// Names the transcript where the challenges it derived are stored, e.g. in a serialized
+    /// [`crate::stepwise::VerificationState`], which is bound to the transcript it was
+    /// created with.
+    const ID: &'static str;
+
+    /// Starts hashing a new challenge.
+    fn new() -> Self;
+
+    /// Appends `data` to the hashed bytes.
+    fn update(&mut self, data: impl AsRef<[u8]>);
+
+    /// Returns the challenge.
+    fn finalize(self, hooks: &K) -> [u8; 32];
+
+    /// Same as [`Transcript::update`], but consumes and returns `self`.
+    fn chain_update(mut self, data: impl AsRef<[u8]>) -> Self {
+        self.update(data);
+        self
+    }
+}
+
+/// The bytes of a challenge being hashed by a [`KeccakHooks`] implementation.
+pub trait KeccakState: Default {
+    /// Appends `data` to the hashed bytes.
+    fn absorb(&mut self, data: &[u8]);
+}
+
+/// Hashes the bytes as they come, with no buffering.
+impl KeccakState for Keccak256 {
+    fn absorb(&mut self, data: &[u8]) {
+        Digest::update(self, data);
+    }
+}
+
+/// Buffers the bytes, for implementations that hash a whole message in a single call.
+impl KeccakState for Vec<u8> {
+    fn absorb(&mut self, data: &[u8]) {
+        self.extend_from_slice(data);
+    }
+}
+
+/// The Keccak-256 implementation used by [`Keccak256Transcript`].
+///
+/// A host function or a precompile usually hashes a whole message at once: such hooks take
+/// `Vec<u8>` as [`KeccakHooks::KeccakState`], so that the boundary is crossed once per
+/// challenge.
+pub trait KeccakHooks {
+    /// Where the bytes of a challenge are accumulated until it's finalized.
+    type KeccakState: KeccakState;
+
+    /// Returns the Keccak-256 digest of the bytes accumulated in `state`.
+    fn keccak_256(&self, state: Self::KeccakState) -> [u8; 32];
+}
+
+/// The software implementation of the `sha3` crate, streaming the bytes into the hash.
+impl KeccakHooks for () {
+    type KeccakState = Keccak256;
+
+    fn keccak_256(&self, state: Keccak256) -> [u8; 32] {
+        state.finalize().into()
+    }
+}
+
+/// The Keccak-256 transcript of the Solidity verifier, and of the proofs written by
+/// `bb prove`, hashed through the `K` hooks.
+pub struct Keccak256Transcript<K: KeccakHooks = ()>(K::KeccakState);
+
+impl<K: KeccakHooks> Transcript<K> for Keccak256Transcript<K> {
+    const ID: &'static str = "keccak256";
+
+    fn new() -> Self {
+        Keccak256Transcript(K::KeccakState::default())
+    }
+
+    fn update(&mut self, data: impl AsRef<[u8]>) {
+        self.0.absorb(data.as_ref());
+    }
+
+    fn finalize(self, hooks: &K) -> [u8; 32] {
+        hooks.keccak_256(self.0)
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod testing {
+    //! A transcript for the tests of the crate, that no proof was generated with.
+
+    use super::*;
+
+    /// Keccak-256, with every challenge prefixed by a domain separator: it derives other
+    /// challenges than [`Keccak256Transcript`] from the same bytes.
+    pub(crate) struct OtherTranscript(Keccak256);
+
+    impl<K: ?Sized> Transcript<K> for OtherTranscript {
+        const ID: &'static str = "other";
+
+        fn new() -> Self {
+            OtherTranscript(Keccak256::new_with_prefix(b"other transcript"))
+        }
+
+        fn update(&mut self, data: impl AsRef<[u8]>) {
+            Digest::update(&mut self.0, data);
+        }
+
+        fn finalize(self, _hooks: &K) -> [u8; 32] {
+            self.0.finalize().into()
+        }
+    }
+}
+
+#[cfg(test)]
+mod should {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    fn hash_the_concatenation_of_the_updates_with_keccak() {
+        let chained = <Keccak256Transcript>::new()
+            .chain_update([1u8, 2])
+            .chain_update([3u8])
+            .finalize(&());
+
+        assert_eq!(
+            chained,
+            <[u8; 32]>::from(Keccak256::new().chain_update([1u8, 2, 3]).finalize())
+        );
+    }
+
+    #[rstest]
+    fn hash_with_the_keccak_hooks_instance() {
+        use core::sync::atomic::{AtomicUsize, Ordering};
+
+        #[derive(Default)]
+        struct CountingHooks {
+            calls: AtomicUsize,
+        }
+
+        impl KeccakHooks for CountingHooks {
+            type KeccakState = Vec<u8>;
+
+            fn keccak_256(&self, data: Vec<u8>) -> [u8; 32] {
+                self.calls.fetch_add(1, Ordering::Relaxed);
+                Keccak256::digest(&data).into()
+            }
+        }
+
+        let hooks = CountingHooks::default();
+        let challenge = Keccak256Transcript::<CountingHooks>::new()
+            .chain_update([1u8, 2])
+            .chain_update([3u8])
+            .finalize(&hooks);
+
+        assert_eq!(
+            challenge,
+            <Keccak256Transcript>::new()
+                .chain_update([1u8, 2, 3])
+                .finalize(&())
+        );
+        assert_eq!(hooks.calls.load(Ordering::Relaxed), 1);
+    }
+}
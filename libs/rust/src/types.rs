@@ -21,3 +21,16 @@ pub type U256 = ark_ff::BigInteger256;
 pub type G1<H> = G1Affine<H>;
 pub type G2<H> = G2Affine<H>;
 pub type Bn254<H> = ark_bn254_ext::Bn254<H>;
+
+/// How strictly proofs and keys are decoded.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum DecodingMode {
+    /// Every value has a single valid encoding: field elements must be lower than the
+    /// modulus and points can't be encoded as `(0, 0)`. Decoded values can then be safely
+    /// identified by the hash of their bytes.
+    #[default]
+    Strict,
+    /// Field elements are reduced modulo `p`, so that several byte strings can decode to
+    /// the same value. This is how proofs used to be decoded.
+    Lenient,
+}
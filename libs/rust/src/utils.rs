@@ -16,11 +16,11 @@
 
 use crate::{
     errors::{FieldError, GroupError},
-    types::G1,
+    types::{DecodingMode, G1},
     Fq, Fr, U256,
 };
 use ark_bn254_ext::CurveHooks;
-use ark_ff::PrimeField;
+use ark_ff::{BigInteger, PrimeField};
 
 pub(crate) trait IntoFq {
     fn into_fq(self) -> Fq;
@@ -147,7 +147,11 @@ impl IntoBytes for Fq {
 }
 
 // Parsing utility for points in G1
-pub(crate) fn read_g1_util<H: CurveHooks>(data: &[u8], reverse: bool) -> Result<G1<H>, GroupError> {
+pub(crate) fn read_g1_util<H: CurveHooks>(
+    data: &[u8],
+    reverse: bool,
+    mode: DecodingMode,
+) -> Result<G1<H>, GroupError> {
     if data.len() != 64 {
         return Err(GroupError::InvalidSliceLength {
             expected_length: 64,
@@ -155,15 +159,20 @@ pub(crate) fn read_g1_util<H: CurveHooks>(data: &[u8], reverse: bool) -> Result<
         });
     }
 
+    // The point at infinity has no affine encoding: (0, 0) is the usual placeholder for it
+    if mode == DecodingMode::Strict && data.iter().all(|&b| b == 0) {
+        return Err(GroupError::PointAtInfinity);
+    }
+
     let x: Fq;
     let y: Fq;
 
     if reverse {
-        y = read_fq_util(&data[0..32]).expect("Should always succeed");
-        x = read_fq_util(&data[32..64]).expect("Should always succeed");
+        y = read_fq_util(&data[0..32], mode).map_err(|_| GroupError::NotMember)?;
+        x = read_fq_util(&data[32..64], mode).map_err(|_| GroupError::NotMember)?;
     } else {
-        x = read_fq_util(&data[0..32]).expect("Should always succeed");
-        y = read_fq_util(&data[32..64]).expect("Should always succeed");
+        x = read_fq_util(&data[0..32], mode).map_err(|_| GroupError::NotMember)?;
+        y = read_fq_util(&data[32..64], mode).map_err(|_| GroupError::NotMember)?;
     }
 
     let point = G1::new_unchecked(x, y);
@@ -177,7 +186,7 @@ pub(crate) fn read_g1_util<H: CurveHooks>(data: &[u8], reverse: bool) -> Result<
 }
 
 // Utility function for parsing points in G2
-pub(crate) fn read_fq_util(data: &[u8]) -> Result<Fq, FieldError> {
+pub(crate) fn read_fq_util(data: &[u8], mode: DecodingMode) -> Result<Fq, FieldError> {
     if data.len() != 32 {
         return Err(FieldError::InvalidSliceLength {
             expected_length: 32,
@@ -193,5 +202,28 @@ pub(crate) fn read_fq_util(data: &[u8]) -> Result<Fq, FieldError> {
 
     let bigint = U256::new(limbs);
 
-    Ok(bigint.into_fq())
+    match mode {
+        DecodingMode::Strict => Fq::from_bigint(bigint).ok_or(FieldError::NotMember),
+        DecodingMode::Lenient => Ok(bigint.into_fq()),
+    }
+}
+
+// Computes Σ limbs[i] << (limb_bits * i), rejecting oversized limbs and values not in Fq.
+pub(crate) fn combine_limbs(limbs: &[U256], limb_bits: usize) -> Option<Fq> {
+    let mut value = U256::from(0u64);
+    for limb in limbs.iter().rev() {
+        if limb.num_bits() as usize > limb_bits {
+            return None;
+        }
+        for _ in 0..limb_bits {
+            if value.mul2() {
+                return None;
+            }
+        }
+        if value.add_with_carry(limb) {
+            return None;
+        }
+    }
+
+    Fq::from_bigint(value)
 }